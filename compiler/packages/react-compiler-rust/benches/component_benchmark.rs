@@ -0,0 +1,119 @@
+//! Benchmark suite for the full pipeline on realistic component shapes
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use react_compiler_rust::compile;
+use oxc_span::SourceType;
+
+/// Compile `code` repeatedly, reporting throughput in source bytes.
+fn bench_component(c: &mut Criterion, name: &str, code: &str) {
+    let mut group = c.benchmark_group("components");
+    group.throughput(Throughput::Bytes(code.len() as u64));
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            let result = compile(black_box(code), SourceType::jsx());
+            black_box(result).unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn benchmark_list_rendering(c: &mut Criterion) {
+    let code = r#"
+function TodoList(props) {
+    const items = props.items;
+    const filter = props.filter;
+    const rows = [];
+    let done = 0;
+    for (let i = 0; i < items.length; i++) {
+        const item = items[i];
+        if (item.done) {
+            done += 1;
+        }
+        if (filter === "all" || (filter === "done") === item.done) {
+            const className = item.done ? "todo done" : "todo";
+            rows.push(
+                <li key={item.id} className={className}>
+                    <span>{item.title}</span>
+                    <small>{item.owner ?? "unassigned"}</small>
+                </li>
+            );
+        }
+    }
+    const summary = done + " of " + items.length + " done";
+    return (
+        <section>
+            <h2>{props.title}</h2>
+            <ul>{rows}</ul>
+            <footer>{summary}</footer>
+        </section>
+    );
+}
+"#;
+
+    bench_component(c, "list_rendering", code);
+}
+
+fn benchmark_form_handlers(c: &mut Criterion) {
+    let mut code = String::from("function SignupForm(props) {\n    const values = props.values;\n    const errors = props.errors;\n");
+    let fields = ["name", "email", "password", "confirm", "street", "city", "zip", "country", "phone", "company"];
+    for field in fields {
+        code.push_str(&format!(
+            "    const {field}Change = props.onChange.bind(null, \"{field}\");\n    const {field}Blur = props.onBlur.bind(null, \"{field}\");\n    const {field}Error = errors.{field} ? errors.{field}.message : null;\n    const {field}Input = <label>{{\"{field}\"}}<input value={{values.{field}}} onChange={{{field}Change}} onBlur={{{field}Blur}} />{{{field}Error}}</label>;\n"
+        ));
+    }
+    let inputs: Vec<_> = fields.iter().map(|field| format!("{{{field}Input}}")).collect();
+    code.push_str(&format!(
+        "    return <form onSubmit={{props.onSubmit}}>{}</form>;\n}}\n",
+        inputs.join("")
+    ));
+
+    bench_component(c, "form_handlers", &code);
+}
+
+fn benchmark_nested_conditionals(c: &mut Criterion) {
+    let code = r#"
+function Dashboard(props) {
+    const user = props.user;
+    if (props.loading) {
+        return <Spinner size={props.compact ? "small" : "large"} />;
+    }
+    if (props.error) {
+        return <ErrorBanner message={props.error.message} retry={props.onRetry} />;
+    }
+    let body;
+    if (user.role === "admin") {
+        if (props.settingsOpen) {
+            body = <AdminSettings user={user} tab={props.tab ?? "general"} />;
+        } else if (props.reportsOpen) {
+            body = <Reports range={props.range} detailed={props.detailed && user.canExport} />;
+        } else {
+            body = <AdminHome user={user} />;
+        }
+    } else if (user.role === "editor") {
+        const drafts = props.drafts;
+        body = drafts.length > 0 ? <Drafts items={drafts} /> : <EmptyState kind="drafts" />;
+    } else {
+        body = user.verified ? (user.premium ? <Premium user={user} /> : <Basic user={user} />) : <Verify email={user.email} />;
+    }
+    const greeting = user.name ? "Welcome back, " + user.name : "Welcome";
+    return (
+        <main>
+            <header>{greeting}</header>
+            {body}
+        </main>
+    );
+}
+"#;
+
+    bench_component(c, "nested_conditionals", code);
+}
+
+criterion_group!(
+    name = component_benchmarks;
+    config = Criterion::default().sample_size(50);
+    targets =
+        benchmark_list_rendering,
+        benchmark_form_handlers,
+        benchmark_nested_conditionals
+);
+criterion_main!(component_benchmarks);
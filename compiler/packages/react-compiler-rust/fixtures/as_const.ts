@@ -0,0 +1,5 @@
+function useConfig(scale: number) {
+    const config = { a: 1, sizes: [8, 16] as const } as const;
+    const factor = <number>scale;
+    return config.a * factor;
+}
@@ -0,0 +1,17 @@
+class Greeting extends React.Component {
+  static defaultProps = { name: "world" };
+
+  handleClick() {
+    this.setState({ clicked: true });
+  }
+
+  render() {
+    const name = this.props.name;
+    const className = this.state.clicked ? "greeting clicked" : "greeting";
+    return (
+      <h1 className={className} onClick={this.handleClick}>
+        Hello {name}
+      </h1>
+    );
+  }
+}
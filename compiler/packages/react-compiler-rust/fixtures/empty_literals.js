@@ -0,0 +1,8 @@
+function emptyLiterals(flag) {
+  let obj = {};
+  let arr = [];
+  if (flag) {
+    obj = { items: arr };
+  }
+  return obj;
+}
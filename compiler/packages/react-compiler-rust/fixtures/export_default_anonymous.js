@@ -0,0 +1,5 @@
+export default function (props) {
+    const label = props.label;
+    const style = { color: props.color, label };
+    return style;
+}
@@ -0,0 +1,13 @@
+export function Button(props) {
+    const label = props.label.toUpperCase();
+    return { type: "button", label };
+}
+
+function helper(value) {
+    return value * 2;
+}
+
+export default function App(props) {
+    const doubled = helper(props.count);
+    return { button: Button({ label: props.title }), doubled };
+}
@@ -0,0 +1,6 @@
+function ThemedButton(props) {
+    const label = props.label;
+    const theme = useContext(ThemeContext);
+    const style = { color: theme.color, label };
+    return style;
+}
@@ -0,0 +1,22 @@
+interface BadgeProps {
+    count: number;
+    label: string;
+}
+
+declare const DEFAULT_LABEL: string;
+
+function Badge(props: BadgeProps) {
+    interface Shown {
+        text: string;
+    }
+    declare function format(count: number): string;
+    enum Tone {
+        Quiet,
+        Loud = 10,
+        Alert,
+        Custom = "custom",
+    }
+    const tone = props.count > 99 ? Tone.Alert : Tone.Quiet;
+    const shown: Shown = { text: props.label + ": " + props.count };
+    return { shown, tone };
+}
@@ -0,0 +1,16 @@
+function Greeting(props) {
+  const label = props.label;
+  return (
+    <div className="greeting" {...props.rest}>
+      <h1 title="Hello &amp; welcome">
+        Hello,   {label}!
+      </h1>
+      <Layout.Header />
+      <>
+        <input disabled />
+        {/* no children here */}
+        {props.children}
+      </>
+    </div>
+  );
+}
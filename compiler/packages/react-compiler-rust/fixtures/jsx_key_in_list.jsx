@@ -0,0 +1,13 @@
+function TodoList(props) {
+  const ids = props.ids;
+  const todos = props.todos;
+  const rows = [];
+  for (let i = 0; i < todos.length; i++) {
+    rows.push(<TodoItem key={ids[i]} ref={props.itemRef} todo={todos[i]} />);
+  }
+  return <ul>{rows}</ul>;
+}
+
+function Field(props) {
+  return <input {...props.inputProps} key={props.id} ref={props.inputRef} />;
+}
@@ -0,0 +1,17 @@
+function TodoItem(props) {
+  const todo = props.todo;
+  return (
+    <li key={todo.id} className="todo">
+      {todo.text}
+    </li>
+  );
+}
+
+function TodoList(props) {
+  return (
+    <ul>
+      <TodoItem todo={props.first} />
+      <TodoItem todo={props.second} />
+    </ul>
+  );
+}
@@ -0,0 +1,9 @@
+function countStrings(value, n) {
+    let total = 0;
+    let i = 0;
+    while (i < n) {
+        total = total + (typeof value === "string" ? 1 : 0);
+        i = i + 1;
+    }
+    return total;
+}
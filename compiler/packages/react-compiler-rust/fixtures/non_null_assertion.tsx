@@ -0,0 +1,5 @@
+function ItemList(props: { data?: { items: string[] } }) {
+    const data = props.data;
+    const items = data!.items;
+    return <ul>{items!.length}</ul>;
+}
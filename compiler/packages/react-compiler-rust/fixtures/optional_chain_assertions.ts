@@ -0,0 +1,7 @@
+function optionalChainAssertions(o: { b?: { c: number } } | null) {
+    const asserted = o?.b!.c;
+    const computed = o?.["b"]!["c"];
+    const cast = (o?.b as any)?.c;
+    const parenthesized = o ? (o?.b as any).c : 0;
+    return [asserted, computed, cast, parenthesized];
+}
@@ -0,0 +1,6 @@
+function Pair(props) {
+    const items = [props.x];
+    log(items);
+    const summary = { x: props.x };
+    return summary;
+}
@@ -0,0 +1,14 @@
+type Point = { x: number; y: number };
+interface Labeled {
+    label: string;
+}
+
+function strip(y: number | null, point: Point, items: string[]): Labeled {
+    type Local = string;
+    const x = (y as number)!;
+    const total: number = x + point.x;
+    const first = items[0] as Local;
+    const config = { scale: 2 } satisfies Record<string, number>;
+    const scaled = (<number>total) * config.scale;
+    return { label: first + scaled };
+}
@@ -0,0 +1,12 @@
+"use client";
+
+function Counter(props) {
+  const label = "Count: " + props.count;
+  return <button onClick={props.onIncrement}>{label}</button>;
+}
+
+function save(data) {
+  "use server";
+  const payload = { data };
+  return payload;
+}
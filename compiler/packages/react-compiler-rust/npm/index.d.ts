@@ -26,6 +26,37 @@ export function compile(source: string): CompileResult;
  */
 export function compileWithOptions(source: string, fileType?: string): CompileResult;
 
+/** Milliseconds spent in each compiler phase */
+export interface PhaseTimingsResult {
+  lowering: number;
+  ssa: number;
+  liveness: number;
+  scopes: number;
+  reactive: number;
+  codegen: number;
+}
+
+/** Result from compiling with per-phase timings */
+export interface TimedCompileResult {
+  /** The compiled output code */
+  code: string;
+  /** Whether compilation was successful */
+  success: boolean;
+  /** Error message if compilation failed */
+  error: string | null;
+  /** Time spent in each phase (absent if compilation failed) */
+  timings: PhaseTimingsResult | null;
+}
+
+/**
+ * Compile and report how long each compiler phase took, in milliseconds.
+ *
+ * @param source - The source code to compile
+ * @param fileType - File type: "js", "jsx", "ts", "tsx"
+ * @returns TimedCompileResult with compiled code and phase timings
+ */
+export function compileWithTimings(source: string, fileType?: string): TimedCompileResult;
+
 /**
  * Get version information
  */
@@ -10,15 +10,145 @@ use crate::hir::reactive_function::{
 };
 use crate::hir::reactive_scopes::ReactiveScopeResult;
 use crate::hir::scope::ScopeId;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 
+/// Options controlling the shape of the generated module.
+#[derive(Debug, Clone, Default)]
+pub struct CodegenOptions {
+    /// Module to import the `_c` memo cache hook from, e.g.
+    /// `"react/compiler-runtime"`. When set, the output starts with
+    /// `import { c as _c } from "<module>";`; otherwise callers must provide `_c`.
+    pub emit_runtime_import: Option<String>,
+    /// How JSX elements are emitted.
+    pub jsx_runtime: JsxRuntime,
+    /// Keep directives like `"use client"` and `"use server"`, at the top of
+    /// the module and at the start of each function that has them. React
+    /// Server Components rely on them to split client and server code.
+    pub preserve_directives: bool,
+    /// Keep the comments directly above each emitted top-level function or
+    /// class, such as JSDoc that type tooling reads. Other comments are
+    /// always dropped.
+    pub preserve_comments: bool,
+}
+
+/// The JSX transform to target, matching Babel's `runtime` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsxRuntime {
+    /// `React.createElement(type, props, ...children)`. `React` must be in scope.
+    #[default]
+    Classic,
+    /// `_jsx(type, { ...props, children }, key)` from `react/jsx-runtime`,
+    /// or `_jsxs` for multiple children. The `_jsx`, `_jsxs` and `_Fragment`
+    /// bindings must be in scope.
+    Automatic,
+}
+
+/// The import statement that brings the memo cache hook `_c` into scope.
+pub fn generate_runtime_import(module: &str) -> String {
+    let escaped = module.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("import {{ c as _c }} from \"{}\";\n", escaped)
+}
+
+/// Object keys that aren't valid identifiers, like JSX's `aria-label`, need quotes.
+fn format_property_key(key: &str) -> String {
+    let is_identifier = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Format a number the way JS's `Number.prototype.toString` does, so the
+/// emitted literal reads back as the same value: integers below 2^53 print
+/// as digits, and larger or tiny magnitudes switch to exponent form (`1e+30`).
+pub(crate) fn format_number(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if n == 0.0 {
+        return if n.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+    if n.fract() == 0.0 && n.abs() < 9007199254740992.0 {
+        return format!("{}", n as i64);
+    }
+
+    // `{:e}` gives the shortest round-tripping digits, e.g. `1.2345e-7`
+    let sign = if n < 0.0 { "-" } else { "" };
+    let scientific = format!("{:e}", n.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let point = exponent.parse::<i32>().unwrap() + 1;
+
+    let body = if k <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        format!("{}.{}", &digits[..point as usize], &digits[point as usize..])
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let exponent = point - 1;
+        let exponent_sign = if exponent < 0 { "-" } else { "+" };
+        let fraction = if k > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+        format!("{}{}e{}{}", &digits[..1], fraction, exponent_sign, exponent.abs())
+    };
+    format!("{}{}", sign, body)
+}
+
+fn pure_annotation(pure: bool) -> &'static str {
+    if pure { "/* @__PURE__ */ " } else { "" }
+}
+
 /// Generate JavaScript code from a ReactiveFunction
 pub fn generate_code(func: &ReactiveFunction, scopes: &ReactiveScopeResult) -> String {
+    generate_code_with_options(func, scopes, &CodegenOptions::default())
+}
+
+/// Generate JavaScript code from a ReactiveFunction, honoring the
+/// expression-level [`CodegenOptions`].
+pub fn generate_code_with_options(
+    func: &ReactiveFunction,
+    scopes: &ReactiveScopeResult,
+    options: &CodegenOptions,
+) -> String {
     let mut codegen = CodeGenerator::new(scopes);
+    codegen.jsx_runtime = options.jsx_runtime;
+    codegen.preserve_directives = options.preserve_directives;
     codegen.generate_function(func)
 }
 
+/// Generate JavaScript code for a ReactiveFunction as a class or object
+/// method named `key`, e.g. `render() { ... }`. The function's own name is
+/// not used.
+pub fn generate_method_with_options(
+    key: &str,
+    func: &ReactiveFunction,
+    scopes: &ReactiveScopeResult,
+    options: &CodegenOptions,
+) -> String {
+    let mut codegen = CodeGenerator::new(scopes);
+    codegen.jsx_runtime = options.jsx_runtime;
+    codegen.preserve_directives = options.preserve_directives;
+    codegen.generate_function_with_head(&method_head(key, func), func)
+}
+
+/// `key` with the method's `async` and `*` modifiers.
+fn method_head(key: &str, func: &ReactiveFunction) -> String {
+    let modifiers = match (func.is_async, func.generator) {
+        (true, true) => "async *",
+        (true, false) => "async ",
+        (false, true) => "*",
+        (false, false) => "",
+    };
+    format!("{}{}", modifiers, key)
+}
+
 struct CodeGenerator<'a> {
     output: String,
     indent: usize,
@@ -26,17 +156,23 @@ struct CodeGenerator<'a> {
     cache_size: usize,
     declared: HashSet<String>,
     declared_base_names: HashSet<String>,
+    /// Temporaries that are printed with their SSA version, see `generate_function`
+    versioned_temps: HashSet<String>,
     params: HashSet<String>,
+    /// Identifiers that are read somewhere in the function body
+    used: HashSet<Identifier>,
+    jsx_runtime: JsxRuntime,
+    preserve_directives: bool,
 }
 
 impl<'a> CodeGenerator<'a> {
     fn new(scopes: &'a ReactiveScopeResult) -> Self {
         // Calculate total cache size needed
         let cache_size = scopes.scopes.iter()
-            .map(|s| s.dependencies.len() + s.declarations.len())
+            .map(|s| s.cache_slots())
             .sum::<usize>()
             .max(1);
-            
+
         Self {
             output: String::new(),
             indent: 0,
@@ -44,18 +180,45 @@ impl<'a> CodeGenerator<'a> {
             cache_size,
             declared: HashSet::new(),
             declared_base_names: HashSet::new(),
+            versioned_temps: HashSet::new(),
             params: HashSet::new(),
+            used: HashSet::new(),
+            jsx_runtime: JsxRuntime::default(),
+            preserve_directives: false,
         }
     }
 
     fn generate_function(&mut self, func: &ReactiveFunction) -> String {
-        let name = func.name.as_deref().unwrap_or("anonymous");
-        
+        let keyword = match (func.is_async, func.generator) {
+            (true, true) => "async function*",
+            (true, false) => "async function",
+            (false, true) => "function*",
+            (false, false) => "function",
+        };
+        // Only `export default` declarations can be anonymous
+        let head = match &func.name {
+            Some(name) => format!("{} {}", keyword, name),
+            None => keyword.to_string(),
+        };
+        self.generate_function_with_head(&head, func)
+    }
+
+    /// Emit `func` as `<head>(params) { body }`, with the closing brace at
+    /// the current indentation.
+    fn generate_function_with_head(&mut self, head: &str, func: &ReactiveFunction) -> String {
         // Function header
         self.params = func.params.iter().map(|p| p.name.clone()).collect();
         let params_str: Vec<_> = func.params.iter().map(|p| self.identifier_name(p)).collect();
-        writeln!(self.output, "function {}({}) {{", name, params_str.join(", ")).unwrap();
+        writeln!(self.output, "{}({}) {{", head, params_str.join(", ")).unwrap();
         self.indent += 1;
+
+        // A directive only counts as one before any other statement
+        if self.preserve_directives {
+            for directive in &func.directives {
+                self.write_indent();
+                writeln!(self.output, "{};", directive).unwrap();
+            }
+        }
         
         // Add cache initialization if we have scopes
         if !self.scopes.scopes.is_empty() {
@@ -64,9 +227,22 @@ impl<'a> CodeGenerator<'a> {
         }
 
         // Hoist declarations
-        // Hoist declarations
+        let mut temp_assignments = HashMap::new();
         for stmt in &func.body {
             Self::collect_declarations(stmt, &mut self.declared, &mut self.declared_base_names);
+            Self::collect_temp_assignments(stmt, &mut temp_assignments);
+            Self::collect_uses(stmt, &mut self.used);
+        }
+
+        // A temporary with several SSA versions is a conditional's result, which
+        // every branch stores before the code after it reads it. Its versions
+        // become separate hoisted variables, like named ones. Code copied into
+        // several branches repeats the same version and can stay `const`.
+        for (name, versions) in temp_assignments {
+            if versions.len() > 1 {
+                self.declared.extend(versions.iter().map(|id| format!("{}_{}", id.name, id.id)));
+                self.versioned_temps.insert(name);
+            }
         }
         
         // Filter out params from declared to avoid re-declaration
@@ -81,17 +257,35 @@ impl<'a> CodeGenerator<'a> {
             writeln!(self.output, "let {};", sorted_vars.join(", ")).unwrap();
         }
         
-        // Generate body
-        for stmt in &func.body {
+        // Generate body. Falling off the end already returns `undefined`, so
+        // a bare `return` there is left out.
+        let body = match func.body.split_last() {
+            Some((ReactiveStatement::Return(None), rest)) => rest,
+            _ => &func.body[..],
+        };
+        for stmt in body {
             self.generate_statement(stmt);
         }
         
         self.indent -= 1;
+        self.write_indent();
         writeln!(self.output, "}}").unwrap();
         
         self.output.clone()
     }
 
+    /// Emit shorthand method syntax, `key(params) { body }`, indented to
+    /// continue the current line.
+    fn generate_method(&self, key: &str, func: &ReactiveFunction) -> String {
+        let scopes = ReactiveScopeResult::default();
+        let mut method = CodeGenerator::new(&scopes);
+        method.jsx_runtime = self.jsx_runtime;
+        method.indent = self.indent;
+        method.preserve_directives = self.preserve_directives;
+        let code = method.generate_function_with_head(&method_head(key, func), func);
+        code.trim_end().to_string()
+    }
+
     fn generate_statement(&mut self, stmt: &ReactiveStatement) {
         match stmt {
             ReactiveStatement::Instruction(instr) => {
@@ -122,8 +316,9 @@ impl<'a> CodeGenerator<'a> {
                 self.write_indent();
                 writeln!(self.output, "}}").unwrap();
             }
-            ReactiveStatement::While { test, body } => {
+            ReactiveStatement::While { test, body, label } => {
                 self.write_indent();
+                self.write_label(label);
                 writeln!(self.output, "while ({}) {{", self.identifier_name(test)).unwrap();
                 self.indent += 1;
                 for s in body {
@@ -133,13 +328,19 @@ impl<'a> CodeGenerator<'a> {
                 self.write_indent();
                 writeln!(self.output, "}}").unwrap();
             }
-            ReactiveStatement::Break => {
+            ReactiveStatement::Break(label) => {
                 self.write_indent();
-                writeln!(self.output, "break;").unwrap();
+                match label {
+                    Some(label) => writeln!(self.output, "break {};", label).unwrap(),
+                    None => writeln!(self.output, "break;").unwrap(),
+                }
             }
-            ReactiveStatement::Continue => {
+            ReactiveStatement::Continue(label) => {
                 self.write_indent();
-                writeln!(self.output, "continue;").unwrap();
+                match label {
+                    Some(label) => writeln!(self.output, "continue {};", label).unwrap(),
+                    None => writeln!(self.output, "continue;").unwrap(),
+                }
             }
             ReactiveStatement::Return(place) => {
                 self.write_indent();
@@ -149,15 +350,16 @@ impl<'a> CodeGenerator<'a> {
                     writeln!(self.output, "return;").unwrap();
                 }
             }
-            ReactiveStatement::Switch { test, cases } => {
+            ReactiveStatement::Switch { test, cases, label } => {
                 self.write_indent();
+                self.write_label(label);
                 writeln!(self.output, "switch ({}) {{", self.identifier_name(test)).unwrap();
                 self.indent += 1;
                 
                 for case in cases {
                     self.write_indent();
                     if let Some(label) = &case.label {
-                        writeln!(self.output, "case {}: {{", self.identifier_name(label)).unwrap();
+                        writeln!(self.output, "case {}: {{", self.generate_value(label)).unwrap();
                     } else {
                         writeln!(self.output, "default: {{").unwrap();
                     }
@@ -184,19 +386,36 @@ impl<'a> CodeGenerator<'a> {
         let rvalue = self.generate_value(&instr.value);
         
         // Skip trivial assignments (LoadLocal where source == dest name)
-        if let ReactiveValue::LoadLocal(src) = &instr.value {
-            if self.identifier_name(src) == lvalue {
-                return;
-            }
+        if let ReactiveValue::LoadLocal(src) = &instr.value
+            && self.identifier_name(src) == lvalue
+        {
+            return;
         }
         
         self.write_indent();
         
         // Use let for declarations, assignment for updates/temporaries
-        let is_temp = instr.lvalue.name.starts_with('t') && instr.lvalue.name[1..].chars().all(|c| c.is_ascii_digit());
+        let is_temp = instr.lvalue.temporary;
         let is_reserved = matches!(instr.lvalue.name.as_str(), "true" | "false" | "null" | "undefined");
         
-        if is_temp || is_reserved {
+        // Side-effecting expressions whose result is never read are emitted
+        // as plain expression statements instead of dead `const tN = ...` bindings.
+        let is_effect = matches!(
+            instr.value,
+            ReactiveValue::Call { .. }
+                | ReactiveValue::MethodCall { .. }
+                | ReactiveValue::PropertyStore { .. }
+                | ReactiveValue::ComputedStore { .. }
+                | ReactiveValue::PropertyDelete { .. }
+                | ReactiveValue::ComputedDelete { .. }
+                | ReactiveValue::Yield { .. }
+                | ReactiveValue::Await { .. }
+        );
+        if is_temp && is_effect && !self.used.contains(&instr.lvalue) {
+            writeln!(self.output, "{};", rvalue).unwrap();
+        } else if is_temp && self.declared.contains(&lvalue) {
+            writeln!(self.output, "{} = {};", lvalue, rvalue).unwrap();
+        } else if is_temp || is_reserved {
             writeln!(self.output, "const {} = {};", lvalue, rvalue).unwrap();
         } else if self.declared.contains(&lvalue) {
             writeln!(self.output, "{} = {};", lvalue, rvalue).unwrap();
@@ -209,13 +428,7 @@ impl<'a> CodeGenerator<'a> {
     fn generate_value(&self, value: &ReactiveValue) -> String {
         match value {
             ReactiveValue::Constant(c) => match c {
-                ConstantValue::Number(n) => {
-                    if n.fract() == 0.0 {
-                        format!("{}", *n as i64)
-                    } else {
-                        format!("{}", n)
-                    }
-                }
+                ConstantValue::Number(n) => format_number(*n),
                 ConstantValue::String(s) => {
                     let escaped = s
                         .replace('\\', "\\\\")
@@ -241,32 +454,37 @@ impl<'a> CodeGenerator<'a> {
                     format!("{}{}", op, self.identifier_name(operand))
                 }
             }
-            ReactiveValue::Call { callee, args } => {
-                let args_str: Vec<_> = args.iter().map(|a| {
-                    match a {
-                        ReactiveArgument::Regular(id) => self.identifier_name(id),
-                        ReactiveArgument::Spread(id) => format!("...{}", self.identifier_name(id)),
-                    }
-                }).collect();
-                format!("{}({})", self.identifier_name(callee), args_str.join(", "))
-            }
+            // Bundlers rely on `@__PURE__` to drop unused calls, so keep it
+            ReactiveValue::Call { callee, args, pure } => format!(
+                "{}{}({})",
+                pure_annotation(*pure),
+                self.identifier_name(callee),
+                self.generate_arguments(args)
+            ),
+            ReactiveValue::MethodCall { receiver, property, args, pure } => format!(
+                "{}{}.{}({})",
+                pure_annotation(*pure),
+                self.identifier_name(receiver),
+                property,
+                self.generate_arguments(args)
+            ),
             ReactiveValue::Object { properties } => {
-                let props: Vec<_> = properties
-                    .iter()
-                    .map(|prop| {
-                        match prop {
-                            ReactiveObjectProperty::KeyValue { key, value } => {
-                                let key_str = match key {
-                                    ReactiveObjectKey::Identifier(s) => s.clone(),
-                                    ReactiveObjectKey::Computed(id) => format!("[{}]", self.identifier_name(id)),
-                                };
-                                format!("{}: {}", key_str, self.identifier_name(value))
-                            }
-                            ReactiveObjectProperty::Spread(id) => format!("...{}", self.identifier_name(id)),
-                        }
-                    })
-                    .collect();
-                format!("{{ {} }}", props.join(", "))
+                let props = self.generate_properties(properties);
+                if props.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", props.join(", "))
+                }
+            }
+            ReactiveValue::JsxElement { tag, props, children, key, ref_ } => {
+                self.generate_jsx(&self.identifier_name(tag), props, children, key.as_ref(), ref_.as_ref())
+            }
+            ReactiveValue::JsxFragment { children } => {
+                let fragment = match self.jsx_runtime {
+                    JsxRuntime::Classic => "React.Fragment",
+                    JsxRuntime::Automatic => "_Fragment",
+                };
+                self.generate_jsx(fragment, &[], children, None, None)
             }
             ReactiveValue::Array { elements } => {
                 let elems: Vec<_> = elements.iter().map(|e| {
@@ -276,7 +494,12 @@ impl<'a> CodeGenerator<'a> {
                         ReactiveArrayElement::Hole => String::new(),
                     }
                 }).collect();
-                format!("[{}]", elems.join(", "))
+                // A trailing hole needs an explicit extra comma: `[1, ,]` has length 2
+                if matches!(elements.last(), Some(ReactiveArrayElement::Hole)) {
+                    format!("[{},]", elems.join(", "))
+                } else {
+                    format!("[{}]", elems.join(", "))
+                }
             }
             ReactiveValue::PropertyLoad { object, property } => {
                 format!("{}.{}", self.identifier_name(object), property)
@@ -290,9 +513,30 @@ impl<'a> CodeGenerator<'a> {
             ReactiveValue::ComputedStore { object, property, value } => {
                 format!("{}[{}] = {}", self.identifier_name(object), self.identifier_name(property), self.identifier_name(value))
             }
+            ReactiveValue::PropertyDelete { object, property } => {
+                format!("delete {}.{}", self.identifier_name(object), property)
+            }
+            ReactiveValue::ComputedDelete { object, property } => {
+                format!("delete {}[{}]", self.identifier_name(object), self.identifier_name(property))
+            }
+            ReactiveValue::ForInKeys { object } => {
+                // There is no built-in for this: `Object.keys` skips inherited properties
+                format!(
+                    "((object) => {{ const keys = []; for (const key in object) keys.push(key); return keys; }})({})",
+                    self.identifier_name(object)
+                )
+            }
             ReactiveValue::LoadLocal(id) => {
                 self.identifier_name(id)
             }
+            ReactiveValue::Yield { argument, delegate } => {
+                let keyword = if *delegate { "yield*" } else { "yield" };
+                match argument {
+                    Some(id) => format!("{} {}", keyword, self.identifier_name(id)),
+                    None => keyword.to_string(),
+                }
+            }
+            ReactiveValue::Await { argument } => format!("await {}", self.identifier_name(argument)),
             ReactiveValue::Phi { operands } => {
                 // Phi nodes shouldn't appear in codegen, but handle gracefully
                 if let Some(first) = operands.first() {
@@ -304,14 +548,124 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
+    fn generate_property_key(&self, key: &ReactiveObjectKey) -> String {
+        match key {
+            ReactiveObjectKey::Identifier(s) => format_property_key(s),
+            ReactiveObjectKey::Computed(id) => format!("[{}]", self.identifier_name(id)),
+        }
+    }
+
+    fn generate_properties(&self, properties: &[ReactiveObjectProperty]) -> Vec<String> {
+        properties
+            .iter()
+            .map(|prop| {
+                match prop {
+                    ReactiveObjectProperty::KeyValue { key, value } => {
+                        format!("{}: {}", self.generate_property_key(key), self.identifier_name(value))
+                    }
+                    ReactiveObjectProperty::Spread(id) => format!("...{}", self.identifier_name(id)),
+                    ReactiveObjectProperty::Method { key, function } => {
+                        self.generate_method(&self.generate_property_key(key), function)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Emit a JSX element as a call for the configured runtime. Classic passes
+    /// children as trailing arguments, since React treats a single child
+    /// differently from an array of them; automatic passes them in props.
+    fn generate_jsx(
+        &self,
+        tag: &str,
+        props: &[ReactiveObjectProperty],
+        children: &[ReactiveArgument],
+        key: Option<&Identifier>,
+        ref_: Option<&Identifier>,
+    ) -> String {
+        // Lowering only separates `key` and `ref` written before any spread,
+        // so putting them first keeps the order props are applied in
+        let ref_prop = ref_.map(|ref_| format!("ref: {}", self.identifier_name(ref_)));
+        match self.jsx_runtime {
+            JsxRuntime::Classic => {
+                let key_prop = key.map(|key| format!("key: {}", self.identifier_name(key)));
+                let props: Vec<_> = key_prop
+                    .into_iter()
+                    .chain(ref_prop)
+                    .chain(self.generate_properties(props))
+                    .collect();
+                let props = if props.is_empty() {
+                    "null".to_string()
+                } else {
+                    format!("{{ {} }}", props.join(", "))
+                };
+                let mut args = vec![tag.to_string(), props];
+                if !children.is_empty() {
+                    args.push(self.generate_arguments(children));
+                }
+                format!("React.createElement({})", args.join(", "))
+            }
+            JsxRuntime::Automatic => {
+                // `key` is passed separately rather than as a prop, even one
+                // written after a spread
+                let is_key = |prop: &ReactiveObjectProperty| {
+                    matches!(prop, ReactiveObjectProperty::KeyValue { key: ReactiveObjectKey::Identifier(name), .. } if name == "key")
+                };
+                let key = props
+                    .iter()
+                    .rev()
+                    .find_map(|prop| match prop {
+                        ReactiveObjectProperty::KeyValue { value, .. } if is_key(prop) => Some(value),
+                        _ => None,
+                    })
+                    .or(key);
+                let remaining: Vec<_> = props.iter().filter(|prop| !is_key(prop)).cloned().collect();
+                let mut props: Vec<_> = ref_prop.into_iter().chain(self.generate_properties(&remaining)).collect();
+
+                // `_jsxs` marks a static list of children, which React
+                // doesn't need to check for keys
+                let callee = match children {
+                    [] => "_jsx",
+                    [ReactiveArgument::Regular(child)] => {
+                        props.push(format!("children: {}", self.identifier_name(child)));
+                        "_jsx"
+                    }
+                    _ => {
+                        props.push(format!("children: [{}]", self.generate_arguments(children)));
+                        "_jsxs"
+                    }
+                };
+                let props = if props.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", props.join(", "))
+                };
+                match key {
+                    Some(key) => format!("{}({}, {}, {})", callee, tag, props, self.identifier_name(key)),
+                    None => format!("{}({}, {})", callee, tag, props),
+                }
+            }
+        }
+    }
+
+    fn generate_arguments(&self, args: &[ReactiveArgument]) -> String {
+        let args_str: Vec<_> = args.iter().map(|a| {
+            match a {
+                ReactiveArgument::Regular(id) => self.identifier_name(id),
+                ReactiveArgument::Spread(id) => format!("...{}", self.identifier_name(id)),
+            }
+        }).collect();
+        args_str.join(", ")
+    }
+
     fn generate_scope(
         &mut self,
-        _id: ScopeId,
+        id: ScopeId,
         dependencies: &[Identifier],
         declarations: &[Identifier],
         body: &[ReactiveStatement],
     ) {
-        // Generate useMemoCache pattern:
+        // Generate useMemoCache pattern, with slots relative to the scope's offset:
         // if ($[0] !== dep1 || $[1] !== dep2) {
         //   // body
         //   $[0] = dep1; $[1] = dep2; $[2] = result;
@@ -322,17 +676,20 @@ impl<'a> CodeGenerator<'a> {
             return;
         }
 
+        let offset = self.scopes.scopes.iter()
+            .find(|scope| scope.id == id)
+            .map_or(0, |scope| scope.cache_offset);
         let dep_count = dependencies.len();
         
         // Generate condition
         self.write_indent();
         if dependencies.is_empty() {
-            writeln!(self.output, "if ($[0] === Symbol.for(\"react.memo_cache_sentinel\")) {{").unwrap();
+            writeln!(self.output, "if ($[{}] === Symbol.for(\"react.memo_cache_sentinel\")) {{", offset).unwrap();
         } else {
             let conditions: Vec<_> = dependencies
                 .iter()
                 .enumerate()
-                .map(|(i, d)| format!("$[{}] !== {}", i, self.identifier_name(d)))
+                .map(|(i, d)| format!("$[{}] !== {}", offset + i, self.identifier_name(d)))
                 .collect();
             writeln!(self.output, "if ({}) {{", conditions.join(" || ")).unwrap();
         }
@@ -347,28 +704,31 @@ impl<'a> CodeGenerator<'a> {
         // Store dependencies
         for (i, dep) in dependencies.iter().enumerate() {
             self.write_indent();
-            writeln!(self.output, "$[{}] = {};", i, self.identifier_name(dep)).unwrap();
+            writeln!(self.output, "$[{}] = {};", offset + i, self.identifier_name(dep)).unwrap();
         }
         
         // Store declarations
         for (i, decl) in declarations.iter().enumerate() {
             self.write_indent();
-            writeln!(self.output, "$[{}] = {};", dep_count + i, self.identifier_name(decl)).unwrap();
+            writeln!(self.output, "$[{}] = {};", offset + dep_count + i, self.identifier_name(decl)).unwrap();
         }
         
         self.indent -= 1;
         self.write_indent();
         writeln!(self.output, "}}").unwrap();
         
-        // Read cached declarations
+        // Read cached declarations. Ones the body assigns are already
+        // hoisted, so they are assigned here rather than redeclared.
         for (i, decl) in declarations.iter().enumerate() {
             self.write_indent();
-            writeln!(self.output, "const {} = $[{}];", self.identifier_name(decl), dep_count + i).unwrap();
+            let name = self.identifier_name(decl);
+            let keyword = if self.declared.contains(&name) { "" } else { "const " };
+            writeln!(self.output, "{}{} = $[{}];", keyword, name, offset + dep_count + i).unwrap();
         }
     }
 
     fn get_canonical_name(id: &Identifier) -> String {
-        let is_temp = id.name.starts_with('t') && id.name.len() > 1 && id.name[1..].chars().all(|c| c.is_ascii_digit());
+        let is_temp = id.temporary;
         let is_reserved = matches!(id.name.as_str(), "true" | "false" | "null" | "undefined");
         if is_temp || is_reserved {
             id.name.clone()
@@ -384,6 +744,20 @@ impl<'a> CodeGenerator<'a> {
             return id.name.clone();
         }
 
+        // Like a named binding's version 0, a temporary read before any
+        // assignment on that path is undefined
+        if self.versioned_temps.contains(&id.name) {
+            if id.id == 0 {
+                return "undefined".to_string();
+            }
+            return format!("{}_{}", id.name, id.id);
+        }
+
+        // `arguments` always refers to the enclosing function's arguments object
+        if id.name == "arguments" && id.id == 0 {
+            return id.name.clone();
+        }
+
         // Check for globals (id=0 and not declared locally)
         if id.id == 0 {
              // If base name IS declared locally (e.g. j_1 exists), then j_0 is Uninitialized Local -> undefined.
@@ -403,12 +777,199 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
+    fn write_label(&mut self, label: &Option<String>) {
+        if let Some(label) = label {
+            write!(self.output, "{}: ", label).unwrap();
+        }
+    }
+
+    fn collect_uses(stmt: &ReactiveStatement, used: &mut HashSet<Identifier>) {
+        match stmt {
+            ReactiveStatement::Instruction(instr) => {
+                Self::collect_value_uses(&instr.value, used);
+            }
+            ReactiveStatement::Scope { dependencies, declarations, body, .. } => {
+                used.extend(dependencies.iter().cloned());
+                used.extend(declarations.iter().cloned());
+                for s in body {
+                    Self::collect_uses(s, used);
+                }
+            }
+            ReactiveStatement::If { test, consequent, alternate } => {
+                used.insert(test.clone());
+                for s in consequent.iter().chain(alternate) {
+                    Self::collect_uses(s, used);
+                }
+            }
+            ReactiveStatement::While { test, body, .. } => {
+                used.insert(test.clone());
+                for s in body {
+                    Self::collect_uses(s, used);
+                }
+            }
+            ReactiveStatement::Return(Some(id)) => {
+                used.insert(id.clone());
+            }
+            ReactiveStatement::Switch { test, cases, .. } => {
+                used.insert(test.clone());
+                for case in cases {
+                    if let Some(label) = &case.label {
+                        Self::collect_value_uses(label, used);
+                    }
+                    for s in &case.body {
+                        Self::collect_uses(s, used);
+                    }
+                }
+            }
+            ReactiveStatement::Return(None) | ReactiveStatement::Break(_) | ReactiveStatement::Continue(_) => {}
+        }
+    }
+
+    fn collect_value_uses(value: &ReactiveValue, used: &mut HashSet<Identifier>) {
+        let use_args = |args: &[ReactiveArgument], used: &mut HashSet<Identifier>| {
+            for arg in args {
+                match arg {
+                    ReactiveArgument::Regular(id) | ReactiveArgument::Spread(id) => {
+                        used.insert(id.clone());
+                    }
+                }
+            }
+        };
+        match value {
+            ReactiveValue::Constant(_) => {}
+            ReactiveValue::BinaryOp { left, right, .. } => {
+                used.insert(left.clone());
+                used.insert(right.clone());
+            }
+            ReactiveValue::UnaryOp { operand, .. } => {
+                used.insert(operand.clone());
+            }
+            ReactiveValue::Call { callee, args, .. } => {
+                used.insert(callee.clone());
+                use_args(args, used);
+            }
+            ReactiveValue::MethodCall { receiver, args, .. } => {
+                used.insert(receiver.clone());
+                use_args(args, used);
+            }
+            ReactiveValue::JsxElement { tag, props, children, key, ref_ } => {
+                used.insert(tag.clone());
+                used.extend(key.iter().chain(ref_).cloned());
+                for prop in props {
+                    match prop {
+                        ReactiveObjectProperty::KeyValue { value, .. } | ReactiveObjectProperty::Spread(value) => {
+                            used.insert(value.clone());
+                        }
+                        ReactiveObjectProperty::Method { .. } => {}
+                    }
+                }
+                use_args(children, used);
+            }
+            ReactiveValue::JsxFragment { children } => {
+                use_args(children, used);
+            }
+            ReactiveValue::Object { properties } => {
+                for prop in properties {
+                    match prop {
+                        ReactiveObjectProperty::KeyValue { key, value } => {
+                            if let ReactiveObjectKey::Computed(k) = key {
+                                used.insert(k.clone());
+                            }
+                            used.insert(value.clone());
+                        }
+                        ReactiveObjectProperty::Spread(id) => {
+                            used.insert(id.clone());
+                        }
+                        // The body is emitted separately and has its own names
+                        ReactiveObjectProperty::Method { key, .. } => {
+                            if let ReactiveObjectKey::Computed(k) = key {
+                                used.insert(k.clone());
+                            }
+                        }
+                    }
+                }
+            }
+            ReactiveValue::Array { elements } => {
+                for elem in elements {
+                    match elem {
+                        ReactiveArrayElement::Regular(id) | ReactiveArrayElement::Spread(id) => {
+                            used.insert(id.clone());
+                        }
+                        ReactiveArrayElement::Hole => {}
+                    }
+                }
+            }
+            ReactiveValue::PropertyLoad { object, .. } => {
+                used.insert(object.clone());
+            }
+            ReactiveValue::PropertyStore { object, value, .. } => {
+                used.insert(object.clone());
+                used.insert(value.clone());
+            }
+            ReactiveValue::ComputedLoad { object, property } => {
+                used.insert(object.clone());
+                used.insert(property.clone());
+            }
+            ReactiveValue::ComputedStore { object, property, value } => {
+                used.insert(object.clone());
+                used.insert(property.clone());
+                used.insert(value.clone());
+            }
+            ReactiveValue::PropertyDelete { object, .. } | ReactiveValue::ForInKeys { object } => {
+                used.insert(object.clone());
+            }
+            ReactiveValue::ComputedDelete { object, property } => {
+                used.insert(object.clone());
+                used.insert(property.clone());
+            }
+            ReactiveValue::LoadLocal(id) => {
+                used.insert(id.clone());
+            }
+            ReactiveValue::Yield { argument, .. } => {
+                used.extend(argument.iter().cloned());
+            }
+            ReactiveValue::Await { argument } => {
+                used.insert(argument.clone());
+            }
+            ReactiveValue::Phi { operands } => {
+                used.extend(operands.iter().cloned());
+            }
+        }
+    }
+
+    /// Record the SSA versions assigned to each temporary, by temporary name.
+    fn collect_temp_assignments(stmt: &ReactiveStatement, assignments: &mut HashMap<String, HashSet<Identifier>>) {
+        match stmt {
+            ReactiveStatement::Instruction(instr) if instr.lvalue.temporary => {
+                assignments.entry(instr.lvalue.name.clone()).or_default().insert(instr.lvalue.clone());
+            }
+            ReactiveStatement::If { consequent, alternate, .. } => {
+                for s in consequent.iter().chain(alternate) {
+                    Self::collect_temp_assignments(s, assignments);
+                }
+            }
+            ReactiveStatement::While { body, .. } | ReactiveStatement::Scope { body, .. } => {
+                for s in body {
+                    Self::collect_temp_assignments(s, assignments);
+                }
+            }
+            ReactiveStatement::Switch { cases, .. } => {
+                for case in cases {
+                    for s in &case.body {
+                        Self::collect_temp_assignments(s, assignments);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn collect_declarations(stmt: &ReactiveStatement, vars: &mut HashSet<String>, base_names: &mut HashSet<String>) {
         match stmt {
             ReactiveStatement::Instruction(instr) => {
                 let name = Self::get_canonical_name(&instr.lvalue);
-                // Only hoist user variables (not temps starting with 't' followed by digit)
-                let is_temp = instr.lvalue.name.starts_with('t') && instr.lvalue.name.len() > 1 && instr.lvalue.name[1..].chars().all(|c| c.is_ascii_digit());
+                // Only hoist user variables, not temporaries
+                let is_temp = instr.lvalue.temporary;
                 let is_reserved = matches!(instr.lvalue.name.as_str(), "true" | "false" | "null" | "undefined");
                 
                 if !is_temp && !is_reserved && !vars.contains(&name) {
@@ -458,15 +1019,133 @@ mod tests {
             scopes: &ReactiveScopeResult {
                 scopes: vec![],
                 instruction_scopes: std::collections::HashMap::new(),
+                split_hooks: vec![],
             },
             cache_size: 0,
             declared: HashSet::new(),
             declared_base_names: HashSet::new(),
+            versioned_temps: HashSet::new(),
             params: HashSet::new(),
+            used: HashSet::new(),
+            jsx_runtime: JsxRuntime::default(),
+            preserve_directives: false,
         };
         
         assert_eq!(generator.generate_value(&ReactiveValue::Constant(ConstantValue::Number(42.0))), "42");
         assert_eq!(generator.generate_value(&ReactiveValue::Constant(ConstantValue::Boolean(true))), "true");
         assert_eq!(generator.generate_value(&ReactiveValue::Constant(ConstantValue::Null)), "null");
+        assert_eq!(generator.generate_value(&ReactiveValue::Constant(ConstantValue::Number(-0.0))), "-0");
+        assert_eq!(generator.generate_value(&ReactiveValue::Constant(ConstantValue::Number(f64::NAN))), "NaN");
+        assert_eq!(generator.generate_value(&ReactiveValue::Constant(ConstantValue::Number(f64::NEG_INFINITY))), "-Infinity");
+    }
+
+    #[test]
+    fn test_number_formatting_matches_js() {
+        assert_eq!(format_number(1e30), "1e+30");
+        assert_eq!(format_number(1e21), "1e+21");
+        assert_eq!(format_number(1e20), "100000000000000000000");
+        assert_eq!(format_number(-1e15 * 1e15), "-1e+30");
+        assert_eq!(format_number(9007199254740993.0), "9007199254740992");
+        assert_eq!(format_number(0.5), "0.5");
+        assert_eq!(format_number(1.5e-7), "1.5e-7");
+        assert_eq!(format_number(0.000001), "0.000001");
+        assert_eq!(format_number(123.456), "123.456");
+    }
+
+    #[test]
+    fn test_array_holes_generation() {
+        let scopes = ReactiveScopeResult {
+            scopes: vec![],
+            instruction_scopes: std::collections::HashMap::new(),
+            split_hooks: vec![],
+        };
+        let generator = CodeGenerator::new(&scopes);
+        let t = |n: usize| ReactiveArrayElement::Regular(Identifier { name: format!("t{}", n), id: n, temporary: true });
+        let array = |elements| generator.generate_value(&ReactiveValue::Array { elements });
+
+        assert_eq!(array(vec![t(0), ReactiveArrayElement::Hole, t(1)]), "[t0, , t1]");
+        assert_eq!(array(vec![ReactiveArrayElement::Hole, ReactiveArrayElement::Hole, t(0)]), "[, , t0]");
+        assert_eq!(array(vec![t(0), ReactiveArrayElement::Hole]), "[t0, ,]");
+        assert_eq!(array(vec![ReactiveArrayElement::Hole]), "[,]");
+        assert_eq!(array(vec![]), "[]");
+    }
+
+    #[test]
+    fn test_empty_literal_generation() {
+        let scopes = ReactiveScopeResult {
+            scopes: vec![],
+            instruction_scopes: std::collections::HashMap::new(),
+            split_hooks: vec![],
+        };
+        let generator = CodeGenerator::new(&scopes);
+
+        assert_eq!(generator.generate_value(&ReactiveValue::Object { properties: vec![] }), "{}");
+        assert_eq!(generator.generate_value(&ReactiveValue::Array { elements: vec![] }), "[]");
+    }
+
+    #[test]
+    fn test_scopes_use_disjoint_cache_slots() {
+        use crate::hir::Place;
+        use crate::hir::scope::{Declaration, Dependency, ReactiveScope};
+
+        let id = |name: &str| Identifier { name: name.to_string(), id: 1, temporary: false };
+        let scope = |n: usize, dep: &str, decl: &str| ReactiveScope {
+            id: ScopeId(n),
+            range: (0, 0),
+            dependencies: vec![Dependency { place: Place { identifier: id(dep) } }],
+            declarations: vec![Declaration { place: Place { identifier: id(decl) } }],
+            cache_offset: 2 * n,
+        };
+        let statement = |n: usize, dep: &str, decl: &str| ReactiveStatement::Scope {
+            id: ScopeId(n),
+            dependencies: vec![id(dep)],
+            declarations: vec![id(decl)],
+            body: vec![ReactiveStatement::Instruction(ReactiveInstruction {
+                lvalue: id(decl),
+                value: ReactiveValue::Array { elements: vec![ReactiveArrayElement::Regular(id(dep))] },
+                scope: Some(ScopeId(n)),
+            })],
+        };
+        let scopes = ReactiveScopeResult {
+            scopes: vec![scope(0, "a", "x"), scope(1, "b", "y")],
+            instruction_scopes: std::collections::HashMap::new(),
+            split_hooks: vec![],
+        };
+        let func = ReactiveFunction {
+            name: Some("f".to_string()),
+            params: vec![],
+            generator: false,
+            is_async: false,
+            directives: vec![],
+            body: vec![statement(0, "a", "x"), statement(1, "b", "y")],
+        };
+        let output = generate_code(&func, &scopes);
+
+        assert!(output.contains("const $ = _c(4);"), "{}", output);
+        assert!(output.contains("if ($[0] !== a_1) {"), "{}", output);
+        assert!(output.contains("if ($[2] !== b_1) {"), "{}", output);
+        assert!(output.contains("$[3] = y_1;"), "{}", output);
+    }
+
+    #[test]
+    fn test_unused_effect_temps_are_statements() {
+        let source = r#"
+function f(arr, x) {
+    const obj = {};
+    arr.push(x);
+    obj.y = 1;
+    let i = 0;
+    i++;
+    return obj;
+}
+"#;
+        let output = crate::compile(source, oxc_span::SourceType::mjs()).unwrap();
+
+        let push = output.lines().find(|l| l.contains(".push(")).unwrap();
+        let store = output.lines().find(|l| l.contains(".y = ")).unwrap();
+        assert!(!push.contains("const "), "{}", output);
+        assert!(!store.contains("const "), "{}", output);
+        // The update's temps are all read, so `i++` still lowers to a load/add/store
+        assert!(output.contains("i_2 = "), "{}", output);
     }
 }
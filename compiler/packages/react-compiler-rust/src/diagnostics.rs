@@ -0,0 +1,68 @@
+//! Compiler diagnostics.
+//!
+//! Every non-fatal problem found while compiling a program goes through one
+//! [`Diagnostics`] accumulator, which remembers the function it came from.
+
+use crate::error::CompilerError;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The function was still compiled, or emitted unchanged.
+    Warning,
+    /// The compiler could not handle the code.
+    Error,
+}
+
+/// A problem reported while compiling one function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// Name of the function the problem was found in, or `"anonymous"`.
+    pub function: String,
+}
+
+/// Accumulates the problems reported by each phase of the pipeline.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    reported: Vec<(String, CompilerError)>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a problem found in `function`.
+    pub fn report(&mut self, function: &str, error: CompilerError) {
+        self.reported.push((function.to_string(), error));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reported.is_empty()
+    }
+
+    /// The reported errors, in the order they were found.
+    pub fn into_errors(self) -> Vec<CompilerError> {
+        self.reported.into_iter().map(|(_, error)| error).collect()
+    }
+
+    /// The reported errors as [`Diagnostic`]s, in the order they were found.
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.reported
+            .into_iter()
+            .map(|(function, error)| {
+                let severity = match miette::Diagnostic::severity(&error) {
+                    Some(miette::Severity::Warning | miette::Severity::Advice) => Severity::Warning,
+                    Some(miette::Severity::Error) | None => Severity::Error,
+                };
+                Diagnostic {
+                    severity,
+                    message: error.to_string(),
+                    function,
+                }
+            })
+            .collect()
+    }
+}
@@ -17,9 +17,54 @@ pub enum CompilerError {
     LoweringError { message: String },
 
     /// Unsupported JavaScript syntax
-    #[error("Unsupported syntax: {syntax}")]
-    #[diagnostic(code(react_compiler::unsupported_syntax), help("This syntax is not yet supported by the compiler"))]
-    UnsupportedSyntax { syntax: String },
+    #[error("Function `{name}` uses unsupported syntax: {syntax}")]
+    #[diagnostic(
+        code(react_compiler::unsupported_syntax),
+        severity(Warning),
+        help("This syntax is not yet supported by the compiler, so the function is emitted as written")
+    )]
+    UnsupportedSyntax { name: String, syntax: String },
+
+    /// Function skipped because it exceeds `CompileOptions::max_instructions`
+    #[error("Function `{name}` has {instructions} instructions, exceeding the limit of {limit}; emitted without memoization")]
+    #[diagnostic(code(react_compiler::function_too_large), severity(Warning))]
+    FunctionTooLarge {
+        name: String,
+        instructions: usize,
+        limit: usize,
+    },
+
+    /// Function skipped because it calls `eval`
+    #[error("Function `{name}` calls `eval`; emitted without memoization")]
+    #[diagnostic(
+        code(react_compiler::eval_unsupported),
+        severity(Warning),
+        help("`eval` can read and declare variables the compiler cannot see")
+    )]
+    EvalUnsupported { name: String },
+
+    /// A memoization scope was split so a hook call stays unconditional
+    #[error("Memoization scope in `{name}` would have contained a call to `{hook}`; split the scope around it")]
+    #[diagnostic(
+        code(react_compiler::hook_in_scope),
+        severity(Warning),
+        help("Hooks must be called unconditionally, so they are never placed inside a memoized block")
+    )]
+    HookInScope { name: String, hook: String },
+
+    /// Function skipped because its CFG could not be rebuilt as a tree
+    #[error("Function `{name}` has control flow too deep or tangled to rebuild; emitted without memoization")]
+    #[diagnostic(code(react_compiler::control_flow_too_complex), severity(Warning))]
+    ControlFlowTooComplex { name: String },
+
+    /// A component or hook writes to one of its parameters
+    #[error("Function `{name}` mutates its parameter `{param}`")]
+    #[diagnostic(
+        code(react_compiler::prop_mutation),
+        severity(Warning),
+        help("Props and hook arguments are read-only; copy the value before changing it")
+    )]
+    PropMutation { name: String, param: String },
 
     /// IO errors
     #[error("IO error: {0}")]
@@ -3,8 +3,11 @@ pub mod dominators;
 pub mod ssa;
 pub mod scope;
 pub mod inference;
+pub mod optimize;
 pub mod reactive_scopes;
 pub mod reactive_function;
+pub mod print;
+pub mod validation;
 
 use scope::ScopeId;
 use serde::{Deserialize, Serialize};
@@ -26,6 +29,14 @@ pub struct HIRFunction {
     pub name: Option<String>,
     /// The parameters of the function.
     pub params: Vec<Identifier>,
+    /// Whether this is a generator function (`function*`).
+    pub generator: bool,
+    /// Whether this is an async function, independently of `generator`:
+    /// `async function*` sets both.
+    pub is_async: bool,
+    /// Directive prologue of the body, such as `"use server"`, as written
+    /// including quotes.
+    pub directives: Vec<String>,
     /// The entry block of the function.
     pub entry_block: BlockId,
     /// All basic blocks in the function, indexed by their ID.
@@ -65,6 +76,30 @@ impl BasicBlock {
     }
 }
 
+impl HIRFunction {
+    /// Every place in the function: instruction lvalues and operands, and
+    /// the places read by terminals.
+    pub fn places_mut(&mut self) -> Vec<&mut Place> {
+        let mut places = Vec::new();
+        for block in self.blocks.values_mut() {
+            for instr in &mut block.instructions {
+                places.push(&mut instr.lvalue);
+                instr.value.collect_places_mut(&mut places);
+            }
+            match &mut block.terminal {
+                Terminal::Goto(_) | Terminal::Return(None) => {}
+                Terminal::If { test, .. } => places.push(test),
+                Terminal::Return(Some(value)) => places.push(value),
+                Terminal::Switch { test, cases, .. } => {
+                    places.push(test);
+                    places.extend(cases.iter_mut().map(|(case, _)| case));
+                }
+            }
+        }
+        places
+    }
+}
+
 /// A single instruction in the HIR: `lvalue = opcode operands`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Instruction {
@@ -86,6 +121,9 @@ pub struct Place {
 pub struct Identifier {
     pub name: String,
     pub id: usize, // Unique ID for this specific identifier instance
+    /// Whether this is a compiler temporary (`t0`, `t1`, ...) rather than a
+    /// binding from the source, which may have the same name.
+    pub temporary: bool,
 }
 
 /// Represents an argument in a function call or array/object element.
@@ -148,6 +186,17 @@ pub enum InstructionValue {
     Call {
         callee: Place,
         args: Vec<Argument>,
+        /// Annotated `/* @__PURE__ */` in the source
+        pure: bool,
+    },
+    /// A method call on a static property: receiver.property(args)
+    /// Kept distinct from `Call` so the receiver is preserved as `this`.
+    MethodCall {
+        receiver: Place,
+        property: String,
+        args: Vec<Argument>,
+        /// Annotated `/* @__PURE__ */` in the source
+        pure: bool,
     },
     /// Create an object literal: { key: value, ... }
     Object {
@@ -179,16 +228,123 @@ pub enum InstructionValue {
         property: Place,
         value: Place,
     },
+    /// Delete a static property: delete object.property
+    PropertyDelete {
+        object: Place,
+        property: String,
+    },
+    /// Delete a computed property: delete object[property]
+    ComputedDelete {
+        object: Place,
+        property: Place,
+    },
+    /// Snapshot of an object's enumerable string keys in `for...in` order
+    ForInKeys {
+        object: Place,
+    },
+    /// A JSX element: `<tag {...props}>{children}</tag>`. Intrinsic tags
+    /// (`div`) are string constants; components are ordinary values.
+    JsxElement {
+        tag: Place,
+        props: Vec<ObjectProperty>,
+        children: Vec<Argument>,
+        /// The `key` attribute. React reads it to reconcile the element; the
+        /// component never sees it, so it is kept out of `props`.
+        key: Option<Place>,
+        /// The `ref` attribute, kept out of `props` like `key`.
+        ref_: Option<Place>,
+    },
+    /// A JSX fragment: `<>{children}</>`
+    JsxFragment {
+        children: Vec<Argument>,
+    },
     /// Load a value from a local variable/binding
     LoadLocal(Place),
     /// Store a value into a local variable/binding (lvalue, value)
     StoreLocal(Place, Place),
+    /// Generator yield: `yield argument` or `yield* argument` when `delegate` is set
+    Yield {
+        argument: Option<Place>,
+        delegate: bool,
+    },
+    /// `await argument`
+    Await {
+        argument: Place,
+    },
     /// Phi node: merges values from predecessor blocks.
     Phi {
         operands: Vec<(BlockId, Place)>,
     },
 }
 
+impl InstructionValue {
+    fn collect_places_mut<'a>(&'a mut self, places: &mut Vec<&'a mut Place>) {
+        fn argument(arg: &mut Argument) -> &mut Place {
+            match arg {
+                Argument::Regular(place) | Argument::Spread(place) => place,
+            }
+        }
+        fn property<'a>(property: &'a mut ObjectProperty, places: &mut Vec<&'a mut Place>) {
+            match property {
+                ObjectProperty::KeyValue { key, value } => {
+                    if let ObjectPropertyKey::Computed(key) = key {
+                        places.push(key);
+                    }
+                    places.push(value);
+                }
+                ObjectProperty::Spread(place) => places.push(place),
+            }
+        }
+        match self {
+            InstructionValue::Constant(_) => {}
+            InstructionValue::BinaryOp { left, right, .. } => places.extend([left, right]),
+            InstructionValue::UnaryOp { operand, .. } => places.push(operand),
+            InstructionValue::Call { callee, args, .. } => {
+                places.push(callee);
+                places.extend(args.iter_mut().map(argument));
+            }
+            InstructionValue::MethodCall { receiver, args, .. } => {
+                places.push(receiver);
+                places.extend(args.iter_mut().map(argument));
+            }
+            InstructionValue::Object { properties } => {
+                for prop in properties {
+                    property(prop, places);
+                }
+            }
+            InstructionValue::Array { elements } => {
+                for element in elements {
+                    if let ArrayElement::Regular(place) | ArrayElement::Spread(place) = element {
+                        places.push(place);
+                    }
+                }
+            }
+            InstructionValue::PropertyLoad { object, .. }
+            | InstructionValue::PropertyDelete { object, .. }
+            | InstructionValue::ForInKeys { object } => places.push(object),
+            InstructionValue::PropertyStore { object, value, .. } => places.extend([object, value]),
+            InstructionValue::ComputedLoad { object, property }
+            | InstructionValue::ComputedDelete { object, property } => places.extend([object, property]),
+            InstructionValue::ComputedStore { object, property, value } => {
+                places.extend([object, property, value])
+            }
+            InstructionValue::JsxElement { tag, props, children, key, ref_ } => {
+                places.push(tag);
+                for prop in props {
+                    property(prop, places);
+                }
+                places.extend(children.iter_mut().map(argument));
+                places.extend(key.iter_mut().chain(ref_.iter_mut()));
+            }
+            InstructionValue::JsxFragment { children } => places.extend(children.iter_mut().map(argument)),
+            InstructionValue::LoadLocal(place) | InstructionValue::Await { argument: place } => places.push(place),
+            InstructionValue::StoreLocal(target, value) => places.extend([target, value]),
+            InstructionValue::Yield { argument, .. } => places.extend(argument.iter_mut()),
+            InstructionValue::Phi { operands } => places.extend(operands.iter_mut().map(|(_, place)| place)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Constant {
     Int(i64),
@@ -199,13 +355,14 @@ pub enum Constant {
     Undefined,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BinaryOperator {
     Add,
     Sub,
     Mul,
     Div,
     Mod,
+    Exponent,
     LessThan,
     LessThanEqual,
     GreaterThan,
@@ -226,7 +383,39 @@ pub enum BinaryOperator {
     In,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl BinaryOperator {
+    /// The JavaScript spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Sub => "-",
+            BinaryOperator::Mul => "*",
+            BinaryOperator::Div => "/",
+            BinaryOperator::Mod => "%",
+            BinaryOperator::Exponent => "**",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanEqual => ">=",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::StrictEqual => "===",
+            BinaryOperator::StrictNotEqual => "!==",
+            BinaryOperator::And => "&&",
+            BinaryOperator::Or => "||",
+            BinaryOperator::BitwiseAnd => "&",
+            BinaryOperator::BitwiseOr => "|",
+            BinaryOperator::BitwiseXor => "^",
+            BinaryOperator::LeftShift => "<<",
+            BinaryOperator::RightShift => ">>",
+            BinaryOperator::UnsignedRightShift => ">>>",
+            BinaryOperator::InstanceOf => "instanceof",
+            BinaryOperator::In => "in",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UnaryOperator {
     /// Logical NOT: !x
     Not,
@@ -260,6 +449,10 @@ pub enum Terminal {
     /// Return from the function.
     Return(Option<Place>),
     /// A switch statement (switch test { case val: goto target; ... default: goto default })
+    ///
+    /// Each case's place is the temp its test expression was lowered into.
+    /// It is defined in this block, so codegen can find the expression and
+    /// write it back into the `case` label.
     Switch {
         test: Place,
         cases: Vec<(Place, BlockId)>,
@@ -53,11 +53,11 @@ impl DominatorTree {
                     }
                 }
 
-                if let Some(new_idom) = new_idom {
-                    if idoms.get(&b) != Some(&new_idom) {
-                        idoms.insert(b, new_idom);
-                        changed = true;
-                    }
+                if let Some(new_idom) = new_idom
+                    && idoms.get(&b) != Some(&new_idom)
+                {
+                    idoms.insert(b, new_idom);
+                    changed = true;
                 }
             }
         }
@@ -118,6 +118,116 @@ impl DominatorTree {
     }
 }
 
+/// Post-dominator tree: the dominator tree of the reversed CFG.
+///
+/// A block's immediate post-dominator is the nearest block that every path
+/// from it to a return passes through, i.e. where its branches join again.
+pub struct PostDominatorTree {
+    /// Map from a block to its immediate post-dominator. Blocks whose paths
+    /// only meet at the function exit, or that never reach a return (the
+    /// body of an infinite loop), have no entry.
+    pub ipdoms: BTreeMap<BlockId, BlockId>,
+}
+
+impl PostDominatorTree {
+    /// Uses the same iterative algorithm as [`DominatorTree::compute`], run on
+    /// the reversed CFG from a virtual exit node that every returning block
+    /// flows into.
+    pub fn compute(func: &HIRFunction) -> Self {
+        let blocks = &func.blocks;
+
+        let mut preds: HashMap<BlockId, Vec<BlockId>> = HashMap::new();
+        let mut exits = Vec::new();
+        for (&id, block) in blocks {
+            let succs = block.successors();
+            if succs.is_empty() {
+                exits.push(id);
+            }
+            for succ in succs {
+                preds.entry(succ).or_default().push(id);
+            }
+        }
+
+        // Post order of the reversed CFG; the virtual exit comes last
+        let mut po = Vec::with_capacity(blocks.len());
+        let mut visited = HashSet::new();
+        for exit in exits {
+            reverse_post_order(exit, &preds, &mut visited, &mut po);
+        }
+        let exit_index = po.len();
+        let po_indices: HashMap<BlockId, usize> = po.iter().enumerate().map(|(i, &b)| (b, i)).collect();
+        let index = |node: Option<BlockId>| node.map_or(exit_index, |b| po_indices[&b]);
+
+        // `None` stands for the virtual exit
+        let mut ipdoms: HashMap<BlockId, Option<BlockId>> = HashMap::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &b in po.iter().rev() {
+                let succs = blocks[&b].successors();
+                let mut new_ipdom = if succs.is_empty() { Some(None) } else { None };
+                for succ in succs {
+                    if !ipdoms.contains_key(&succ) {
+                        continue;
+                    }
+                    new_ipdom = Some(match new_ipdom {
+                        None => Some(succ),
+                        Some(mut current) => {
+                            let mut other = Some(succ);
+                            while current != other {
+                                while index(current) < index(other) {
+                                    current = ipdoms[&current.unwrap()];
+                                }
+                                while index(other) < index(current) {
+                                    other = ipdoms[&other.unwrap()];
+                                }
+                            }
+                            current
+                        }
+                    });
+                }
+
+                if let Some(new_ipdom) = new_ipdom
+                    && ipdoms.get(&b) != Some(&new_ipdom)
+                {
+                    ipdoms.insert(b, new_ipdom);
+                    changed = true;
+                }
+            }
+        }
+
+        Self {
+            ipdoms: ipdoms
+                .into_iter()
+                .filter_map(|(b, ipdom)| ipdom.map(|ipdom| (b, ipdom)))
+                .collect(),
+        }
+    }
+
+    /// Where the paths leaving `block` first meet again, if they do before
+    /// the function returns.
+    pub fn immediate_post_dominator(&self, block: BlockId) -> Option<BlockId> {
+        self.ipdoms.get(&block).copied()
+    }
+}
+
+fn reverse_post_order(
+    current: BlockId,
+    preds: &HashMap<BlockId, Vec<BlockId>>,
+    visited: &mut HashSet<BlockId>,
+    po: &mut Vec<BlockId>
+) {
+    if !visited.insert(current) {
+        return;
+    }
+
+    for &pred in preds.get(&current).into_iter().flatten() {
+        reverse_post_order(pred, preds, visited, po);
+    }
+
+    po.push(current);
+}
+
 fn post_order(
     current: BlockId,
     blocks: &BTreeMap<BlockId, BasicBlock>,
@@ -162,3 +272,93 @@ fn intersect(
     }
     b1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hir::{Identifier, Place, Terminal};
+
+    fn function_with_blocks(terminals: Vec<Terminal>) -> HIRFunction {
+        let blocks = terminals
+            .into_iter()
+            .enumerate()
+            .map(|(i, terminal)| {
+                let block = BasicBlock {
+                    id: BlockId(i),
+                    instructions: vec![],
+                    terminal,
+                    preds: vec![],
+                };
+                (BlockId(i), block)
+            })
+            .collect();
+        HIRFunction {
+            name: None,
+            params: vec![],
+            generator: false,
+            is_async: false,
+            directives: vec![],
+            entry_block: BlockId(0),
+            blocks,
+            loop_headers: Default::default(),
+        }
+    }
+
+    fn branch(consequent: usize, alternate: usize) -> Terminal {
+        Terminal::If {
+            test: Place {
+                identifier: Identifier { name: "c".to_string(), id: 0, temporary: false },
+            },
+            consequent: BlockId(consequent),
+            alternate: BlockId(alternate),
+        }
+    }
+
+    #[test]
+    fn test_diamond_joins_at_merge_block() {
+        // bb0 -> bb1 | bb2 -> bb3 -> return
+        let func = function_with_blocks(vec![
+            branch(1, 2),
+            Terminal::Goto(BlockId(3)),
+            Terminal::Goto(BlockId(3)),
+            Terminal::Return(None),
+        ]);
+        let tree = PostDominatorTree::compute(&func);
+        assert_eq!(tree.immediate_post_dominator(BlockId(0)), Some(BlockId(3)));
+        assert_eq!(tree.immediate_post_dominator(BlockId(1)), Some(BlockId(3)));
+        assert_eq!(tree.immediate_post_dominator(BlockId(2)), Some(BlockId(3)));
+        assert_eq!(tree.immediate_post_dominator(BlockId(3)), None);
+    }
+
+    #[test]
+    fn test_nested_diamond_joins_inner_branches_first() {
+        // bb0 -> bb1 | bb2; bb2 -> bb3 | bb4 -> bb5; bb1, bb5 -> bb6
+        let func = function_with_blocks(vec![
+            branch(1, 2),
+            Terminal::Goto(BlockId(6)),
+            branch(3, 4),
+            Terminal::Goto(BlockId(5)),
+            Terminal::Goto(BlockId(5)),
+            Terminal::Goto(BlockId(6)),
+            Terminal::Return(None),
+        ]);
+        let tree = PostDominatorTree::compute(&func);
+        assert_eq!(tree.immediate_post_dominator(BlockId(0)), Some(BlockId(6)));
+        assert_eq!(tree.immediate_post_dominator(BlockId(2)), Some(BlockId(5)));
+        assert_eq!(tree.immediate_post_dominator(BlockId(5)), Some(BlockId(6)));
+    }
+
+    #[test]
+    fn test_branches_that_return_separately_have_no_join() {
+        // bb0 -> bb1 | bb2, both return; bb3 loops forever
+        let func = function_with_blocks(vec![
+            branch(1, 2),
+            Terminal::Return(None),
+            Terminal::Return(None),
+            Terminal::Goto(BlockId(3)),
+        ]);
+        let tree = PostDominatorTree::compute(&func);
+        assert_eq!(tree.immediate_post_dominator(BlockId(0)), None);
+        assert_eq!(tree.immediate_post_dominator(BlockId(3)), None);
+    }
+}
@@ -99,7 +99,7 @@ pub fn infer_liveness(func: &HIRFunction) -> LivenessResult {
                     InstructionValue::UnaryOp { operand, .. } => {
                         mark_use(operand);
                     }
-                    InstructionValue::Call { callee, args } => {
+                    InstructionValue::Call { callee, args, .. } => {
                         mark_use(callee);
                         for arg in args {
                             match arg {
@@ -108,6 +108,15 @@ pub fn infer_liveness(func: &HIRFunction) -> LivenessResult {
                             }
                         }
                     }
+                    InstructionValue::MethodCall { receiver, args, .. } => {
+                        mark_use(receiver);
+                        for arg in args {
+                            match arg {
+                                crate::hir::Argument::Regular(p) => mark_use(p),
+                                crate::hir::Argument::Spread(p) => mark_use(p),
+                            }
+                        }
+                    }
                     InstructionValue::PropertyStore { object, value, .. } => {
                         mark_use(object);
                         mark_use(value);
@@ -117,13 +126,40 @@ pub fn infer_liveness(func: &HIRFunction) -> LivenessResult {
                         mark_use(property);
                         mark_use(value);
                     }
-                    InstructionValue::PropertyLoad { object, .. } => {
+                    InstructionValue::PropertyLoad { object, .. }
+                    | InstructionValue::PropertyDelete { object, .. }
+                    | InstructionValue::ForInKeys { object } => {
                         mark_use(object);
                     }
-                    InstructionValue::ComputedLoad { object, property } => {
+                    InstructionValue::ComputedLoad { object, property }
+                    | InstructionValue::ComputedDelete { object, property } => {
                         mark_use(object);
                         mark_use(property);
                     }
+                    InstructionValue::JsxElement { tag, props, children, key, ref_ } => {
+                        mark_use(tag);
+                        key.iter().chain(ref_).for_each(&mut mark_use);
+                        for prop in props {
+                            match prop {
+                                crate::hir::ObjectProperty::KeyValue { value, .. } => mark_use(value),
+                                crate::hir::ObjectProperty::Spread(p) => mark_use(p),
+                            }
+                        }
+                        for child in children {
+                            match child {
+                                crate::hir::Argument::Regular(p) => mark_use(p),
+                                crate::hir::Argument::Spread(p) => mark_use(p),
+                            }
+                        }
+                    }
+                    InstructionValue::JsxFragment { children } => {
+                        for child in children {
+                            match child {
+                                crate::hir::Argument::Regular(p) => mark_use(p),
+                                crate::hir::Argument::Spread(p) => mark_use(p),
+                            }
+                        }
+                    }
                     InstructionValue::Object { properties } => {
                         for prop in properties {
                             match prop {
@@ -157,6 +193,10 @@ pub fn infer_liveness(func: &HIRFunction) -> LivenessResult {
                             mark_use(val);
                         }
                     }
+                    InstructionValue::Yield { argument: Some(arg), .. }
+                    | InstructionValue::Await { argument: arg } => {
+                        mark_use(arg);
+                    }
                     _ => {}
                 }
             }
@@ -209,6 +249,12 @@ pub struct DisjointSet {
     parents: HashMap<Identifier, Identifier>,
 }
 
+impl Default for DisjointSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DisjointSet {
     pub fn new() -> Self {
         Self { parents: HashMap::new() }
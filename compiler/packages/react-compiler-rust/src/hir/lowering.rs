@@ -3,27 +3,63 @@ use crate::hir::{
     InstrId, Instruction, InstructionValue, ObjectProperty, ObjectPropertyKey, Place, Terminal,
     UnaryOperator,
 };
+use crate::error::CompilerError;
 use oxc_ast::ast::{self, Expression, Statement};
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-pub struct LoweringContext {
+pub struct LoweringContext<'a> {
     blocks: BTreeMap<BlockId, BasicBlock>,
     current_block_id: BlockId,
     next_block_id: usize,
     next_instr_id: usize,
     next_temp_id: usize,
     loop_stack: Vec<LoopInfo>,
+    /// Label of the loop or switch currently being lowered, if it has one
+    pending_label: Option<String>,
+    /// Blocks given a terminal on purpose; any other block still has the
+    /// `Return(None)` placeholder it was created with
     terminated_blocks: HashSet<BlockId>,
     loop_headers: HashSet<BlockId>,
+    /// Syntax the lowering had to skip, in source order
+    unsupported: Vec<&'static str>,
+    /// The first inconsistency the lowering ran into, returned by
+    /// `build_checked` in place of the HIR
+    error: Option<CompilerError>,
+    /// Whether the function is a class method, the only place `this` is
+    /// lowered; anywhere else it is reported as unsupported
+    method: bool,
+    /// `finally` blocks of the `try` statements being lowered, innermost last
+    finalizers: Vec<Finalizer<'a>>,
+    /// When set, `console.<method>(...)` statements are dropped unless the
+    /// method is listed
+    strip_console: Option<Vec<String>>,
 }
 
-#[derive(Clone, Copy)]
+/// The `finally` block of a `try` statement. Every jump out of the `try`
+/// runs a copy of it, as does reaching its end.
+struct Finalizer<'a> {
+    block: &'a ast::BlockStatement<'a>,
+    /// Length of the loop stack at the `try`, i.e. how many loops, switches
+    /// and labeled blocks enclose it
+    loop_depth: usize,
+}
+
+/// A member expression assignment target whose object (and key) are already evaluated.
+enum MemberTarget {
+    Static { object: Place, property: String },
+    Computed { object: Place, property: Place },
+}
+
+#[derive(Clone)]
 struct LoopInfo {
     break_target: BlockId,
     continue_target: Option<BlockId>,
+    label: Option<String>,
+    /// Labeled blocks can only be exited with `break label`, never a bare `break`
+    label_only: bool,
 }
 
-impl LoweringContext {
+impl<'a> LoweringContext<'a> {
     pub fn new() -> Self {
         let entry_block_id = BlockId(0);
         let entry_block = BasicBlock {
@@ -43,12 +79,39 @@ impl LoweringContext {
             next_instr_id: 0,
             next_temp_id: 0,
             loop_stack: Vec::new(),
+            pending_label: None,
             terminated_blocks: HashSet::new(),
             loop_headers: HashSet::new(),
+            unsupported: Vec::new(),
+            error: None,
+            method: false,
+            finalizers: Vec::new(),
+            strip_console: None,
         }
     }
 
-    pub fn build(mut self, func: &ast::Function) -> HIRFunction {
+    /// A context for lowering a class method, which can read `this`.
+    pub fn for_method() -> Self {
+        Self { method: true, ..Self::new() }
+    }
+
+    /// Drop `console.<method>(...)` statements, except for the methods in
+    /// `keep`.
+    pub fn strip_console(mut self, keep: &[String]) -> Self {
+        self.strip_console = Some(keep.to_vec());
+        self
+    }
+
+    /// Lower `func` to HIR, also returning a description of each piece of
+    /// syntax that could not be lowered. If there are any, the HIR is missing
+    /// their effects and should not be used to emit code.
+    ///
+    /// Fails if the lowering reached an inconsistent state, or if a reachable
+    /// block was never terminated.
+    pub fn build_checked(
+        mut self,
+        func: &'a ast::Function<'a>,
+    ) -> Result<(HIRFunction, Vec<&'static str>), CompilerError> {
         // Extract function parameters
         let mut params = Vec::new();
         for (idx, param) in func.params.items.iter().enumerate() {
@@ -57,6 +120,7 @@ impl LoweringContext {
                     params.push(Identifier {
                         name: id.name.to_string(),
                         id: 0,
+                        temporary: false,
                     });
                 }
                 _ => {
@@ -65,39 +129,83 @@ impl LoweringContext {
                     params.push(Identifier {
                         name: format!("_param{}", idx),
                         id: idx,
+                        temporary: false,
                     });
                 }
             }
         }
 
+        let mut directives = Vec::new();
         if let Some(body) = &func.body {
+            directives.extend(body.directives.iter().map(directive_source));
             for stmt in &body.statements {
                 self.lower_statement(stmt);
             }
         }
+        // Falling off the end of the body returns `undefined`
+        self.terminated_blocks.insert(self.current_block_id);
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.validate_terminals()?;
 
-        HIRFunction {
+        let mut hir = HIRFunction {
             name: func.id.as_ref().map(|id| id.name.to_string()),
             params,
+            generator: func.generator,
+            is_async: func.r#async,
+            directives,
             entry_block: BlockId(0),
             blocks: self.blocks,
             loop_headers: self.loop_headers,
+        };
+        rename_clashing_temporaries(&mut hir, self.next_temp_id);
+        Ok((hir, self.unsupported))
+    }
+
+    /// Check that every block reachable from the entry exists and was given
+    /// its terminal on purpose, rather than keeping the placeholder.
+    fn validate_terminals(&self) -> Result<(), CompilerError> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![BlockId(0)];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(block) = self.blocks.get(&id) else {
+                return Err(CompilerError::LoweringError {
+                    message: format!("a jump targets {:?}, which was never created", id),
+                });
+            };
+            if !self.is_block_terminated(id) {
+                return Err(CompilerError::LoweringError {
+                    message: format!("{:?} is reachable but was never terminated", id),
+                });
+            }
+            stack.extend(block.successors());
         }
+        Ok(())
     }
 
-    fn lower_statement(&mut self, stmt: &Statement) {
+    fn lower_statement(&mut self, stmt: &'a Statement<'a>) {
         match stmt {
+            // `declare` statements only describe values defined elsewhere, so
+            // like type declarations they have no runtime effect
+            Statement::VariableDeclaration(decl) if decl.declare => {}
+            Statement::FunctionDeclaration(func) if func.declare => {}
+            Statement::ClassDeclaration(class) if class.declare => {}
+            Statement::TSModuleDeclaration(module) if module.declare => {}
+            Statement::TSEnumDeclaration(decl) if decl.declare => {}
             Statement::ReturnStatement(ret) => {
-                let value = if let Some(arg) = &ret.argument {
-                    Some(self.lower_expression(arg))
-                } else {
-                    None
-                };
+                // The value is read before any `finally` block runs
+                let value = ret.argument.as_ref().map(|arg| self.lower_expression(arg));
+                self.lower_finalizers(0);
                 self.terminate_block(Terminal::Return(value));
             }
             Statement::VariableDeclaration(decl) => {
                 self.lower_variable_declaration(decl);
             }
+            Statement::ExpressionStatement(expr) if self.is_stripped_console_call(&expr.expression) => {}
             Statement::ExpressionStatement(expr) => {
                 self.lower_expression(&expr.expression);
             }
@@ -116,9 +224,11 @@ impl LoweringContext {
                 });
 
                 // --- Then Block ---
+                // A nested conditional leaves us in its merge block, so check
+                // the block we ended up in rather than the one we started
                 self.start_block(then_block_id);
                 self.lower_statement(&if_stmt.consequent);
-                if !self.is_block_terminated(then_block_id) {
+                if !self.is_block_terminated(self.current_block_id) {
                     self.terminate_block(Terminal::Goto(merge_block_id));
                 }
 
@@ -127,7 +237,7 @@ impl LoweringContext {
                 if let Some(alternate) = &if_stmt.alternate {
                     self.lower_statement(alternate);
                 }
-                if !self.is_block_terminated(else_block_id) {
+                if !self.is_block_terminated(self.current_block_id) {
                     self.terminate_block(Terminal::Goto(merge_block_id));
                 }
 
@@ -225,53 +335,239 @@ impl LoweringContext {
                 // 7. Exit block (next statements will continue from here)
                 self.start_block(exit_block_id);
             }
+            Statement::ForInStatement(for_in) => {
+                self.lower_for_in_statement(for_in);
+            }
             Statement::BlockStatement(block) => {
                  for stmt in &block.body {
                      self.lower_statement(stmt);
                  }
             }
-            Statement::BreakStatement(_) => {
-                if let Some(loop_info) = self.loop_stack.last() {
-                    self.terminate_block(Terminal::Goto(loop_info.break_target));
+            Statement::BreakStatement(break_stmt) => {
+                let label = break_stmt.label.as_ref().map(|l| l.name.as_str());
+                let target = self.loop_stack.iter().rposition(|info| match label {
+                    Some(label) => info.label.as_deref() == Some(label),
+                    None => !info.label_only,
+                });
+                if let Some(index) = target {
+                    let break_target = self.loop_stack[index].break_target;
+                    self.lower_finalizers(index + 1);
+                    self.terminate_block(Terminal::Goto(break_target));
                 }
             }
-            Statement::ContinueStatement(_) => {
-                // Find nearest loop (skip switches)
-                for loop_info in self.loop_stack.iter().rev() {
-                    if let Some(target) = loop_info.continue_target {
-                        self.terminate_block(Terminal::Goto(target));
-                        break;
-                    }
+            Statement::ContinueStatement(continue_stmt) => {
+                // Find nearest loop (skip switches and labeled blocks)
+                let label = continue_stmt.label.as_ref().map(|l| l.name.as_str());
+                let target = self.loop_stack.iter().rposition(|info| {
+                    info.continue_target.is_some()
+                        && label.is_none_or(|label| info.label.as_deref() == Some(label))
+                });
+                if let Some(index) = target
+                    && let Some(continue_target) = self.loop_stack[index].continue_target
+                {
+                    self.lower_finalizers(index + 1);
+                    self.terminate_block(Terminal::Goto(continue_target));
                 }
             }
+            Statement::LabeledStatement(labeled) => {
+                self.lower_labeled_statement(labeled);
+            }
             Statement::SwitchStatement(switch_stmt) => {
                 self.lower_switch_statement(switch_stmt);
             }
+            Statement::TryStatement(try_stmt) if try_stmt.handler.is_none() => {
+                self.lower_try_finally(try_stmt);
+            }
+            // Type declarations have no runtime effect
+            Statement::EmptyStatement(_)
+            | Statement::TSTypeAliasDeclaration(_)
+            | Statement::TSInterfaceDeclaration(_)
+            | Statement::TSGlobalDeclaration(_) => {}
+            Statement::TSEnumDeclaration(decl) => {
+                self.lower_enum_declaration(decl);
+            }
             _ => {
                 // TODO: Handle other statements
+                self.unsupported.push(describe_statement(stmt));
             }
         }
     }
 
+    /// Lower `for (key in object)` as a counted loop over a snapshot of the keys:
+    ///
+    /// ```text
+    /// keys = ForInKeys object; i = 0
+    /// while (i < keys.length) { key = keys[i]; body; i = i + 1 }
+    /// ```
+    fn lower_for_in_statement(&mut self, for_in: &'a ast::ForInStatement<'a>) {
+        let object = self.lower_expression(&for_in.right);
+        let keys = self.push_instruction(InstructionValue::ForInKeys { object });
+        // The counter is carried around the loop, so it has to be a variable
+        // (temporaries are single-assignment and never get phis)
+        let index = Place {
+            identifier: Identifier {
+                name: format!("_forIn{}", keys.identifier.id),
+                id: 0,
+                temporary: false,
+            },
+        };
+        let zero = self.push_instruction(InstructionValue::Constant(Constant::Float(0.0)));
+        self.push_instruction(InstructionValue::StoreLocal(index.clone(), zero));
+
+        let header_block_id = self.next_block_id();
+        let body_block_id = self.next_block_id();
+        let update_block_id = self.next_block_id();
+        let exit_block_id = self.next_block_id();
+
+        self.terminate_block(Terminal::Goto(header_block_id));
+
+        // Header: i < keys.length
+        self.start_block(header_block_id);
+        let current = self.push_instruction(InstructionValue::LoadLocal(index.clone()));
+        let length = self.push_instruction(InstructionValue::PropertyLoad {
+            object: keys.clone(),
+            property: "length".to_string(),
+        });
+        let test = self.push_instruction(InstructionValue::BinaryOp {
+            op: BinaryOperator::LessThan,
+            left: current,
+            right: length,
+        });
+        self.terminate_block(Terminal::If {
+            test,
+            consequent: body_block_id,
+            alternate: exit_block_id,
+        });
+
+        // Body: bind the key, then run the loop body
+        self.start_block(body_block_id);
+        let current = self.push_instruction(InstructionValue::LoadLocal(index.clone()));
+        let key = self.push_instruction(InstructionValue::ComputedLoad {
+            object: keys,
+            property: current,
+        });
+        let key_name = match &for_in.left {
+            ast::ForStatementLeft::VariableDeclaration(decl) => {
+                decl.declarations.first().and_then(|declarator| match &declarator.id.kind {
+                    ast::BindingPatternKind::BindingIdentifier(id) => Some(id.name.to_string()),
+                    _ => None,
+                })
+            }
+            ast::ForStatementLeft::AssignmentTargetIdentifier(id) => Some(id.name.to_string()),
+            _ => None,
+        };
+        if let Some(name) = key_name {
+            let var_place = Place {
+                identifier: Identifier { name, id: 0, temporary: false },
+            };
+            self.push_instruction(InstructionValue::StoreLocal(var_place, key));
+        }
+
+        self.start_loop(header_block_id, exit_block_id, Some(update_block_id));
+        self.lower_statement(&for_in.body);
+        self.end_loop();
+
+        let current_block = self.current_block_id;
+        if !self.is_block_terminated(current_block) {
+            self.terminate_block(Terminal::Goto(update_block_id));
+        }
+
+        // Update: i = i + 1
+        self.start_block(update_block_id);
+        let current = self.push_instruction(InstructionValue::LoadLocal(index.clone()));
+        let one = self.push_instruction(InstructionValue::Constant(Constant::Float(1.0)));
+        let next = self.push_instruction(InstructionValue::BinaryOp {
+            op: BinaryOperator::Add,
+            left: current,
+            right: one,
+        });
+        self.push_instruction(InstructionValue::StoreLocal(index, next));
+        self.terminate_block(Terminal::Goto(header_block_id));
+
+        self.start_block(exit_block_id);
+    }
+
     fn lower_variable_declaration(&mut self, decl: &ast::VariableDeclaration) {
         for declarator in &decl.declarations {
-            if let Some(init) = &declarator.init {
-                let value_place = self.lower_expression(init);
-                // Extract the binding identifier
-                if let ast::BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind {
-                    let var_place = Place {
-                        identifier: Identifier {
-                            name: id.name.to_string(),
-                            id: 0, // TODO: Real ID mapping
-                        },
-                    };
-                    // Emit StoreLocal: x = value
-                    self.push_instruction(InstructionValue::StoreLocal(var_place, value_place));
+            let value_place = match &declarator.init {
+                Some(init) => self.lower_expression(init),
+                // `let x;` still defines `x` (as undefined) at this point. A bare
+                // `var x;` is skipped since it never resets an existing value.
+                None if decl.kind == ast::VariableDeclarationKind::Let => {
+                    self.push_instruction(InstructionValue::Constant(Constant::Undefined))
                 }
+                None => continue,
+            };
+            // Extract the binding identifier
+            if let ast::BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind {
+                let var_place = Place {
+                    identifier: Identifier {
+                        name: id.name.to_string(),
+                        id: 0, // TODO: Real ID mapping
+                        temporary: false,
+                    },
+                };
+                // Emit StoreLocal: x = value
+                self.push_instruction(InstructionValue::StoreLocal(var_place, value_place));
             }
         }
     }
 
+    /// Lower `enum E { A, B = "b" }` to the object it evaluates to, stored to
+    /// `E`. Members count up from the last numeric value as in TypeScript,
+    /// but the reverse (value to name) mapping of numeric members is not
+    /// built. Initializers other than number and string literals are
+    /// reported as unsupported.
+    fn lower_enum_declaration(&mut self, decl: &ast::TSEnumDeclaration) {
+        let mut properties = Vec::new();
+        let mut next = Some(0.0);
+        for member in &decl.body.members {
+            let name = match &member.id {
+                ast::TSEnumMemberName::Identifier(id) => id.name.to_string(),
+                ast::TSEnumMemberName::String(s) | ast::TSEnumMemberName::ComputedString(s) => {
+                    s.value.to_string()
+                }
+                ast::TSEnumMemberName::ComputedTemplateString(_) => {
+                    self.unsupported.push("computed enum member");
+                    return;
+                }
+            };
+            let value = match (&member.initializer, next) {
+                (None, Some(n)) => Constant::Float(n),
+                (Some(Expression::NumericLiteral(lit)), _) => Constant::Float(lit.value),
+                (Some(Expression::UnaryExpression(unary)), _)
+                    if unary.operator == ast::UnaryOperator::UnaryNegation
+                        && let Expression::NumericLiteral(lit) = &unary.argument =>
+                {
+                    Constant::Float(-lit.value)
+                }
+                (Some(Expression::StringLiteral(lit)), _) => Constant::String(lit.value.to_string()),
+                _ => {
+                    self.unsupported.push("computed enum member");
+                    return;
+                }
+            };
+            next = match value {
+                Constant::Float(n) => Some(n + 1.0),
+                _ => None,
+            };
+            let value = self.push_instruction(InstructionValue::Constant(value));
+            properties.push(ObjectProperty::KeyValue {
+                key: ObjectPropertyKey::Identifier(name),
+                value,
+            });
+        }
+        let object = self.push_instruction(InstructionValue::Object { properties });
+        let var_place = Place {
+            identifier: Identifier {
+                name: decl.id.name.to_string(),
+                id: 0,
+                temporary: false,
+            },
+        };
+        self.push_instruction(InstructionValue::StoreLocal(var_place, object));
+    }
+
     fn lower_for_statement_init(&mut self, init: &ast::ForStatementInit) {
         match init {
             ast::ForStatementInit::VariableDeclaration(decl) => {
@@ -302,6 +598,32 @@ impl LoweringContext {
             Expression::UpdateExpression(update) => self.lower_update_expression(update),
             Expression::AssignmentExpression(assign) => self.lower_assignment_expression(assign),
             Expression::CallExpression(call) => self.lower_call_expression(call),
+            Expression::ParenthesizedExpression(paren) => self.lower_expression(&paren.expression),
+            // Evaluated left to right; the value is the last expression's
+            Expression::SequenceExpression(seq) => {
+                let mut value = None;
+                for expr in &seq.expressions {
+                    value = Some(self.lower_expression(expr));
+                }
+                value.unwrap_or_else(|| self.create_temp())
+            }
+            // Type-only TypeScript wrappers evaluate to the wrapped expression
+            Expression::TSAsExpression(ts) => self.lower_expression(&ts.expression),
+            Expression::TSSatisfiesExpression(ts) => self.lower_expression(&ts.expression),
+            Expression::TSNonNullExpression(ts) => self.lower_expression(&ts.expression),
+            Expression::TSTypeAssertion(ts) => self.lower_expression(&ts.expression),
+            Expression::TSInstantiationExpression(ts) => self.lower_expression(&ts.expression),
+            Expression::YieldExpression(yield_expr) => {
+                let argument = yield_expr.argument.as_ref().map(|arg| self.lower_expression(arg));
+                self.push_instruction(InstructionValue::Yield {
+                    argument,
+                    delegate: yield_expr.delegate,
+                })
+            }
+            Expression::AwaitExpression(await_expr) => {
+                let argument = self.lower_expression(&await_expr.argument);
+                self.push_instruction(InstructionValue::Await { argument })
+            }
             Expression::NumericLiteral(lit) => {
                 self.push_instruction(InstructionValue::Constant(Constant::Float(lit.value)))
             }
@@ -314,11 +636,13 @@ impl LoweringContext {
             Expression::NullLiteral(_) => {
                 self.push_instruction(InstructionValue::Constant(Constant::Null))
             }
+            Expression::ThisExpression(_) => self.lower_this(),
             Expression::Identifier(id) => {
                  let var_place = Place {
                     identifier: Identifier {
                         name: id.name.to_string(),
-                        id: 0, 
+                        id: 0,
+                        temporary: false,
                     },
                 };
                 self.push_instruction(InstructionValue::LoadLocal(var_place))
@@ -394,6 +718,9 @@ impl LoweringContext {
                     property,
                 })
             }
+            Expression::ChainExpression(chain) => self.lower_chain_expression(chain),
+            Expression::JSXElement(element) => self.lower_jsx_element(element),
+            Expression::JSXFragment(fragment) => self.lower_jsx_fragment(fragment),
             Expression::LogicalExpression(logical) => {
                 let left = self.lower_expression(&logical.left);
                 let right_block_id = self.next_block_id();
@@ -519,7 +846,10 @@ impl LoweringContext {
                     self.push_instruction(InstructionValue::Constant(Constant::String(String::new())))
                 })
             }
-            _ => self.create_temp(),
+            _ => {
+                self.unsupported.push(describe_expression(expr));
+                self.create_temp()
+            }
         }
     }
 
@@ -532,6 +862,7 @@ impl LoweringContext {
             ast::BinaryOperator::Multiplication => BinaryOperator::Mul,
             ast::BinaryOperator::Division => BinaryOperator::Div,
             ast::BinaryOperator::Remainder => BinaryOperator::Mod,
+            ast::BinaryOperator::Exponential => BinaryOperator::Exponent,
             ast::BinaryOperator::LessThan => BinaryOperator::LessThan,
             ast::BinaryOperator::LessEqualThan => BinaryOperator::LessThanEqual,
             ast::BinaryOperator::GreaterThan => BinaryOperator::GreaterThan,
@@ -548,12 +879,46 @@ impl LoweringContext {
             ast::BinaryOperator::ShiftRightZeroFill => BinaryOperator::UnsignedRightShift,
             ast::BinaryOperator::Instanceof => BinaryOperator::InstanceOf,
             ast::BinaryOperator::In => BinaryOperator::In,
-            _ => BinaryOperator::Add,
         };
         self.push_instruction(InstructionValue::BinaryOp { op, left, right })
     }
 
     fn lower_unary_expression(&mut self, unary: &ast::UnaryExpression) -> Place {
+        // `delete` needs the member expression itself, not the value it reads
+        if unary.operator == ast::UnaryOperator::Delete {
+            match &unary.argument {
+                Expression::StaticMemberExpression(member) => {
+                    let object = self.lower_expression(&member.object);
+                    return self.push_instruction(InstructionValue::PropertyDelete {
+                        object,
+                        property: member.property.name.to_string(),
+                    });
+                }
+                Expression::ComputedMemberExpression(member) => {
+                    let object = self.lower_expression(&member.object);
+                    let property = self.lower_expression(&member.expression);
+                    return self.push_instruction(InstructionValue::ComputedDelete { object, property });
+                }
+                _ => {}
+            }
+        }
+
+        // `typeof` reads a variable by name rather than through a loaded
+        // temp: for an undeclared global it is "undefined", where loading the
+        // global first would throw
+        if unary.operator == ast::UnaryOperator::Typeof
+            && let Expression::Identifier(id) = &unary.argument
+        {
+            let operand = Place {
+                identifier: Identifier {
+                    name: id.name.to_string(),
+                    id: 0,
+                    temporary: false,
+                },
+            };
+            return self.push_instruction(InstructionValue::UnaryOp { op: UnaryOperator::TypeOf, operand });
+        }
+
         let operand = self.lower_expression(&unary.argument);
         let op = match unary.operator {
             ast::UnaryOperator::LogicalNot => UnaryOperator::Not,
@@ -574,10 +939,14 @@ impl LoweringContext {
                     identifier: Identifier {
                         name: id.name.to_string(),
                         id: 0,
+                        temporary: false,
                     },
                 }
             }
-            _ => return self.create_temp(),
+            _ => {
+                self.unsupported.push("update of a member expression");
+                return self.create_temp();
+            }
         };
 
         let current = self.push_instruction(InstructionValue::LoadLocal(arg_place.clone()));
@@ -598,25 +967,60 @@ impl LoweringContext {
     }
 
     fn lower_assignment_expression(&mut self, assign: &ast::AssignmentExpression) -> Place {
-        let right_value = self.lower_expression(&assign.right);
-        
+        // Member targets evaluate their object and key before the right-hand side
+        let member_target = match &assign.left {
+            ast::AssignmentTarget::StaticMemberExpression(static_expr) => {
+                let object = self.lower_expression(&static_expr.object);
+                Some(MemberTarget::Static {
+                    object,
+                    property: static_expr.property.name.to_string(),
+                })
+            }
+            ast::AssignmentTarget::ComputedMemberExpression(computed_expr) => {
+                let object = self.lower_expression(&computed_expr.object);
+                let property = self.lower_expression(&computed_expr.expression);
+                Some(MemberTarget::Computed { object, property })
+            }
+            _ => None,
+        };
+
         // Handle compound assignments (+=, -=, etc)
         let value = if assign.operator == ast::AssignmentOperator::Assign {
-            right_value
+            self.lower_expression(&assign.right)
         } else {
             // Lower left side for reading
-            let left_value = match &assign.left {
-                ast::AssignmentTarget::AssignmentTargetIdentifier(id) => {
+            let left_value = match (&assign.left, &member_target) {
+                (ast::AssignmentTarget::AssignmentTargetIdentifier(id), _) => {
                     let place = Place {
                         identifier: Identifier {
                             name: id.name.to_string(),
                             id: 0,
+                            temporary: false,
                         },
                     };
                     self.push_instruction(InstructionValue::LoadLocal(place))
                 }
-                _ => panic!("Complex compound assignment targets not yet supported"),
+                (_, Some(MemberTarget::Static { object, property })) => {
+                    self.push_instruction(InstructionValue::PropertyLoad {
+                        object: object.clone(),
+                        property: property.clone(),
+                    })
+                }
+                (_, Some(MemberTarget::Computed { object, property })) => {
+                    self.push_instruction(InstructionValue::ComputedLoad {
+                        object: object.clone(),
+                        property: property.clone(),
+                    })
+                }
+                _ => {
+                    self.unsupported.push("compound assignment to this target");
+                    return self.create_temp();
+                }
             };
+            if assign.operator.is_logical() {
+                return self.lower_logical_assignment(assign, left_value, member_target);
+            }
+            let right_value = self.lower_expression(&assign.right);
 
             let op = match assign.operator {
                 ast::AssignmentOperator::Addition => BinaryOperator::Add,
@@ -624,13 +1028,18 @@ impl LoweringContext {
                 ast::AssignmentOperator::Multiplication => BinaryOperator::Mul,
                 ast::AssignmentOperator::Division => BinaryOperator::Div,
                 ast::AssignmentOperator::Remainder => BinaryOperator::Mod,
+                ast::AssignmentOperator::Exponential => BinaryOperator::Exponent,
                 ast::AssignmentOperator::BitwiseOR => BinaryOperator::BitwiseOr,
                 ast::AssignmentOperator::BitwiseXOR => BinaryOperator::BitwiseXor,
                 ast::AssignmentOperator::BitwiseAnd => BinaryOperator::BitwiseAnd,
                 ast::AssignmentOperator::ShiftLeft => BinaryOperator::LeftShift,
                 ast::AssignmentOperator::ShiftRight => BinaryOperator::RightShift,
                 ast::AssignmentOperator::ShiftRightZeroFill => BinaryOperator::UnsignedRightShift,
-                _ => panic!("Unsupported assignment operator: {:?}", assign.operator),
+                // Logical assignments are lowered above
+                operator => {
+                    self.fail(format!("unexpected compound assignment operator {:?}", operator));
+                    return left_value;
+                }
             };
             
             self.push_instruction(InstructionValue::BinaryOp {
@@ -640,32 +1049,76 @@ impl LoweringContext {
             })
         };
 
-        match &assign.left {
+        self.store_assignment(&assign.left, member_target, value.clone());
+        value
+    }
+
+    /// `x &&= y`, `x ||= y` and `x ??= y`: `y` is only evaluated, and `x`
+    /// only assigned, when the current value of `x` doesn't short-circuit.
+    fn lower_logical_assignment(
+        &mut self,
+        assign: &ast::AssignmentExpression,
+        left: Place,
+        member_target: Option<MemberTarget>,
+    ) -> Place {
+        let assign_block_id = self.next_block_id();
+        let short_circuit_block_id = self.next_block_id();
+        let merge_block_id = self.next_block_id();
+        let result_place = self.create_temp();
+
+        let (test, consequent, alternate) = match assign.operator {
+            ast::AssignmentOperator::LogicalAnd => (left.clone(), assign_block_id, short_circuit_block_id),
+            ast::AssignmentOperator::LogicalOr => (left.clone(), short_circuit_block_id, assign_block_id),
+            _ => {
+                let is_nullish = self.push_instruction(InstructionValue::UnaryOp {
+                    op: UnaryOperator::IsNullish,
+                    operand: left.clone(),
+                });
+                (is_nullish, assign_block_id, short_circuit_block_id)
+            }
+        };
+        self.terminate_block(Terminal::If { test, consequent, alternate });
+
+        self.start_block(short_circuit_block_id);
+        self.push_instruction(InstructionValue::StoreLocal(result_place.clone(), left));
+        self.terminate_block(Terminal::Goto(merge_block_id));
+
+        self.start_block(assign_block_id);
+        let right = self.lower_expression(&assign.right);
+        self.store_assignment(&assign.left, member_target, right.clone());
+        self.push_instruction(InstructionValue::StoreLocal(result_place.clone(), right));
+        self.terminate_block(Terminal::Goto(merge_block_id));
+
+        self.start_block(merge_block_id);
+        self.push_instruction(InstructionValue::LoadLocal(result_place))
+    }
+
+    /// Store an assignment's `value` into the member `member_target` or,
+    /// when there isn't one, the variable or pattern `target`.
+    fn store_assignment(&mut self, target: &ast::AssignmentTarget, member_target: Option<MemberTarget>, value: Place) {
+        match member_target {
+            Some(MemberTarget::Static { object, property }) => {
+                self.push_instruction(InstructionValue::PropertyStore { object, property, value });
+            }
+            Some(MemberTarget::Computed { object, property }) => {
+                self.push_instruction(InstructionValue::ComputedStore { object, property, value });
+            }
+            None => self.lower_assignment_target(target, value),
+        }
+    }
+
+    /// Assign `value` to a plain variable or a (possibly nested) destructuring pattern.
+    fn lower_assignment_target(&mut self, target: &ast::AssignmentTarget, value: Place) {
+        match target {
             ast::AssignmentTarget::AssignmentTargetIdentifier(id) => {
                 let var_place = Place {
                     identifier: Identifier {
                         name: id.name.to_string(),
                         id: 0,
+                        temporary: false,
                     },
                 };
-                self.push_instruction(InstructionValue::StoreLocal(var_place, value.clone()));
-            }
-            ast::AssignmentTarget::StaticMemberExpression(static_expr) => {
-                let object = self.lower_expression(&static_expr.object);
-                self.push_instruction(InstructionValue::PropertyStore {
-                    object,
-                    property: static_expr.property.name.to_string(),
-                    value: value.clone(),
-                });
-            }
-            ast::AssignmentTarget::ComputedMemberExpression(computed_expr) => {
-                let object = self.lower_expression(&computed_expr.object);
-                let property = self.lower_expression(&computed_expr.expression);
-                self.push_instruction(InstructionValue::ComputedStore {
-                    object,
-                    property,
-                    value: value.clone(),
-                });
+                self.push_instruction(InstructionValue::StoreLocal(var_place, value));
             }
             ast::AssignmentTarget::ArrayAssignmentTarget(arr_target) => {
                 for (idx, element) in arr_target.elements.iter().enumerate() {
@@ -675,18 +1128,7 @@ impl LoweringContext {
                             object: value.clone(),
                             property: idx_place,
                         });
-                        match target {
-                            ast::AssignmentTargetMaybeDefault::AssignmentTargetIdentifier(id) => {
-                                let var_place = Place {
-                                    identifier: Identifier {
-                                        name: id.name.to_string(),
-                                        id: 0,
-                                    },
-                                };
-                                self.push_instruction(InstructionValue::StoreLocal(var_place, elem_value));
-                            }
-                            _ => {}
-                        }
+                        self.store_to_target_maybe_default(target, elem_value);
                     }
                 }
             }
@@ -695,14 +1137,18 @@ impl LoweringContext {
                     match prop {
                         ast::AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(id_prop) => {
                             let prop_name = id_prop.binding.name.to_string();
-                            let prop_value = self.push_instruction(InstructionValue::PropertyLoad {
+                            let mut prop_value = self.push_instruction(InstructionValue::PropertyLoad {
                                 object: value.clone(),
                                 property: prop_name.clone(),
                             });
+                            if let Some(init) = &id_prop.init {
+                                prop_value = self.lower_default_value(prop_value, init);
+                            }
                             let var_place = Place {
                                 identifier: Identifier {
                                     name: prop_name,
                                     id: 0,
+                                    temporary: false,
                                 },
                             };
                             self.push_instruction(InstructionValue::StoreLocal(var_place, prop_value));
@@ -717,30 +1163,369 @@ impl LoweringContext {
                                 object: value.clone(),
                                 property: key_name,
                             });
-                            match &key_prop.binding {
-                                ast::AssignmentTargetMaybeDefault::AssignmentTargetIdentifier(id) => {
-                                    let var_place = Place {
-                                        identifier: Identifier {
-                                            name: id.name.to_string(),
-                                            id: 0,
-                                        },
-                                    };
-                                    self.push_instruction(InstructionValue::StoreLocal(var_place, prop_value));
-                                }
-                                _ => {}
-                            }
+                            self.store_to_target_maybe_default(&key_prop.binding, prop_value);
                         }
                     }
                 }
             }
             _ => {}
         }
-        value
+    }
+
+    fn lower_jsx_element(&mut self, element: &ast::JSXElement) -> Place {
+        let tag = self.lower_jsx_tag(&element.opening_element.name);
+
+        let mut props = Vec::new();
+        let mut key = None;
+        let mut ref_ = None;
+        let mut after_spread = false;
+        for item in &element.opening_element.attributes {
+            match item {
+                ast::JSXAttributeItem::Attribute(attr) => {
+                    let name = match &attr.name {
+                        ast::JSXAttributeName::Identifier(id) => id.name.to_string(),
+                        ast::JSXAttributeName::NamespacedName(ns) => {
+                            format!("{}:{}", ns.namespace.name, ns.name.name)
+                        }
+                    };
+                    // A bare attribute (`<input disabled />`) is `true`
+                    let value = match &attr.value {
+                        None => self.push_instruction(InstructionValue::Constant(Constant::Boolean(true))),
+                        Some(ast::JSXAttributeValue::StringLiteral(lit)) => self.push_instruction(
+                            InstructionValue::Constant(Constant::String(decode_jsx_entities(&lit.value))),
+                        ),
+                        Some(ast::JSXAttributeValue::ExpressionContainer(container)) => {
+                            match container.expression.as_expression() {
+                                Some(expr) => self.lower_expression(expr),
+                                None => continue,
+                            }
+                        }
+                        Some(ast::JSXAttributeValue::Element(element)) => self.lower_jsx_element(element),
+                        Some(ast::JSXAttributeValue::Fragment(fragment)) => self.lower_jsx_fragment(fragment),
+                    };
+                    // A spread may carry its own `key` or `ref`, so one written
+                    // after it has to stay in order with the props
+                    match name.as_str() {
+                        "key" if !after_spread => key = Some(value),
+                        "ref" if !after_spread => ref_ = Some(value),
+                        _ => props.push(ObjectProperty::KeyValue {
+                            key: ObjectPropertyKey::Identifier(name),
+                            value,
+                        }),
+                    }
+                }
+                ast::JSXAttributeItem::SpreadAttribute(spread) => {
+                    props.push(ObjectProperty::Spread(self.lower_expression(&spread.argument)));
+                    after_spread = true;
+                }
+            }
+        }
+
+        let children = self.lower_jsx_children(&element.children);
+        self.push_instruction(InstructionValue::JsxElement { tag, props, children, key, ref_ })
+    }
+
+    fn lower_jsx_fragment(&mut self, fragment: &ast::JSXFragment) -> Place {
+        let children = self.lower_jsx_children(&fragment.children);
+        self.push_instruction(InstructionValue::JsxFragment { children })
+    }
+
+    /// Lowercase tags are intrinsic elements and become strings; anything
+    /// else names a component in scope.
+    fn lower_jsx_tag(&mut self, name: &ast::JSXElementName) -> Place {
+        match name {
+            ast::JSXElementName::Identifier(id) => {
+                self.push_instruction(InstructionValue::Constant(Constant::String(id.name.to_string())))
+            }
+            ast::JSXElementName::NamespacedName(ns) => self.push_instruction(InstructionValue::Constant(
+                Constant::String(format!("{}:{}", ns.namespace.name, ns.name.name)),
+            )),
+            ast::JSXElementName::IdentifierReference(id) => self.lower_jsx_reference(&id.name),
+            ast::JSXElementName::MemberExpression(member) => self.lower_jsx_member(member),
+            ast::JSXElementName::ThisExpression(_) => self.lower_this(),
+        }
+    }
+
+    fn lower_jsx_member(&mut self, member: &ast::JSXMemberExpression) -> Place {
+        let object = match &member.object {
+            ast::JSXMemberExpressionObject::IdentifierReference(id) => self.lower_jsx_reference(&id.name),
+            ast::JSXMemberExpressionObject::MemberExpression(inner) => self.lower_jsx_member(inner),
+            ast::JSXMemberExpressionObject::ThisExpression(_) => self.lower_this(),
+        };
+        self.push_instruction(InstructionValue::PropertyLoad {
+            object,
+            property: member.property.name.to_string(),
+        })
+    }
+
+    /// `this` is read like a variable that is never reassigned.
+    fn lower_this(&mut self) -> Place {
+        if !self.method {
+            self.unsupported.push("`this`");
+            return self.create_temp();
+        }
+        self.lower_jsx_reference("this")
+    }
+
+    fn lower_jsx_reference(&mut self, name: &str) -> Place {
+        self.push_instruction(InstructionValue::LoadLocal(Place {
+            identifier: Identifier {
+                name: name.to_string(),
+                id: 0,
+                temporary: false,
+            },
+        }))
+    }
+
+    fn lower_jsx_children(&mut self, children: &[ast::JSXChild]) -> Vec<Argument> {
+        let mut result = Vec::new();
+        for child in children {
+            match child {
+                ast::JSXChild::Text(text) => {
+                    if let Some(text) = clean_jsx_text(&text.value) {
+                        let place = self.push_instruction(InstructionValue::Constant(Constant::String(text)));
+                        result.push(Argument::Regular(place));
+                    }
+                }
+                ast::JSXChild::Element(element) => {
+                    result.push(Argument::Regular(self.lower_jsx_element(element)));
+                }
+                ast::JSXChild::Fragment(fragment) => {
+                    result.push(Argument::Regular(self.lower_jsx_fragment(fragment)));
+                }
+                ast::JSXChild::ExpressionContainer(container) => {
+                    // `{/* comment */}` has no value
+                    if let Some(expr) = container.expression.as_expression() {
+                        result.push(Argument::Regular(self.lower_expression(expr)));
+                    }
+                }
+                ast::JSXChild::Spread(spread) => {
+                    result.push(Argument::Spread(self.lower_expression(&spread.expression)));
+                }
+            }
+        }
+        result
+    }
+
+    /// Store a destructured value into a target, applying its default
+    /// (`[a = 1] = arr`) first if it has one.
+    fn store_to_target_maybe_default(&mut self, target: &ast::AssignmentTargetMaybeDefault, value: Place) {
+        match target {
+            ast::AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(with_default) => {
+                let value = self.lower_default_value(value, &with_default.init);
+                self.lower_assignment_target(&with_default.binding, value);
+            }
+            _ => {
+                if let Some(target) = target.as_assignment_target() {
+                    self.lower_assignment_target(target, value);
+                }
+            }
+        }
+    }
+
+    /// `value === undefined ? default : value`, evaluating `default` only when
+    /// it is needed. `null` does not trigger a default.
+    fn lower_default_value(&mut self, value: Place, default: &Expression) -> Place {
+        let undefined = self.push_instruction(InstructionValue::Constant(Constant::Undefined));
+        let test = self.push_instruction(InstructionValue::BinaryOp {
+            op: BinaryOperator::StrictEqual,
+            left: value.clone(),
+            right: undefined,
+        });
+
+        let default_block_id = self.next_block_id();
+        let value_block_id = self.next_block_id();
+        let merge_block_id = self.next_block_id();
+        let result_place = self.create_temp();
+
+        self.terminate_block(Terminal::If {
+            test,
+            consequent: default_block_id,
+            alternate: value_block_id,
+        });
+
+        self.start_block(default_block_id);
+        let default_value = self.lower_expression(default);
+        self.push_instruction(InstructionValue::StoreLocal(result_place.clone(), default_value));
+        self.terminate_block(Terminal::Goto(merge_block_id));
+
+        self.start_block(value_block_id);
+        self.push_instruction(InstructionValue::StoreLocal(result_place.clone(), value));
+        self.terminate_block(Terminal::Goto(merge_block_id));
+
+        self.start_block(merge_block_id);
+        self.push_instruction(InstructionValue::LoadLocal(result_place))
     }
 
     fn lower_call_expression(&mut self, call: &ast::CallExpression) -> Place {
+        // Method calls keep their receiver so that `this` is bound correctly
+        if let Expression::StaticMemberExpression(member) = &call.callee {
+            let receiver = self.lower_expression(&member.object);
+            let args = self.lower_arguments(&call.arguments);
+            return self.push_instruction(InstructionValue::MethodCall {
+                receiver,
+                property: member.property.name.to_string(),
+                args,
+                pure: call.pure,
+            });
+        }
+
         let callee = self.lower_expression(&call.callee);
-        let args = call.arguments.iter().map(|arg| {
+        let args = self.lower_arguments(&call.arguments);
+        self.push_instruction(InstructionValue::Call {
+            callee,
+            args,
+            pure: call.pure,
+        })
+    }
+
+    /// Lower an optional chain (`a?.b?.c()`). Every `?.` link tests its object
+    /// and, if nullish, jumps straight to a shared block that makes the whole
+    /// chain evaluate to `undefined` without evaluating the rest of it.
+    fn lower_chain_expression(&mut self, chain: &ast::ChainExpression) -> Place {
+        let nullish_block_id = self.next_block_id();
+        let merge_block_id = self.next_block_id();
+        let result_place = self.create_temp();
+
+        let value = match &chain.expression {
+            ast::ChainElement::CallExpression(call) => self.lower_chain_call(call, nullish_block_id),
+            ast::ChainElement::StaticMemberExpression(member) => {
+                self.lower_chain_static_member(member, nullish_block_id)
+            }
+            ast::ChainElement::ComputedMemberExpression(member) => {
+                self.lower_chain_computed_member(member, nullish_block_id)
+            }
+            // `o?.b!` is still `o?.b`
+            ast::ChainElement::TSNonNullExpression(ts) => self.lower_chain_object(&ts.expression, nullish_block_id),
+            ast::ChainElement::PrivateFieldExpression(_) => {
+                self.unsupported.push("private field in an optional chain");
+                self.create_temp()
+            }
+        };
+        self.push_instruction(InstructionValue::StoreLocal(result_place.clone(), value));
+        self.terminate_block(Terminal::Goto(merge_block_id));
+
+        self.start_block(nullish_block_id);
+        let undefined = self.push_instruction(InstructionValue::Constant(Constant::Undefined));
+        self.push_instruction(InstructionValue::StoreLocal(result_place.clone(), undefined));
+        self.terminate_block(Terminal::Goto(merge_block_id));
+
+        self.start_block(merge_block_id);
+        self.push_instruction(InstructionValue::LoadLocal(result_place))
+    }
+
+    /// Lower the object of a link in an optional chain. Nested member and call
+    /// expressions belong to the same chain and share its nullish exit.
+    fn lower_chain_object(&mut self, expr: &Expression, nullish_block_id: BlockId) -> Place {
+        match expr {
+            Expression::CallExpression(call) => self.lower_chain_call(call, nullish_block_id),
+            Expression::StaticMemberExpression(member) => {
+                self.lower_chain_static_member(member, nullish_block_id)
+            }
+            Expression::ComputedMemberExpression(member) => {
+                self.lower_chain_computed_member(member, nullish_block_id)
+            }
+            // Type-only wrappers stay part of the chain: `o?.b!.c` skips `.c`
+            // when `o` is nullish, like `o?.b.c`
+            Expression::TSNonNullExpression(ts) => self.lower_chain_object(&ts.expression, nullish_block_id),
+            Expression::TSAsExpression(ts) => self.lower_chain_object(&ts.expression, nullish_block_id),
+            Expression::TSSatisfiesExpression(ts) => self.lower_chain_object(&ts.expression, nullish_block_id),
+            Expression::TSTypeAssertion(ts) => self.lower_chain_object(&ts.expression, nullish_block_id),
+            Expression::TSInstantiationExpression(ts) => self.lower_chain_object(&ts.expression, nullish_block_id),
+            _ => self.lower_expression(expr),
+        }
+    }
+
+    fn lower_chain_static_member(
+        &mut self,
+        member: &ast::StaticMemberExpression,
+        nullish_block_id: BlockId,
+    ) -> Place {
+        let object = self.lower_chain_object(&member.object, nullish_block_id);
+        if member.optional {
+            self.branch_if_nullish(object.clone(), nullish_block_id);
+        }
+        self.push_instruction(InstructionValue::PropertyLoad {
+            object,
+            property: member.property.name.to_string(),
+        })
+    }
+
+    fn lower_chain_computed_member(
+        &mut self,
+        member: &ast::ComputedMemberExpression,
+        nullish_block_id: BlockId,
+    ) -> Place {
+        let object = self.lower_chain_object(&member.object, nullish_block_id);
+        if member.optional {
+            self.branch_if_nullish(object.clone(), nullish_block_id);
+        }
+        let property = self.lower_expression(&member.expression);
+        self.push_instruction(InstructionValue::ComputedLoad { object, property })
+    }
+
+    fn lower_chain_call(&mut self, call: &ast::CallExpression, nullish_block_id: BlockId) -> Place {
+        if let Expression::StaticMemberExpression(member) = &call.callee {
+            let receiver = self.lower_chain_object(&member.object, nullish_block_id);
+            if member.optional {
+                self.branch_if_nullish(receiver.clone(), nullish_block_id);
+            }
+            if call.optional {
+                // `a.b?.()` tests the method itself, then calls what it read with
+                // the receiver as `this` rather than reading `a.b` again
+                let method = self.push_instruction(InstructionValue::PropertyLoad {
+                    object: receiver.clone(),
+                    property: member.property.name.to_string(),
+                });
+                self.branch_if_nullish(method.clone(), nullish_block_id);
+                let mut args = vec![Argument::Regular(receiver)];
+                args.extend(self.lower_arguments(&call.arguments));
+                return self.push_instruction(InstructionValue::MethodCall {
+                    receiver: method,
+                    property: "call".to_string(),
+                    args,
+                    pure: call.pure,
+                });
+            }
+            let args = self.lower_arguments(&call.arguments);
+            return self.push_instruction(InstructionValue::MethodCall {
+                receiver,
+                property: member.property.name.to_string(),
+                args,
+                pure: call.pure,
+            });
+        }
+
+        let callee = self.lower_chain_object(&call.callee, nullish_block_id);
+        if call.optional {
+            self.branch_if_nullish(callee.clone(), nullish_block_id);
+        }
+        let args = self.lower_arguments(&call.arguments);
+        self.push_instruction(InstructionValue::Call {
+            callee,
+            args,
+            pure: call.pure,
+        })
+    }
+
+    /// Jump to `nullish_block_id` if `value` is null or undefined, otherwise
+    /// continue in a fresh block.
+    fn branch_if_nullish(&mut self, value: Place, nullish_block_id: BlockId) {
+        let is_nullish = self.push_instruction(InstructionValue::UnaryOp {
+            op: UnaryOperator::IsNullish,
+            operand: value,
+        });
+        let continue_block_id = self.next_block_id();
+        self.terminate_block(Terminal::If {
+            test: is_nullish,
+            consequent: nullish_block_id,
+            alternate: continue_block_id,
+        });
+        self.start_block(continue_block_id);
+    }
+
+    fn lower_arguments(&mut self, arguments: &[ast::Argument]) -> Vec<Argument> {
+        arguments.iter().map(|arg| {
             match arg {
                 ast::Argument::SpreadElement(spread) => {
                     let place = self.lower_expression(&spread.argument);
@@ -754,15 +1539,109 @@ impl LoweringContext {
                     }
                 }
             }
-        }).collect();
+        }).collect()
+    }
+
+    fn lower_labeled_statement(&mut self, labeled: &'a ast::LabeledStatement<'a>) {
+        let label = labeled.label.name.to_string();
+        match &labeled.body {
+            // Loops and switches own their break target; they pick up the label in
+            // `start_loop` / `lower_switch_statement`
+            Statement::WhileStatement(_)
+            | Statement::ForStatement(_)
+            | Statement::ForInStatement(_)
+            | Statement::SwitchStatement(_) => {
+                self.pending_label = Some(label);
+                self.lower_statement(&labeled.body);
+            }
+            body => {
+                // Any other statement is a labeled block: `break label` jumps past it
+                let exit_block_id = self.next_block_id();
+                self.loop_stack.push(LoopInfo {
+                    break_target: exit_block_id,
+                    continue_target: None,
+                    label: Some(label),
+                    label_only: true,
+                });
+
+                self.lower_statement(body);
+                self.loop_stack.pop();
+
+                let current_block = self.current_block_id;
+                if !self.is_block_terminated(current_block) {
+                    self.terminate_block(Terminal::Goto(exit_block_id));
+                }
+                self.start_block(exit_block_id);
+            }
+        }
+    }
 
-        self.push_instruction(InstructionValue::Call { callee, args })
+    /// Whether `expr` is a `console.<method>(...)` call that `strip_console`
+    /// removes.
+    fn is_stripped_console_call(&self, expr: &Expression) -> bool {
+        let Some(keep) = &self.strip_console else {
+            return false;
+        };
+        let Expression::CallExpression(call) = expr.without_parentheses() else {
+            return false;
+        };
+        let Expression::StaticMemberExpression(member) = call.callee.without_parentheses() else {
+            return false;
+        };
+        matches!(&member.object, Expression::Identifier(object) if object.name == "console")
+            && !keep.iter().any(|method| method == member.property.name.as_str())
+    }
+
+    /// Lower `try { block } finally { finalizer }` without exception edges:
+    /// the finalizer is copied onto every path that leaves the block, so a
+    /// `return`, `break` or `continue` inside it runs the finalizer first.
+    ///
+    /// The HIR has no notion of a throw, so an exception thrown inside the
+    /// block skips the finalizer.
+    fn lower_try_finally(&mut self, try_stmt: &'a ast::TryStatement<'a>) {
+        let Some(finalizer) = &try_stmt.finalizer else {
+            return;
+        };
+        self.finalizers.push(Finalizer {
+            block: finalizer,
+            loop_depth: self.loop_stack.len(),
+        });
+        for stmt in &try_stmt.block.body {
+            self.lower_statement(stmt);
+        }
+        self.finalizers.pop();
+
+        for stmt in &finalizer.body {
+            self.lower_statement(stmt);
+        }
+    }
+
+    /// Lower the `finally` blocks a jump leaves, innermost first: those of
+    /// every `try` with at least `loop_depth` loops around it, i.e. nested
+    /// in the jump's target.
+    ///
+    /// Each copy is lowered as if at its `try`, outside the loops and `try`
+    /// statements nested in it, so jumps in a `finally` block find the right
+    /// target and only run the finalizers further out.
+    fn lower_finalizers(&mut self, loop_depth: usize) {
+        let mut left = Vec::new();
+        while let Some(finalizer) = self.finalizers.pop_if(|finalizer| finalizer.loop_depth >= loop_depth) {
+            let inner_loops = self.loop_stack.split_off(finalizer.loop_depth);
+            for stmt in &finalizer.block.body {
+                self.lower_statement(stmt);
+            }
+            self.loop_stack.extend(inner_loops);
+            left.push(finalizer);
+        }
+        self.finalizers.extend(left.into_iter().rev());
     }
 
     fn start_loop(&mut self, header_id: BlockId, break_target: BlockId, continue_target: Option<BlockId>) {
         self.loop_stack.push(LoopInfo {
             break_target,
             continue_target,
+            label: self.pending_label.take(),
+            label_only: false,
         });
         self.loop_headers.insert(header_id);
     }
@@ -771,25 +1650,25 @@ impl LoweringContext {
         self.loop_stack.pop();
     }
 
-    fn lower_switch_statement(&mut self, switch_stmt: &ast::SwitchStatement) {
+    fn lower_switch_statement(&mut self, switch_stmt: &'a ast::SwitchStatement<'a>) {
         let discriminant = self.lower_expression(&switch_stmt.discriminant);
         let exit_block = self.next_block_id();
         
         self.loop_stack.push(LoopInfo {
             break_target: exit_block,
             continue_target: None,
+            label: self.pending_label.take(),
+            label_only: false,
         });
         
         // Generate block IDs for all cases and default
         let mut case_blocks = Vec::with_capacity(switch_stmt.cases.len());
         let mut default_block_id = exit_block; // Fallback if no default
-        let mut has_default = false;
 
         for case in &switch_stmt.cases {
             let blk = self.next_block_id();
             case_blocks.push((blk, case));
             if case.test.is_none() {
-                has_default = true;
                 default_block_id = blk;
             }
         }
@@ -848,23 +1727,31 @@ impl LoweringContext {
             scope: None,
         };
         
-        let block = self.blocks.get_mut(&self.current_block_id).unwrap();
-        block.instructions.push(instr);
+        self.current_block().instructions.push(instr);
         
         temp
     }
 
+    /// Record an internal error; lowering carries on so the caller gets the
+    /// first one from `build_checked`.
+    fn fail(&mut self, message: String) {
+        self.error.get_or_insert(CompilerError::LoweringError { message });
+    }
+
     fn start_block(&mut self, id: BlockId) {
-        if !self.blocks.contains_key(&id) {
-             let new_block = BasicBlock {
-                id,
-                instructions: Vec::new(),
-                terminal: Terminal::Return(None), // Default
-                preds: Vec::new(),
-            };
-            self.blocks.insert(id, new_block);
-        }
         self.current_block_id = id;
+        self.current_block();
+    }
+
+    /// The block being lowered into, created empty if it is not there yet.
+    fn current_block(&mut self) -> &mut BasicBlock {
+        let id = self.current_block_id;
+        self.blocks.entry(id).or_insert_with(|| BasicBlock {
+            id,
+            instructions: Vec::new(),
+            terminal: Terminal::Return(None), // Default
+            preds: Vec::new(),
+        })
     }
 
     fn is_block_terminated(&self, id: BlockId) -> bool {
@@ -873,8 +1760,7 @@ impl LoweringContext {
 
     fn terminate_block(&mut self, terminal: Terminal) {
         self.terminated_blocks.insert(self.current_block_id);
-        let block = self.blocks.get_mut(&self.current_block_id).unwrap();
-        block.terminal = terminal;
+        self.current_block().terminal = terminal;
         
         let new_block_id = self.next_block_id();
         self.start_block(new_block_id);
@@ -887,6 +1773,7 @@ impl LoweringContext {
             identifier: Identifier {
                 name: format!("t{}", id),
                 id,
+                temporary: true,
             },
         }
     }
@@ -904,8 +1791,230 @@ impl LoweringContext {
     }
 }
 
-impl Default for LoweringContext {
+/// Give each temporary whose name a source binding also uses, like a
+/// variable called `t1`, the next free name instead, so later passes and the
+/// generated code can't confuse the two.
+fn rename_clashing_temporaries(hir: &mut HIRFunction, mut next_temp_id: usize) {
+    let mut taken: HashSet<String> = hir.params.iter().map(|param| param.name.clone()).collect();
+    taken.extend(
+        hir.places_mut()
+            .into_iter()
+            .filter(|place| !place.identifier.temporary)
+            .map(|place| place.identifier.name.clone()),
+    );
+    let mut renamed: HashMap<usize, usize> = HashMap::new();
+    for place in hir.places_mut() {
+        let id = &mut place.identifier;
+        if !id.temporary || !taken.contains(&id.name) {
+            continue;
+        }
+        let new_id = *renamed.entry(id.id).or_insert_with(|| {
+            while taken.contains(&format!("t{}", next_temp_id)) {
+                next_temp_id += 1;
+            }
+            next_temp_id += 1;
+            next_temp_id - 1
+        });
+        id.id = new_id;
+        id.name = format!("t{}", new_id);
+    }
+}
+
+/// A directive as written, quotes included: `"use client"`.
+pub fn directive_source(directive: &ast::Directive) -> String {
+    match &directive.expression.raw {
+        Some(raw) => raw.to_string(),
+        None => format!("\"{}\"", directive.directive),
+    }
+}
+
+/// Collapse JSX text the way Babel does: lines are trimmed, whitespace-only
+/// lines are dropped, and the rest are joined with single spaces. Returns
+/// `None` if nothing but whitespace is left.
+fn clean_jsx_text(value: &str) -> Option<String> {
+    let lines: Vec<&str> = value.split("\r\n").flat_map(|l| l.split(['\n', '\r'])).collect();
+    let last_non_empty = lines
+        .iter()
+        .rposition(|line| line.chars().any(|c| c != ' ' && c != '\t'))?;
+
+    let mut text = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let mut line = line.replace('\t', " ");
+        if idx != 0 {
+            line = line.trim_start_matches(' ').to_string();
+        }
+        if idx != lines.len() - 1 {
+            line = line.trim_end_matches(' ').to_string();
+        }
+        if !line.is_empty() {
+            text.push_str(&line);
+            if idx != last_non_empty {
+                text.push(' ');
+            }
+        }
+    }
+    Some(decode_jsx_entities(&text))
+}
+
+/// Decode the HTML entities JSX text and attribute strings may contain.
+/// Only numeric references and the common named ones are recognized.
+fn decode_jsx_entities(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => entity.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                    };
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn describe_statement(stmt: &Statement) -> &'static str {
+    match stmt {
+        Statement::DoWhileStatement(_) => "do...while loop",
+        Statement::ForOfStatement(_) => "for...of loop",
+        Statement::ThrowStatement(_) => "throw statement",
+        Statement::TryStatement(_) => "try...catch statement",
+        Statement::FunctionDeclaration(_) => "nested function declaration",
+        Statement::ClassDeclaration(_) => "class declaration",
+        Statement::TSModuleDeclaration(_) => "namespace declaration",
+        _ => "statement",
+    }
+}
+
+fn describe_expression(expr: &Expression) -> &'static str {
+    match expr {
+        Expression::ArrowFunctionExpression(_) => "arrow function",
+        Expression::FunctionExpression(_) => "function expression",
+        Expression::ClassExpression(_) => "class expression",
+        Expression::NewExpression(_) => "`new` expression",
+        _ => "expression",
+    }
+}
+
+impl Default for LoweringContext<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    #[test]
+    fn test_complex_control_flow_terminates_every_block() {
+        let source = r#"
+function Search(props) {
+    let found = null;
+    outer: for (let i = 0; i < props.rows.length; i++) {
+        const row = props.rows[i];
+        for (const key in row) {
+            switch (row[key]) {
+                case "skip":
+                    continue outer;
+                case "stop":
+                    break outer;
+                case "done":
+                    try {
+                        return found ?? key;
+                    } finally {
+                        props.log.push(key);
+                    }
+                default:
+                    found ||= key;
+            }
+        }
+        while (props.ready && !found) {
+            if (props.retry) {
+                continue;
+            }
+            break;
+        }
+    }
+    found ??= props.fallback ? "fallback" : null;
+}
+"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        let Statement::FunctionDeclaration(func) = &ret.program.body[0] else {
+            panic!("expected a function declaration");
+        };
+
+        let (hir, unsupported) = LoweringContext::default().build_checked(func).unwrap();
+        assert!(unsupported.is_empty(), "{:?}", unsupported);
+        // The function falls off its end, so some block returns implicitly
+        assert!(hir.blocks.values().any(|block| matches!(block.terminal, Terminal::Return(None))));
+    }
+
+    #[test]
+    fn test_block_left_unterminated_is_an_error() {
+        let mut ctx = LoweringContext::new();
+        let consequent = ctx.next_block_id();
+        let alternate = ctx.next_block_id();
+        let test = ctx.create_temp();
+        ctx.terminate_block(Terminal::If { test, consequent, alternate });
+        ctx.start_block(alternate);
+        ctx.terminate_block(Terminal::Return(None));
+        // A lowering that starts the consequent but forgets to terminate it
+        ctx.start_block(consequent);
+
+        let error = ctx.validate_terminals().unwrap_err();
+        assert!(error.to_string().contains(&format!("{:?} is reachable", consequent)), "{}", error);
+
+        // A jump to a block that was never started is caught too
+        let mut ctx = LoweringContext::new();
+        let target = ctx.next_block_id();
+        ctx.terminate_block(Terminal::Goto(target));
+
+        let error = ctx.validate_terminals().unwrap_err();
+        assert!(error.to_string().contains("never created"), "{}", error);
+    }
+
+    #[test]
+    fn test_internal_error_is_returned_instead_of_the_hir() {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, "function f() { return 1; }", SourceType::mjs()).parse();
+        let Statement::FunctionDeclaration(func) = &ret.program.body[0] else {
+            panic!("expected a function declaration");
+        };
+
+        let mut ctx = LoweringContext::default();
+        ctx.fail("first".to_string());
+        ctx.fail("second".to_string());
+        let error = ctx.build_checked(func).unwrap_err();
+        assert_eq!(error.to_string(), "Lowering error: first");
+    }
+}
@@ -0,0 +1,674 @@
+//! HIR Optimization Passes
+//!
+//! Transformations over the SSA form of the HIR. They run after `enter_ssa`
+//! and before liveness/scope construction, so they only need to keep the CFG
+//! and SSA invariants intact.
+
+use crate::codegen::format_number;
+use crate::hir::dominators::DominatorTree;
+use crate::hir::reactive_scopes::get_operand_identifiers;
+use crate::hir::{
+    BinaryOperator, BlockId, Constant, HIRFunction, Identifier, Instruction, InstructionValue,
+    Place, Terminal, UnaryOperator,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Constant folding.
+///
+/// Replaces numeric arithmetic and comparisons whose operands are known
+/// constants with their result, following JS number semantics: `0 / 0` is
+/// `NaN`, `1 / 0` is `Infinity`, the sign of zero is kept, and `NaN` is never
+/// equal to itself. Adding two string literals folds into one, so the literal
+/// prefix of `"a" + "b" + x` becomes `"ab"`. Constants are propagated through
+/// SSA copies, so `const x = 1; x + 2` folds as well.
+pub fn fold_constants(func: &mut HIRFunction) {
+    let mut constants: HashMap<Identifier, Constant> = HashMap::new();
+
+    // Definitions dominate their uses, but blocks are keyed by id rather than
+    // visited in dominator order, so repeat until nothing else folds
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in func.blocks.values_mut() {
+            for instr in &mut block.instructions {
+                if constants.contains_key(&instr.lvalue.identifier) {
+                    continue;
+                }
+                let folded = match &instr.value {
+                    InstructionValue::Constant(c) => Some(copy_constant(c)),
+                    InstructionValue::LoadLocal(place) => {
+                        constants.get(&place.identifier).map(copy_constant)
+                    }
+                    InstructionValue::BinaryOp { op, left, right } => {
+                        match (constants.get(&left.identifier), constants.get(&right.identifier)) {
+                            (Some(left), Some(right)) => fold_binary(op, left, right),
+                            _ => None,
+                        }
+                    }
+                    InstructionValue::UnaryOp { op, operand } => constants
+                        .get(&operand.identifier)
+                        .and_then(|operand| fold_unary(op, operand)),
+                    _ => None,
+                };
+                if let Some(constant) = folded {
+                    if !matches!(instr.value, InstructionValue::Constant(_)) {
+                        instr.value = InstructionValue::Constant(copy_constant(&constant));
+                    }
+                    constants.insert(instr.lvalue.identifier.clone(), constant);
+                    changed = true;
+                }
+            }
+        }
+    }
+}
+
+fn copy_constant(constant: &Constant) -> Constant {
+    match constant {
+        Constant::Int(n) => Constant::Int(*n),
+        Constant::Float(n) => Constant::Float(*n),
+        Constant::String(s) => Constant::String(s.clone()),
+        Constant::Boolean(b) => Constant::Boolean(*b),
+        Constant::Null => Constant::Null,
+        Constant::Undefined => Constant::Undefined,
+    }
+}
+
+fn as_number(constant: &Constant) -> Option<f64> {
+    match constant {
+        Constant::Int(n) => Some(*n as f64),
+        Constant::Float(n) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Fold a binary operation over two numbers, or the concatenation of two
+/// strings. Mixed operand types are left alone: number-to-string conversion
+/// and the bitwise operators' int32 conversions are easy to get subtly wrong.
+fn fold_binary(op: &BinaryOperator, left: &Constant, right: &Constant) -> Option<Constant> {
+    if let (BinaryOperator::Add, Constant::String(l), Constant::String(r)) = (op, left, right) {
+        return Some(Constant::String(format!("{}{}", l, r)));
+    }
+    let (l, r) = (as_number(left)?, as_number(right)?);
+    // f64 arithmetic is IEEE 754, same as JS numbers, including `%` truncating
+    // towards zero and comparisons with NaN all being false
+    let result = match op {
+        BinaryOperator::Add => Constant::Float(l + r),
+        BinaryOperator::Sub => Constant::Float(l - r),
+        BinaryOperator::Mul => Constant::Float(l * r),
+        BinaryOperator::Div => Constant::Float(l / r),
+        BinaryOperator::Mod => Constant::Float(l % r),
+        BinaryOperator::LessThan => Constant::Boolean(l < r),
+        BinaryOperator::LessThanEqual => Constant::Boolean(l <= r),
+        BinaryOperator::GreaterThan => Constant::Boolean(l > r),
+        BinaryOperator::GreaterThanEqual => Constant::Boolean(l >= r),
+        BinaryOperator::Equal | BinaryOperator::StrictEqual => Constant::Boolean(l == r),
+        BinaryOperator::NotEqual | BinaryOperator::StrictNotEqual => Constant::Boolean(l != r),
+        _ => return None,
+    };
+    printable(result)
+}
+
+fn fold_unary(op: &UnaryOperator, operand: &Constant) -> Option<Constant> {
+    let result = match (op, operand) {
+        (UnaryOperator::Not, Constant::Boolean(b)) => Constant::Boolean(!b),
+        (UnaryOperator::Negate, _) => Constant::Float(-as_number(operand)?),
+        (UnaryOperator::Plus, _) => Constant::Float(as_number(operand)?),
+        _ => return None,
+    };
+    printable(result)
+}
+
+/// A folded number replaces the original expression in the output, so only
+/// keep it if its literal reads back as the same value.
+fn printable(constant: Constant) -> Option<Constant> {
+    if let Constant::Float(n) = constant
+        && n.is_finite()
+        && format_number(n).parse::<f64>() != Ok(n)
+    {
+        return None;
+    }
+    Some(constant)
+}
+
+/// An expression whose value depends only on its operator and SSA operands.
+#[derive(PartialEq, Eq, Hash)]
+enum PureExpression {
+    Load(Identifier),
+    Binary(BinaryOperator, Identifier, Identifier),
+    Unary(UnaryOperator, Identifier),
+}
+
+/// Common subexpression elimination.
+///
+/// Replaces a pure `BinaryOp`/`UnaryOp` that repeats an earlier one over the
+/// same SSA values with a copy of the earlier result. Separate loads of the
+/// same SSA version count as the same value. Calls and property reads are
+/// never merged, since they can have side effects or see a mutated object,
+/// and neither are operators that convert an object operand, which calls its
+/// `valueOf` or `toString` each time.
+///
+/// Only reuses results from earlier in the same block: temporaries are
+/// emitted as block-scoped `const`s, so a value from a dominating block is
+/// not necessarily in scope (e.g. a loop header's values after the loop).
+pub fn eliminate_common_subexpressions(func: &mut HIRFunction) {
+    let params: HashSet<String> = func.params.iter().map(|p| p.name.clone()).collect();
+    let primitives = primitive_values(func);
+
+    for block in func.blocks.values_mut() {
+        // Each temp's representative: the first temp holding the same value
+        let mut canonical: HashMap<Identifier, Identifier> = HashMap::new();
+        let mut available: HashMap<PureExpression, Identifier> = HashMap::new();
+
+        for instr in &mut block.instructions {
+            let resolve = |place: &Place| {
+                canonical
+                    .get(&place.identifier)
+                    .unwrap_or(&place.identifier)
+                    .clone()
+            };
+            let expression = match &instr.value {
+                // Globals can be reassigned by any call, so only versioned
+                // names and parameters are stable
+                InstructionValue::LoadLocal(place)
+                    if place.identifier.id != 0 || params.contains(&place.identifier.name) =>
+                {
+                    PureExpression::Load(resolve(place))
+                }
+                InstructionValue::BinaryOp { op, left, right }
+                    if matches!(op, BinaryOperator::StrictEqual | BinaryOperator::StrictNotEqual)
+                        || (!matches!(op, BinaryOperator::In | BinaryOperator::InstanceOf)
+                            && primitives.contains(&left.identifier)
+                            && primitives.contains(&right.identifier)) =>
+                {
+                    PureExpression::Binary(*op, resolve(left), resolve(right))
+                }
+                // `typeof` reads a global directly, which calls can reassign
+                InstructionValue::UnaryOp { op: UnaryOperator::TypeOf, operand }
+                    if operand.identifier.id == 0 && !params.contains(&operand.identifier.name) =>
+                {
+                    continue;
+                }
+                // `!`, `void` and the nullish test never convert their operand
+                InstructionValue::UnaryOp { op, operand }
+                    if matches!(
+                        op,
+                        UnaryOperator::Not | UnaryOperator::TypeOf | UnaryOperator::Void | UnaryOperator::IsNullish
+                    ) || (!matches!(op, UnaryOperator::Delete) && primitives.contains(&operand.identifier)) =>
+                {
+                    PureExpression::Unary(*op, resolve(operand))
+                }
+                _ => continue,
+            };
+
+            let lvalue = instr.lvalue.identifier.clone();
+            match available.get(&expression) {
+                Some(earlier) => {
+                    // Loads are already as cheap as a copy; just note the alias
+                    if !matches!(expression, PureExpression::Load(_)) {
+                        instr.value = InstructionValue::LoadLocal(Place {
+                            identifier: earlier.clone(),
+                        });
+                    }
+                    canonical.insert(lvalue, earlier.clone());
+                }
+                None => {
+                    available.insert(expression, lvalue);
+                }
+            }
+        }
+    }
+}
+
+/// Loop-invariant code motion.
+///
+/// Moves pure instructions whose operands are all defined outside a loop into
+/// the loop's preheader, so they are computed once instead of on every
+/// iteration. Only instructions that can neither observe nor cause side
+/// effects are candidates: calls, stores, property reads and yields stay put.
+/// The preheader runs even when the loop body doesn't, so operators that could
+/// throw or call `valueOf` are only hoisted over known primitive operands.
+pub fn hoist_loop_invariants(func: &mut HIRFunction) {
+    let dom_tree = DominatorTree::compute(func);
+    let params: HashSet<String> = func.params.iter().map(|p| p.name.clone()).collect();
+    let primitives = primitive_values(func);
+
+    // Inner loops get higher block ids than the loops around them, so visiting
+    // headers in descending order lets an outer loop hoist what an inner one
+    // already moved into its preheader.
+    let mut headers: Vec<BlockId> = func.loop_headers.iter().copied().collect();
+    headers.sort_by(|a, b| b.cmp(a));
+
+    for header in headers {
+        let Some(body) = natural_loop(func, &dom_tree, header) else {
+            continue;
+        };
+        let Some(preheader) = find_preheader(func, header, &body) else {
+            continue;
+        };
+
+        // Everything defined inside the loop, including phis at the header
+        let mut loop_defs: HashSet<Identifier> = HashSet::new();
+        for block_id in &body {
+            for instr in &func.blocks[block_id].instructions {
+                loop_defs.insert(instr.lvalue.identifier.clone());
+                if let InstructionValue::StoreLocal(target, _) = &instr.value {
+                    loop_defs.insert(target.identifier.clone());
+                }
+            }
+        }
+
+        // Hoisting one instruction can make its users invariant, so repeat until stable
+        let mut hoisted: Vec<Instruction> = Vec::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block_id in &body {
+                let block = func.blocks.get_mut(block_id).unwrap();
+                let mut idx = 0;
+                while idx < block.instructions.len() {
+                    let instr = &block.instructions[idx];
+                    if is_hoistable(&instr.value, &params, &primitives)
+                        && operands(&instr.value).iter().all(|id| !loop_defs.contains(id))
+                    {
+                        let instr = block.instructions.remove(idx);
+                        loop_defs.remove(&instr.lvalue.identifier);
+                        hoisted.push(instr);
+                        changed = true;
+                    } else {
+                        idx += 1;
+                    }
+                }
+            }
+        }
+
+        func.blocks
+            .get_mut(&preheader)
+            .unwrap()
+            .instructions
+            .extend(hoisted);
+    }
+}
+
+/// Dead phi elimination.
+///
+/// SSA places a phi wherever a variable's definitions meet, whether or not the
+/// merged value is read afterwards. Each phi becomes a copy on every incoming
+/// edge, so unread ones are dropped, repeating until stable since dropping one
+/// can leave the phis feeding it unread.
+pub fn eliminate_dead_phis(func: &mut HIRFunction) {
+    loop {
+        let mut used: HashSet<Identifier> = HashSet::new();
+        for block in func.blocks.values() {
+            for instr in &block.instructions {
+                // A loop phi reading its own value doesn't keep itself alive
+                used.extend(
+                    get_operand_identifiers(&instr.value)
+                        .into_iter()
+                        .filter(|id| *id != instr.lvalue.identifier),
+                );
+            }
+            match &block.terminal {
+                Terminal::If { test, .. } => {
+                    used.insert(test.identifier.clone());
+                }
+                Terminal::Return(Some(place)) => {
+                    used.insert(place.identifier.clone());
+                }
+                Terminal::Switch { test, cases, .. } => {
+                    used.insert(test.identifier.clone());
+                    used.extend(cases.iter().map(|(label, _)| label.identifier.clone()));
+                }
+                Terminal::Goto(_) | Terminal::Return(None) => {}
+            }
+        }
+
+        let mut removed = false;
+        for block in func.blocks.values_mut() {
+            let before = block.instructions.len();
+            block.instructions.retain(|instr| {
+                !matches!(instr.value, InstructionValue::Phi { .. }) || used.contains(&instr.lvalue.identifier)
+            });
+            removed |= block.instructions.len() != before;
+        }
+        if !removed {
+            break;
+        }
+    }
+}
+
+/// Collect the blocks of the natural loop headed by `header`: the header plus
+/// every block that reaches a back edge without passing through the header.
+fn natural_loop(
+    func: &HIRFunction,
+    dom_tree: &DominatorTree,
+    header: BlockId,
+) -> Option<BTreeSet<BlockId>> {
+    let back_edges: Vec<BlockId> = func.blocks.get(&header)?
+        .preds
+        .iter()
+        .copied()
+        .filter(|&pred| dominates(dom_tree, header, pred))
+        .collect();
+    if back_edges.is_empty() {
+        return None;
+    }
+
+    let mut body = BTreeSet::from([header]);
+    let mut worklist = back_edges;
+    while let Some(block_id) = worklist.pop() {
+        if body.insert(block_id) {
+            worklist.extend(func.blocks[&block_id].preds.iter().copied());
+        }
+    }
+    Some(body)
+}
+
+/// The single block outside the loop that jumps straight to the header.
+fn find_preheader(func: &HIRFunction, header: BlockId, body: &BTreeSet<BlockId>) -> Option<BlockId> {
+    let mut outside = func.blocks[&header]
+        .preds
+        .iter()
+        .copied()
+        .filter(|pred| !body.contains(pred));
+    let preheader = outside.next()?;
+    if outside.next().is_some() {
+        return None;
+    }
+    match func.blocks[&preheader].terminal {
+        Terminal::Goto(target) if target == header => Some(preheader),
+        _ => None,
+    }
+}
+
+fn dominates(dom_tree: &DominatorTree, dominator: BlockId, mut block: BlockId) -> bool {
+    loop {
+        if block == dominator {
+            return true;
+        }
+        match dom_tree.idoms.get(&block) {
+            Some(&idom) if idom != block => block = idom,
+            _ => return false,
+        }
+    }
+}
+
+/// Whether an instruction may be evaluated earlier (or more often) without
+/// changing behavior, including before a loop whose body never runs.
+fn is_hoistable(
+    value: &InstructionValue,
+    params: &HashSet<String>,
+    primitives: &HashSet<Identifier>,
+) -> bool {
+    match value {
+        InstructionValue::Constant(_) => true,
+        // Strict equality never converts its operands
+        InstructionValue::BinaryOp {
+            op: BinaryOperator::StrictEqual | BinaryOperator::StrictNotEqual,
+            ..
+        } => true,
+        // `in` and `instanceof` throw on non-object operands
+        InstructionValue::BinaryOp { op: BinaryOperator::In | BinaryOperator::InstanceOf, .. } => false,
+        // Other operators call `valueOf`/`toString` on objects and throw on
+        // Symbols or on mixing BigInt with numbers
+        InstructionValue::BinaryOp { left, right, .. } => {
+            primitives.contains(&left.identifier) && primitives.contains(&right.identifier)
+        }
+        // Unversioned names are globals (or parameters); a call in the loop
+        // could reassign a global, so only parameters are safe to read early.
+        // `typeof` reads its operand by name like a load.
+        InstructionValue::UnaryOp { op: UnaryOperator::TypeOf, operand }
+        | InstructionValue::LoadLocal(operand) => {
+            operand.identifier.id != 0 || params.contains(&operand.identifier.name)
+        }
+        InstructionValue::UnaryOp { op: UnaryOperator::Delete, .. } => false,
+        InstructionValue::UnaryOp { operand, .. } => primitives.contains(&operand.identifier),
+        _ => false,
+    }
+}
+
+/// Values known to be primitives other than BigInt and Symbol, which every
+/// operator accepts without throwing or running user code: constants, the
+/// results of comparisons and `typeof`, and arithmetic over such values.
+fn primitive_values(func: &HIRFunction) -> HashSet<Identifier> {
+    let mut primitives: HashSet<Identifier> = HashSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in func.blocks.values() {
+            for instr in &block.instructions {
+                let primitive = match &instr.value {
+                    InstructionValue::Constant(_) => true,
+                    InstructionValue::BinaryOp { op, left, right } => {
+                        is_comparison(op)
+                            || (primitives.contains(&left.identifier)
+                                && primitives.contains(&right.identifier))
+                    }
+                    InstructionValue::UnaryOp {
+                        op: UnaryOperator::Not | UnaryOperator::TypeOf | UnaryOperator::Delete,
+                        ..
+                    } => true,
+                    InstructionValue::UnaryOp { operand, .. } => primitives.contains(&operand.identifier),
+                    InstructionValue::LoadLocal(place) => primitives.contains(&place.identifier),
+                    InstructionValue::StoreLocal(target, value) => {
+                        if primitives.contains(&value.identifier) {
+                            changed |= primitives.insert(target.identifier.clone());
+                        }
+                        primitives.contains(&value.identifier)
+                    }
+                    _ => false,
+                };
+                if primitive {
+                    changed |= primitives.insert(instr.lvalue.identifier.clone());
+                }
+            }
+        }
+    }
+    primitives
+}
+
+/// Operators that always produce a boolean, whatever their operands.
+fn is_comparison(op: &BinaryOperator) -> bool {
+    matches!(
+        op,
+        BinaryOperator::LessThan
+            | BinaryOperator::LessThanEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanEqual
+            | BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::StrictEqual
+            | BinaryOperator::StrictNotEqual
+            | BinaryOperator::InstanceOf
+            | BinaryOperator::In
+    )
+}
+
+fn operands(value: &InstructionValue) -> Vec<Identifier> {
+    match value {
+        InstructionValue::BinaryOp { left, right, .. } => {
+            vec![left.identifier.clone(), right.identifier.clone()]
+        }
+        InstructionValue::UnaryOp { operand, .. } => vec![operand.identifier.clone()],
+        InstructionValue::LoadLocal(place) => vec![place.identifier.clone()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile;
+    use oxc_span::SourceType;
+
+    fn fold(op: BinaryOperator, left: f64, right: f64) -> f64 {
+        match fold_binary(&op, &Constant::Float(left), &Constant::Float(right)) {
+            Some(Constant::Float(n)) => n,
+            other => panic!("expected a number, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fold_division_by_zero() {
+        assert!(fold(BinaryOperator::Div, 0.0, 0.0).is_nan());
+        assert_eq!(fold(BinaryOperator::Div, 1.0, 0.0), f64::INFINITY);
+        assert_eq!(fold(BinaryOperator::Div, -1.0, 0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_fold_keeps_negative_zero() {
+        let product = fold(BinaryOperator::Mul, -1.0, 0.0);
+        assert_eq!(product, 0.0);
+        assert!(product.is_sign_negative());
+        let negated = fold_unary(&UnaryOperator::Negate, &Constant::Float(0.0));
+        assert!(matches!(negated, Some(Constant::Float(n)) if n == 0.0 && n.is_sign_negative()));
+    }
+
+    #[test]
+    fn test_fold_nan_is_not_equal_to_itself() {
+        let nan = Constant::Float(f64::NAN);
+        let equal = fold_binary(&BinaryOperator::StrictEqual, &nan, &nan);
+        assert!(matches!(equal, Some(Constant::Boolean(false))));
+        let not_equal = fold_binary(&BinaryOperator::NotEqual, &nan, &nan);
+        assert!(matches!(not_equal, Some(Constant::Boolean(true))));
+    }
+
+    #[test]
+    fn test_fold_prints_results_as_js_literals() {
+        let source = r#"
+function f() {
+    const big = 1e15 * 1e15;
+    const negativeZero = -1 * 0;
+    const nan = 0 / 0;
+    const infinity = 1 / 0;
+    return [big, negativeZero, nan, infinity];
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        assert!(output.contains("1e+30"), "{}", output);
+        assert!(!output.contains("9223372036854775807"), "{}", output);
+        assert!(output.contains("-0"), "{}", output);
+        assert!(output.contains("NaN"), "{}", output);
+        assert!(output.contains("Infinity"), "{}", output);
+    }
+
+    #[test]
+    fn test_fold_leaves_strings_alone() {
+        let left = Constant::String("1".to_string());
+        assert!(fold_binary(&BinaryOperator::Add, &left, &Constant::Float(1.0)).is_none());
+    }
+
+    #[test]
+    fn test_fold_string_literal_prefix() {
+        let source = r#"
+function greet(name) {
+    return "Hello, " + "dear " + name + "!";
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        assert!(output.contains("\"Hello, dear \""), "{}", output);
+        // Only the two concatenations involving `name` remain
+        assert_eq!(output.matches(" + ").count(), 2, "{}", output);
+    }
+
+    #[test]
+    fn test_repeated_binary_op_is_reused() {
+        let source = r#"
+function twice(a, b) {
+    const less = a < b;
+    const more = a > b;
+    const x = less + more;
+    const y = less + more;
+    const same = a === b;
+    const sameAgain = a === b;
+    return [x, y, same, sameAgain];
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        assert_eq!(output.matches(" + ").count(), 1, "{}", output);
+        assert_eq!(output.matches(" === ").count(), 1, "{}", output);
+    }
+
+    #[test]
+    fn test_repeated_conversions_are_not_merged() {
+        // `a` could be an object whose `valueOf` returns something new each time
+        let source = r#"
+function twice(a, b) {
+    const x = a + b;
+    const y = a + b;
+    return [x, y];
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        assert_eq!(output.matches(" + ").count(), 2, "{}", output);
+    }
+
+    #[test]
+    fn test_repeated_calls_are_not_merged() {
+        let source = r#"
+function twice(f) {
+    const x = f();
+    const y = f();
+    return [x, y];
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        assert_eq!(output.matches("()").count(), 2, "{}", output);
+    }
+
+    #[test]
+    fn test_invariant_binary_op_is_hoisted() {
+        let source = r#"
+function countStrings(value, n) {
+    let total = 0;
+    let i = 0;
+    while (i < n) {
+        total = total + (typeof value === "string" ? 1 : 0);
+        i = i + 1;
+    }
+    return total;
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        // `typeof value === "string"` can't throw, so it moves to the preheader
+        assert!(output.find(" === ").unwrap() < output.find("while").unwrap(), "{}", output);
+    }
+
+    #[test]
+    fn test_arithmetic_on_unknown_values_stays_in_loop() {
+        let source = r#"
+function sum(a, b, n) {
+    let total = 0;
+    let i = 0;
+    while (i < n) {
+        total = total + (a + b);
+        i = i + 1;
+    }
+    return total;
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        let (before, inside) = output.split_at(output.find("while").unwrap());
+        // `a + b` throws for mixed BigInt operands, so it must not run before
+        // the loop test has been checked
+        assert_eq!(before.matches(" + ").count(), 0, "{}", output);
+        assert_eq!(inside.matches(" + ").count(), 3, "{}", output);
+    }
+
+    #[test]
+    fn test_loop_dependent_values_stay_in_loop() {
+        let source = r#"
+function count(items) {
+    let i = 0;
+    while (i < items.length) {
+        i = i + 1;
+    }
+    return i;
+}
+"#;
+        let output = compile(source, SourceType::mjs()).unwrap();
+        let loop_start = output.find("while").unwrap();
+        // Both the property read and the increment depend on the loop
+        assert!(output.find(".length").unwrap() > loop_start, "{}", output);
+        assert!(output.find(" + ").unwrap() > loop_start, "{}", output);
+    }
+}
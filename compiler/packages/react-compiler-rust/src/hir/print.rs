@@ -0,0 +1,249 @@
+//! HIR Pretty-Printer
+//!
+//! Renders an `HIRFunction` as a compact block-by-block listing, in the
+//! spirit of the upstream React Compiler's `printHIR`:
+//!
+//! ```text
+//! function conditional(a$0)
+//! bb0:
+//!   [0] t0 = LoadLocal a$0
+//!   If t0 then bb1 else bb2
+//! ```
+
+use crate::hir::{
+    Argument, ArrayElement, BasicBlock, Constant, HIRFunction, Identifier, InstructionValue,
+    ObjectProperty, ObjectPropertyKey, Place, Terminal,
+};
+use std::fmt::Write;
+
+/// Format a function's CFG as a readable textual listing.
+pub fn format_hir(func: &HIRFunction) -> String {
+    let mut output = String::new();
+
+    let params: Vec<_> = func.params.iter().map(format_identifier).collect();
+    writeln!(
+        output,
+        "{} {}({})",
+        match (func.is_async, func.generator) {
+            (true, true) => "async function*",
+            (true, false) => "async function",
+            (false, true) => "function*",
+            (false, false) => "function",
+        },
+        func.name.as_deref().unwrap_or("anonymous"),
+        params.join(", ")
+    )
+    .unwrap();
+
+    for block in func.blocks.values() {
+        format_block(&mut output, block);
+    }
+
+    output
+}
+
+fn format_block(output: &mut String, block: &BasicBlock) {
+    writeln!(output, "bb{}:", block.id.0).unwrap();
+    for instr in &block.instructions {
+        writeln!(
+            output,
+            "  [{}] {} = {}",
+            instr.id.0,
+            format_place(&instr.lvalue),
+            format_value(&instr.value)
+        )
+        .unwrap();
+    }
+    writeln!(output, "  {}", format_terminal(&block.terminal)).unwrap();
+}
+
+fn format_terminal(terminal: &Terminal) -> String {
+    match terminal {
+        Terminal::Goto(target) => format!("Goto bb{}", target.0),
+        Terminal::If { test, consequent, alternate } => format!(
+            "If {} then bb{} else bb{}",
+            format_place(test),
+            consequent.0,
+            alternate.0
+        ),
+        Terminal::Return(Some(place)) => format!("Return {}", format_place(place)),
+        Terminal::Return(None) => "Return".to_string(),
+        Terminal::Switch { test, cases, default, .. } => {
+            let cases: Vec<_> = cases
+                .iter()
+                .map(|(label, target)| format!("{}: bb{}", format_place(label), target.0))
+                .collect();
+            format!(
+                "Switch {} [{}] default bb{}",
+                format_place(test),
+                cases.join(", "),
+                default.0
+            )
+        }
+    }
+}
+
+fn format_value(value: &InstructionValue) -> String {
+    match value {
+        InstructionValue::Constant(c) => format!("Constant {}", format_constant(c)),
+        InstructionValue::BinaryOp { op, left, right } => format!(
+            "BinaryOp {} {} {}",
+            format_place(left),
+            op.as_str(),
+            format_place(right)
+        ),
+        InstructionValue::UnaryOp { op, operand } => {
+            format!("UnaryOp {:?} {}", op, format_place(operand))
+        }
+        InstructionValue::Call { callee, args, pure } => format!(
+            "Call {}{}({})",
+            format_pure(*pure),
+            format_place(callee),
+            format_arguments(args)
+        ),
+        InstructionValue::MethodCall { receiver, property, args, pure } => format!(
+            "MethodCall {}{}.{}({})",
+            format_pure(*pure),
+            format_place(receiver),
+            property,
+            format_arguments(args)
+        ),
+        InstructionValue::Object { properties } => {
+            format!("Object {{ {} }}", format_properties(properties))
+        }
+        InstructionValue::Array { elements } => {
+            let elems: Vec<_> = elements
+                .iter()
+                .map(|elem| match elem {
+                    ArrayElement::Regular(place) => format_place(place),
+                    ArrayElement::Spread(place) => format!("...{}", format_place(place)),
+                    ArrayElement::Hole => String::new(),
+                })
+                .collect();
+            format!("Array [{}]", elems.join(", "))
+        }
+        InstructionValue::PropertyLoad { object, property } => {
+            format!("PropertyLoad {}.{}", format_place(object), property)
+        }
+        InstructionValue::PropertyStore { object, property, value } => format!(
+            "PropertyStore {}.{} = {}",
+            format_place(object),
+            property,
+            format_place(value)
+        ),
+        InstructionValue::ComputedLoad { object, property } => format!(
+            "ComputedLoad {}[{}]",
+            format_place(object),
+            format_place(property)
+        ),
+        InstructionValue::ComputedStore { object, property, value } => format!(
+            "ComputedStore {}[{}] = {}",
+            format_place(object),
+            format_place(property),
+            format_place(value)
+        ),
+        InstructionValue::PropertyDelete { object, property } => {
+            format!("PropertyDelete {}.{}", format_place(object), property)
+        }
+        InstructionValue::ComputedDelete { object, property } => format!(
+            "ComputedDelete {}[{}]",
+            format_place(object),
+            format_place(property)
+        ),
+        InstructionValue::ForInKeys { object } => format!("ForInKeys {}", format_place(object)),
+        InstructionValue::JsxElement { tag, props, children, key, ref_ } => {
+            let mut attributes = String::new();
+            if let Some(key) = key {
+                attributes.push_str(&format!("key={} ", format_place(key)));
+            }
+            if let Some(ref_) = ref_ {
+                attributes.push_str(&format!("ref={} ", format_place(ref_)));
+            }
+            format!(
+                "JsxElement <{} {}{}>{}</>",
+                format_place(tag),
+                attributes,
+                format_properties(props),
+                format_arguments(children)
+            )
+        }
+        InstructionValue::JsxFragment { children } => {
+            format!("JsxFragment <>{}</>", format_arguments(children))
+        }
+        InstructionValue::LoadLocal(place) => format!("LoadLocal {}", format_place(place)),
+        InstructionValue::StoreLocal(target, value) => {
+            format!("StoreLocal {} = {}", format_place(target), format_place(value))
+        }
+        InstructionValue::Yield { argument, delegate } => {
+            let keyword = if *delegate { "Yield*" } else { "Yield" };
+            match argument {
+                Some(place) => format!("{} {}", keyword, format_place(place)),
+                None => keyword.to_string(),
+            }
+        }
+        InstructionValue::Await { argument } => format!("Await {}", format_place(argument)),
+        InstructionValue::Phi { operands } => {
+            let operands: Vec<_> = operands
+                .iter()
+                .map(|(block, place)| format!("bb{}: {}", block.0, format_place(place)))
+                .collect();
+            format!("Phi({})", operands.join(", "))
+        }
+    }
+}
+
+fn format_constant(constant: &Constant) -> String {
+    match constant {
+        Constant::Int(n) => n.to_string(),
+        Constant::Float(n) => n.to_string(),
+        Constant::String(s) => format!("{:?}", s),
+        Constant::Boolean(b) => b.to_string(),
+        Constant::Null => "null".to_string(),
+        Constant::Undefined => "undefined".to_string(),
+    }
+}
+
+fn format_properties(properties: &[ObjectProperty]) -> String {
+    let props: Vec<_> = properties
+        .iter()
+        .map(|prop| match prop {
+            ObjectProperty::KeyValue { key, value } => {
+                let key = match key {
+                    ObjectPropertyKey::Identifier(name) => name.clone(),
+                    ObjectPropertyKey::Computed(place) => format!("[{}]", format_place(place)),
+                };
+                format!("{}: {}", key, format_place(value))
+            }
+            ObjectProperty::Spread(place) => format!("...{}", format_place(place)),
+        })
+        .collect();
+    props.join(", ")
+}
+
+fn format_pure(pure: bool) -> &'static str {
+    if pure { "@__PURE__ " } else { "" }
+}
+
+fn format_arguments(args: &[Argument]) -> String {
+    let args: Vec<_> = args
+        .iter()
+        .map(|arg| match arg {
+            Argument::Regular(place) => format_place(place),
+            Argument::Spread(place) => format!("...{}", format_place(place)),
+        })
+        .collect();
+    args.join(", ")
+}
+
+fn format_place(place: &Place) -> String {
+    format_identifier(&place.identifier)
+}
+
+/// Temporaries print as `tN`; named bindings print with their SSA version (`x$1`).
+fn format_identifier(id: &Identifier) -> String {
+    if id.temporary {
+        id.name.clone()
+    } else {
+        format!("{}${}", id.name, id.id)
+    }
+}
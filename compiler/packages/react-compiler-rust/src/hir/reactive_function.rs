@@ -3,24 +3,29 @@
 //! This module converts the graph-based HIR (CFG) back into a tree structure
 //! suitable for JavaScript code generation.
 
+use crate::hir::dominators::PostDominatorTree;
 use crate::hir::scope::ScopeId;
 use crate::hir::{
-    BlockId, HIRFunction, Identifier, Instruction, InstructionValue, Terminal,
+    BasicBlock, BlockId, HIRFunction, Identifier, Instruction, InstructionValue, Terminal,
 };
-use crate::hir::reactive_scopes::ReactiveScopeResult;
+use crate::error::CompilerError;
+use crate::hir::reactive_scopes::{get_operand_identifiers, ReactiveScopeResult};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// A tree-structured representation of a function for code generation.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReactiveFunction {
     pub name: Option<String>,
     pub params: Vec<Identifier>,
+    pub generator: bool,
+    pub is_async: bool,
+    pub directives: Vec<String>,
     pub body: Vec<ReactiveStatement>,
 }
 
 /// A statement in the reactive function tree.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReactiveStatement {
     /// A single instruction (expression statement or declaration)
     Instruction(ReactiveInstruction),
@@ -44,13 +49,17 @@ pub enum ReactiveStatement {
     While {
         test: Identifier,
         body: Vec<ReactiveStatement>,
+        /// Set when a `break` or `continue` nested in another loop or switch
+        /// targets this loop
+        label: Option<String>,
     },
 
-    /// A break statement
-    Break,
+    /// A break statement, labeled when it exits more than the innermost loop
+    /// or switch
+    Break(Option<String>),
 
-    /// A continue statement
-    Continue,
+    /// A continue statement, labeled when it skips to an outer loop
+    Continue(Option<String>),
 
     /// A return statement
     Return(Option<Identifier>),
@@ -59,12 +68,16 @@ pub enum ReactiveStatement {
     Switch {
         test: Identifier,
         cases: Vec<ReactiveSwitchCase>,
+        /// Set when a `break` nested in a loop or another switch exits it
+        label: Option<String>,
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReactiveSwitchCase {
-    pub label: Option<Identifier>, // None for default
+    /// The case test: its constant, or the identifier holding it. `None`
+    /// for `default`.
+    pub label: Option<ReactiveValue>,
     pub body: Vec<ReactiveStatement>,
 }
 
@@ -82,14 +95,28 @@ pub enum ReactiveValue {
     Constant(ConstantValue),
     BinaryOp { op: String, left: Identifier, right: Identifier },
     UnaryOp { op: String, operand: Identifier },
-    Call { callee: Identifier, args: Vec<ReactiveArgument> },
+    Call { callee: Identifier, args: Vec<ReactiveArgument>, pure: bool },
+    MethodCall { receiver: Identifier, property: String, args: Vec<ReactiveArgument>, pure: bool },
     Object { properties: Vec<ReactiveObjectProperty> },
     Array { elements: Vec<ReactiveArrayElement> },
     PropertyLoad { object: Identifier, property: String },
     PropertyStore { object: Identifier, property: String, value: Identifier },
     ComputedLoad { object: Identifier, property: Identifier },
     ComputedStore { object: Identifier, property: Identifier, value: Identifier },
+    PropertyDelete { object: Identifier, property: String },
+    ComputedDelete { object: Identifier, property: Identifier },
+    ForInKeys { object: Identifier },
+    JsxElement {
+        tag: Identifier,
+        props: Vec<ReactiveObjectProperty>,
+        children: Vec<ReactiveArgument>,
+        key: Option<Identifier>,
+        ref_: Option<Identifier>,
+    },
+    JsxFragment { children: Vec<ReactiveArgument> },
     LoadLocal(Identifier),
+    Yield { argument: Option<Identifier>, delegate: bool },
+    Await { argument: Identifier },
     Phi { operands: Vec<Identifier> },
 }
 
@@ -103,6 +130,9 @@ pub enum ReactiveArgument {
 pub enum ReactiveObjectProperty {
     KeyValue { key: ReactiveObjectKey, value: Identifier },
     Spread(Identifier),
+    /// Shorthand method: `key(params) { body }`. The function is emitted on
+    /// its own, without a memo cache.
+    Method { key: ReactiveObjectKey, function: Box<ReactiveFunction> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,11 +157,29 @@ pub enum ConstantValue {
     Undefined,
 }
 
+/// Deepest nesting of branches and loop bodies before giving up. Frames are
+/// large in debug builds, so this stays well short of overflowing a 2 MiB
+/// thread stack. The code following a statement is built in a loop, so long
+/// straight-line functions don't count towards it.
+const MAX_BUILD_DEPTH: usize = 200;
+
+/// Most blocks converted in total. Blocks reached along several paths are
+/// rebuilt on each, so tangled or irreducible CFGs can blow up exponentially.
+const MAX_BLOCKS_BUILT: usize = 100_000;
+
+/// Label for the loop or switch built from `block_id`.
+fn block_label(block_id: BlockId) -> String {
+    format!("bb{}", block_id.0)
+}
+
 /// Convert HIR (CFG) to ReactiveFunction (tree)
+///
+/// Fails with [`CompilerError::ControlFlowTooComplex`] if the CFG is too deep
+/// or tangled to rebuild as a tree.
 pub fn build_reactive_function(
     hir: &HIRFunction,
     scope_result: &ReactiveScopeResult,
-) -> ReactiveFunction {
+) -> Result<ReactiveFunction, CompilerError> {
     let mut builder = TreeBuilder::new(hir, scope_result);
     builder.build()
 }
@@ -141,58 +189,173 @@ struct TreeBuilder<'a> {
     visited_blocks: HashSet<BlockId>,
     current_loops: HashSet<BlockId>,
     loop_stack: Vec<TreeLoopInfo>,
+    /// Gives the join point of each `If`, and of a `Switch` with no merge target
+    post_dominators: PostDominatorTree,
+    /// Join points of the `If`s being built. Branches stop when they reach
+    /// one, so the code after a conditional is only built once.
+    merge_stack: Vec<BlockId>,
+    depth: usize,
+    blocks_built: usize,
+    /// Set once a limit is hit; every later `build_block` returns immediately
+    gave_up: bool,
+    /// Case test temps that are only read by their label. Their value is
+    /// written into the label instead, so it is evaluated when that case is
+    /// compared, as in the source.
+    inlined_case_tests: HashSet<Identifier>,
+    /// Loop headers and switch blocks targeted by a `break` or `continue`
+    /// from inside a nested loop or switch, which need a label
+    labeled: HashSet<BlockId>,
+}
+
+/// Case test temps defined in their switch's block and read nowhere else.
+///
+/// Only the last operation of a test moves into its label: in
+/// `case a() + b():` the calls still run before the switch, and only the
+/// addition is deferred.
+fn inlined_case_tests(hir: &HIRFunction) -> HashSet<Identifier> {
+    let mut reads: HashMap<Identifier, usize> = HashMap::new();
+    let mut read = |id: &Identifier| *reads.entry(id.clone()).or_default() += 1;
+    for block in hir.blocks.values() {
+        for instr in &block.instructions {
+            get_operand_identifiers(&instr.value).iter().for_each(&mut read);
+        }
+        match &block.terminal {
+            Terminal::If { test, .. } | Terminal::Return(Some(test)) => read(&test.identifier),
+            Terminal::Switch { test, cases, .. } => {
+                read(&test.identifier);
+                cases.iter().for_each(|(label, _)| read(&label.identifier));
+            }
+            Terminal::Goto(_) | Terminal::Return(None) => {}
+        }
+    }
+
+    let mut inlined = HashSet::new();
+    for block in hir.blocks.values() {
+        let Terminal::Switch { cases, .. } = &block.terminal else {
+            continue;
+        };
+        for (label, _) in cases {
+            let defined_here = block.instructions.iter().any(|instr| {
+                instr.lvalue.identifier == label.identifier
+                    && !matches!(instr.value, InstructionValue::Phi { .. })
+            });
+            if defined_here && reads.get(&label.identifier) == Some(&1) {
+                inlined.insert(label.identifier.clone());
+            }
+        }
+    }
+    inlined
 }
 
 #[derive(Clone, Copy)]
 struct TreeLoopInfo {
     header: BlockId,
     break_target: BlockId,
+    /// Switches take a `break` but not a `continue`
+    is_loop: bool,
 }
 
 impl<'a> TreeBuilder<'a> {
-    fn new(hir: &'a HIRFunction, scope_result: &'a ReactiveScopeResult) -> Self {
+    fn new(hir: &'a HIRFunction, _scope_result: &'a ReactiveScopeResult) -> Self {
         Self {
             hir,
             visited_blocks: HashSet::new(),
             current_loops: HashSet::new(),
             loop_stack: Vec::new(),
+            post_dominators: PostDominatorTree::compute(hir),
+            merge_stack: Vec::new(),
+            depth: 0,
+            blocks_built: 0,
+            gave_up: false,
+            inlined_case_tests: inlined_case_tests(hir),
+            labeled: HashSet::new(),
         }
     }
 
-    pub fn build(&mut self) -> ReactiveFunction {
+    pub fn build(&mut self) -> Result<ReactiveFunction, CompilerError> {
         let body = self.build_block(self.hir.entry_block, None);
-        ReactiveFunction {
+        if self.gave_up {
+            return Err(CompilerError::ControlFlowTooComplex {
+                name: self.hir.name.clone().unwrap_or_else(|| "anonymous".to_string()),
+            });
+        }
+        Ok(ReactiveFunction {
             name: self.hir.name.clone(),
             params: self.hir.params.clone(),
+            generator: self.hir.generator,
+            is_async: self.hir.is_async,
+            directives: self.hir.directives.clone(),
             body,
-        }
+        })
     }
 
+    /// Build `block_id` and every block that follows it at the same nesting
+    /// level, such as the code after a conditional.
     fn build_block(&mut self, block_id: BlockId, prev_id: Option<BlockId>) -> Vec<ReactiveStatement> {
+        if self.gave_up || self.depth >= MAX_BUILD_DEPTH {
+            self.gave_up = true;
+            return Vec::new();
+        }
+        self.depth += 1;
+
+        let mut statements = Vec::new();
+        // Blocks that handed off to a continuation stay visited until the
+        // whole sequence is built, as if each had built the next recursively
+        let mut sequence = Vec::new();
+        let mut next = Some((block_id, prev_id));
+        while let Some((block_id, prev_id)) = next {
+            self.blocks_built += 1;
+            if self.gave_up || self.blocks_built > MAX_BLOCKS_BUILT {
+                self.gave_up = true;
+                break;
+            }
+            let (block_statements, continuation) = self.build_block_inner(block_id, prev_id);
+            statements.extend(block_statements);
+            if continuation.is_some() {
+                sequence.push(block_id);
+            }
+            next = continuation;
+        }
+        for block_id in sequence {
+            self.visited_blocks.remove(&block_id);
+        }
+
+        self.depth -= 1;
+        statements
+    }
+
+    /// Build one block, returning its statements and the block to continue
+    /// with (and the predecessor to assign its phis from), if any. A block
+    /// with a continuation is left in `visited_blocks` for the caller to clear.
+    fn build_block_inner(
+        &mut self,
+        block_id: BlockId,
+        prev_id: Option<BlockId>,
+    ) -> (Vec<ReactiveStatement>, Option<(BlockId, Option<BlockId>)>) {
         let mut statements = Vec::new();
 
         // 1. Handle Phis from predecessors
         if let Some(block) = self.hir.blocks.get(&block_id) {
             for instr in &block.instructions {
-                if let InstructionValue::Phi { operands } = &instr.value {
-                    if let Some(p_id) = prev_id {
-                        for (pred_id, place) in operands {
-                            if *pred_id == p_id {
-                                statements.push(ReactiveStatement::Instruction(ReactiveInstruction {
-                                    lvalue: instr.lvalue.identifier.clone(),
-                                    value: ReactiveValue::LoadLocal(place.identifier.clone()),
-                                    scope: None,
-                                }));
-                            }
+                if let InstructionValue::Phi { operands } = &instr.value
+                    && let Some(p_id) = prev_id
+                {
+                    for (pred_id, place) in operands {
+                        if *pred_id == p_id {
+                            statements.push(ReactiveStatement::Instruction(ReactiveInstruction {
+                                lvalue: instr.lvalue.identifier.clone(),
+                                value: ReactiveValue::LoadLocal(place.identifier.clone()),
+                                scope: None,
+                            }));
                         }
                     }
                 }
             }
         }
 
-        // 2. Handle recursion and loops
-        if self.visited_blocks.contains(&block_id) {
-            return statements;
+        // 2. Handle recursion, loops and the join point of an enclosing `If`
+        if self.visited_blocks.contains(&block_id) || self.merge_stack.contains(&block_id) {
+            return (statements, None);
         }
         self.visited_blocks.insert(block_id);
 
@@ -221,34 +384,38 @@ impl<'a> TreeBuilder<'a> {
                         let test_id = test.identifier.clone();
                         
                         // if (!test) break;
+                        // The exit block's phis are assigned right before this break, like
+                        // any other break, so a `break` in the body doesn't get clobbered
+                        let mut exit_stmts = self.emit_phi_assignments(*alternate, block_id);
+                        exit_stmts.push(ReactiveStatement::Break(None));
                         loop_body.push(ReactiveStatement::If {
                             test: test_id,
                             consequent: vec![],
-                            alternate: vec![ReactiveStatement::Break],
+                            alternate: exit_stmts,
                         });
                         
                         // Body path
                         self.loop_stack.push(TreeLoopInfo {
                             header: block_id,
                             break_target: *alternate,
+                            is_loop: true,
                         });
-                        // println!("Loop start {:?}, break_target {:?}", block_id, *alternate);
                         loop_body.extend(self.build_block(*consequent, Some(block_id)));
                         self.loop_stack.pop();
-                        // println!("Loop end {:?}", block_id);
                         
                         // Now we have the While statement
                         // We'll use "true" as a hacky literal identifier
-                        let true_id = Identifier { name: "true".to_string(), id: 0 };
+                        let true_id = Identifier { name: "true".to_string(), id: 0, temporary: false };
                         statements.push(ReactiveStatement::While {
                             test: true_id,
                             body: loop_body,
+                            label: self.label(block_id),
                         });
                         
                         self.current_loops.remove(&block_id);
                         
-                        // Exit path (after the loop)
-                        statements.extend(self.build_block(*alternate, Some(block_id)));
+                        // Exit path (after the loop); phis were already assigned at each break
+                        return (statements, Some((*alternate, None)));
                     }
                     _ => {
                         // Unstructured loop? Fallback to normal
@@ -257,12 +424,14 @@ impl<'a> TreeBuilder<'a> {
                 }
                 
                 self.visited_blocks.remove(&block_id);
-                return statements;
+                return (statements, None);
             }
 
             // Normal non-loop block
             for instr in &block.instructions {
-                if matches!(instr.value, InstructionValue::Phi { .. }) {
+                if matches!(instr.value, InstructionValue::Phi { .. })
+                    || self.inlined_case_tests.contains(&instr.lvalue.identifier)
+                {
                     continue;
                 }
                 let reactive_instr = self.convert_instruction(instr);
@@ -279,26 +448,44 @@ impl<'a> TreeBuilder<'a> {
                 Terminal::Goto(target) => {
                     // Check for break/continue across the entire loop stack
                     // (e.g. `continue` inside a switch inside a for loop)
-                    for loop_info in self.loop_stack.iter().rev() {
+                    let jump = self.loop_stack.iter().enumerate().rev().find_map(|(index, loop_info)| {
                         if *target == loop_info.break_target {
-                            statements.extend(self.emit_phi_assignments(*target, block_id));
-                            statements.push(ReactiveStatement::Break);
-                            self.visited_blocks.remove(&block_id);
-                            return statements;
-                        }
-                        if *target == loop_info.header {
-                            statements.extend(self.emit_phi_assignments(*target, block_id));
-                            statements.push(ReactiveStatement::Continue);
-                            self.visited_blocks.remove(&block_id);
-                            return statements;
+                            Some((index, true))
+                        } else if *target == loop_info.header {
+                            Some((index, false))
+                        } else {
+                            None
                         }
+                    });
+                    if let Some((index, is_break)) = jump {
+                        let enclosing = &self.loop_stack[index];
+                        let inner = &self.loop_stack[index + 1..];
+                        // A plain jump would stop at the innermost loop or switch in between
+                        let needs_label = if is_break { !inner.is_empty() } else { inner.iter().any(|info| info.is_loop) };
+                        let label = if needs_label {
+                            self.labeled.insert(enclosing.header);
+                            Some(block_label(enclosing.header))
+                        } else {
+                            None
+                        };
+                        statements.extend(self.emit_phi_assignments(*target, block_id));
+                        statements.push(if is_break {
+                            ReactiveStatement::Break(label)
+                        } else {
+                            ReactiveStatement::Continue(label)
+                        });
+                        self.visited_blocks.remove(&block_id);
+                        return (statements, None);
                     }
 
-                    let next_stmts = self.build_block(*target, Some(block_id));
-                    statements.extend(next_stmts);
+                    return (statements, Some((*target, Some(block_id))));
                 }
                 Terminal::If { test, consequent, alternate } => {
                     let test_id = test.identifier.clone();
+                    let merge = self.merge_block(block_id);
+                    if let Some(merge) = merge {
+                        self.merge_stack.push(merge);
+                    }
                     let then_stmts = self.build_block(*consequent, Some(block_id));
                     let else_stmts = self.build_block(*alternate, Some(block_id));
                     
@@ -307,12 +494,23 @@ impl<'a> TreeBuilder<'a> {
                         consequent: then_stmts,
                         alternate: else_stmts,
                     });
+
+                    // Both branches stopped at the join point after assigning its
+                    // phis, so the code after the conditional follows it once
+                    // instead of being copied into every branch
+                    if let Some(merge) = merge {
+                        self.merge_stack.pop();
+                        return (statements, Some((merge, None)));
+                    }
                 }
                 Terminal::Switch { test, cases, default, merge_target } => {
                     let test_id = test.identifier.clone();
+                    // Lowering records where `break` goes; otherwise the cases
+                    // join wherever they post-dominate
+                    let merge_target = merge_target.or_else(|| self.merge_block(block_id));
                     
                     if let Some(target) = merge_target {
-                        self.loop_stack.push(TreeLoopInfo { header: block_id, break_target: *target });
+                        self.loop_stack.push(TreeLoopInfo { header: block_id, break_target: target, is_loop: false });
                     }
                     
                     let mut reactive_cases = Vec::with_capacity(cases.len() + 1);
@@ -321,7 +519,7 @@ impl<'a> TreeBuilder<'a> {
                     for (val, target) in cases {
                          let case_stmts = self.build_block(*target, Some(block_id));
                          reactive_cases.push(ReactiveSwitchCase {
-                             label: Some(val.identifier.clone()),
+                             label: Some(self.case_label(block, &val.identifier)),
                              body: case_stmts,
                          });
                     }
@@ -340,6 +538,7 @@ impl<'a> TreeBuilder<'a> {
                     statements.push(ReactiveStatement::Switch {
                         test: test_id,
                         cases: reactive_cases,
+                        label: self.label(block_id),
                     });
 
                     // Continue building from the merge block (code after the switch)
@@ -348,15 +547,36 @@ impl<'a> TreeBuilder<'a> {
                     // Pass None as prev_id: phi assignments for the merge block are
                     // already handled within each case's break path.
                     if let Some(target) = merge_target {
-                        let merge_stmts = self.build_block(*target, None);
-                        statements.extend(merge_stmts);
+                        return (statements, Some((target, None)));
                     }
                 }
             }
         }
 
         self.visited_blocks.remove(&block_id);
-        statements
+        (statements, None)
+    }
+
+    /// The label of the loop or switch built from `block_id`, if a jump
+    /// from a nested one targets it.
+    fn label(&self, block_id: BlockId) -> Option<String> {
+        self.labeled.contains(&block_id).then(|| block_label(block_id))
+    }
+
+    /// Where the branches leaving `block_id` meet again, if they can be built
+    /// as a structured statement followed by the join point. Joins that are
+    /// a `break` or `continue` target of an enclosing loop or switch are left
+    /// to those paths.
+    fn merge_block(&self, block_id: BlockId) -> Option<BlockId> {
+        let merge = self.post_dominators.immediate_post_dominator(block_id)?;
+        let is_jump_target = self
+            .loop_stack
+            .iter()
+            .any(|info| info.header == merge || info.break_target == merge);
+        if is_jump_target || self.current_loops.contains(&merge) || self.merge_stack.contains(&merge) {
+            return None;
+        }
+        Some(merge)
     }
 
     fn is_loop_header(&self, block_id: BlockId) -> bool {
@@ -384,6 +604,20 @@ impl<'a> TreeBuilder<'a> {
     }
 
 
+    /// Case tests are lowered into temps in the switch's block. A constant
+    /// one, or one read only by its label, is written into the label itself,
+    /// as in the source; anything else is referred to by name.
+    fn case_label(&self, block: &BasicBlock, test: &Identifier) -> ReactiveValue {
+        let inlined = self.inlined_case_tests.contains(test);
+        block
+            .instructions
+            .iter()
+            .find(|instr| instr.lvalue.identifier == *test)
+            .map(|instr| self.convert_instruction(instr).value)
+            .filter(|value| inlined || matches!(value, ReactiveValue::Constant(_)))
+            .unwrap_or_else(|| ReactiveValue::LoadLocal(test.clone()))
+    }
+
     fn convert_instruction(&self, instr: &Instruction) -> ReactiveInstruction {
         let value = match &instr.value {
             InstructionValue::Constant(c) => {
@@ -399,34 +633,8 @@ impl<'a> TreeBuilder<'a> {
                 ReactiveValue::Constant(cv)
             }
             InstructionValue::BinaryOp { op, left, right } => {
-                use crate::hir::BinaryOperator;
-                let op_str = match op {
-                    BinaryOperator::Add => "+",
-                    BinaryOperator::Sub => "-",
-                    BinaryOperator::Mul => "*",
-                    BinaryOperator::Div => "/",
-                    BinaryOperator::Mod => "%",
-                    BinaryOperator::LessThan => "<",
-                    BinaryOperator::LessThanEqual => "<=",
-                    BinaryOperator::GreaterThan => ">",
-                    BinaryOperator::GreaterThanEqual => ">=",
-                    BinaryOperator::Equal => "==",
-                    BinaryOperator::NotEqual => "!=",
-                    BinaryOperator::StrictEqual => "===",
-                    BinaryOperator::StrictNotEqual => "!==",
-                    BinaryOperator::And => "&&",
-                    BinaryOperator::Or => "||",
-                    BinaryOperator::BitwiseAnd => "&",
-                    BinaryOperator::BitwiseOr => "|",
-                    BinaryOperator::BitwiseXor => "^",
-                    BinaryOperator::LeftShift => "<<",
-                    BinaryOperator::RightShift => ">>",
-                    BinaryOperator::UnsignedRightShift => ">>>",
-                    BinaryOperator::InstanceOf => "instanceof",
-                    BinaryOperator::In => "in",
-                };
                 ReactiveValue::BinaryOp {
-                    op: op_str.to_string(),
+                    op: op.as_str().to_string(),
                     left: left.identifier.clone(),
                     right: right.identifier.clone(),
                 }
@@ -448,7 +656,7 @@ impl<'a> TreeBuilder<'a> {
                     operand: operand.identifier.clone(),
                 }
             }
-            InstructionValue::Call { callee, args } => {
+            InstructionValue::Call { callee, args, pure } => {
                 ReactiveValue::Call {
                     callee: callee.identifier.clone(),
                     args: args.iter().map(|a| {
@@ -457,25 +665,39 @@ impl<'a> TreeBuilder<'a> {
                             crate::hir::Argument::Spread(p) => ReactiveArgument::Spread(p.identifier.clone()),
                         }
                     }).collect(),
+                    pure: *pure,
+                }
+            }
+            InstructionValue::MethodCall { receiver, property, args, pure } => {
+                ReactiveValue::MethodCall {
+                    receiver: receiver.identifier.clone(),
+                    property: property.clone(),
+                    args: args.iter().map(|a| {
+                        match a {
+                            crate::hir::Argument::Regular(p) => ReactiveArgument::Regular(p.identifier.clone()),
+                            crate::hir::Argument::Spread(p) => ReactiveArgument::Spread(p.identifier.clone()),
+                        }
+                    }).collect(),
+                    pure: *pure,
                 }
             }
             InstructionValue::Object { properties } => {
                 ReactiveValue::Object {
-                    properties: properties
-                        .iter()
-                        .map(|prop| {
-                            match prop {
-                                crate::hir::ObjectProperty::KeyValue { key, value } => {
-                                    let reactive_key = match key {
-                                        crate::hir::ObjectPropertyKey::Identifier(s) => ReactiveObjectKey::Identifier(s.clone()),
-                                        crate::hir::ObjectPropertyKey::Computed(p) => ReactiveObjectKey::Computed(p.identifier.clone()),
-                                    };
-                                    ReactiveObjectProperty::KeyValue { key: reactive_key, value: value.identifier.clone() }
-                                }
-                                crate::hir::ObjectProperty::Spread(p) => ReactiveObjectProperty::Spread(p.identifier.clone()),
-                            }
-                        })
-                        .collect(),
+                    properties: convert_properties(properties),
+                }
+            }
+            InstructionValue::JsxElement { tag, props, children, key, ref_ } => {
+                ReactiveValue::JsxElement {
+                    tag: tag.identifier.clone(),
+                    props: convert_properties(props),
+                    children: convert_arguments(children),
+                    key: key.as_ref().map(|p| p.identifier.clone()),
+                    ref_: ref_.as_ref().map(|p| p.identifier.clone()),
+                }
+            }
+            InstructionValue::JsxFragment { children } => {
+                ReactiveValue::JsxFragment {
+                    children: convert_arguments(children),
                 }
             }
             InstructionValue::Array { elements } => {
@@ -515,6 +737,21 @@ impl<'a> TreeBuilder<'a> {
                     value: value.identifier.clone(),
                 }
             }
+            InstructionValue::PropertyDelete { object, property } => {
+                ReactiveValue::PropertyDelete {
+                    object: object.identifier.clone(),
+                    property: property.clone(),
+                }
+            }
+            InstructionValue::ComputedDelete { object, property } => {
+                ReactiveValue::ComputedDelete {
+                    object: object.identifier.clone(),
+                    property: property.identifier.clone(),
+                }
+            }
+            InstructionValue::ForInKeys { object } => {
+                ReactiveValue::ForInKeys { object: object.identifier.clone() }
+            }
             InstructionValue::LoadLocal(place) => {
                 ReactiveValue::LoadLocal(place.identifier.clone())
             }
@@ -522,6 +759,15 @@ impl<'a> TreeBuilder<'a> {
                 // StoreLocal becomes a LoadLocal (copy) after SSA
                 ReactiveValue::LoadLocal(value.identifier.clone())
             }
+            InstructionValue::Yield { argument, delegate } => {
+                ReactiveValue::Yield {
+                    argument: argument.as_ref().map(|p| p.identifier.clone()),
+                    delegate: *delegate,
+                }
+            }
+            InstructionValue::Await { argument } => ReactiveValue::Await {
+                argument: argument.identifier.clone(),
+            },
             InstructionValue::Phi { operands } => {
                 ReactiveValue::Phi {
                     operands: operands.iter().map(|(_, p)| p.identifier.clone()).collect(),
@@ -537,6 +783,33 @@ impl<'a> TreeBuilder<'a> {
     }
 }
 
+fn convert_properties(properties: &[crate::hir::ObjectProperty]) -> Vec<ReactiveObjectProperty> {
+    properties
+        .iter()
+        .map(|prop| {
+            match prop {
+                crate::hir::ObjectProperty::KeyValue { key, value } => {
+                    let reactive_key = match key {
+                        crate::hir::ObjectPropertyKey::Identifier(s) => ReactiveObjectKey::Identifier(s.clone()),
+                        crate::hir::ObjectPropertyKey::Computed(p) => ReactiveObjectKey::Computed(p.identifier.clone()),
+                    };
+                    ReactiveObjectProperty::KeyValue { key: reactive_key, value: value.identifier.clone() }
+                }
+                crate::hir::ObjectProperty::Spread(p) => ReactiveObjectProperty::Spread(p.identifier.clone()),
+            }
+        })
+        .collect()
+}
+
+fn convert_arguments(args: &[crate::hir::Argument]) -> Vec<ReactiveArgument> {
+    args.iter().map(|a| {
+        match a {
+            crate::hir::Argument::Regular(p) => ReactiveArgument::Regular(p.identifier.clone()),
+            crate::hir::Argument::Spread(p) => ReactiveArgument::Spread(p.identifier.clone()),
+        }
+    }).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -547,4 +820,145 @@ mod tests {
         let json = serde_json::to_string(&val).unwrap();
         assert!(json.contains("42"));
     }
+
+    fn function_with_blocks(terminals: Vec<Terminal>) -> HIRFunction {
+        use crate::hir::BasicBlock;
+
+        let blocks = terminals
+            .into_iter()
+            .enumerate()
+            .map(|(i, terminal)| {
+                let block = BasicBlock {
+                    id: BlockId(i),
+                    instructions: vec![],
+                    terminal,
+                    preds: vec![],
+                };
+                (BlockId(i), block)
+            })
+            .collect();
+        HIRFunction {
+            name: Some("tangled".to_string()),
+            params: vec![],
+            generator: false,
+            is_async: false,
+            directives: vec![],
+            entry_block: BlockId(0),
+            blocks,
+            loop_headers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_irreducible_cfg_errors_instead_of_hanging() {
+        // Every block branches to the next two, wrapping around, and nothing
+        // is marked as a loop header. Each simple path through the cycle is
+        // rebuilt separately, so the tree would be enormous.
+        let count = 24;
+        let test = crate::hir::Place {
+            identifier: Identifier { name: "c".to_string(), id: 0, temporary: false },
+        };
+        let mut terminals: Vec<_> = (0..count)
+            .map(|i| Terminal::If {
+                test: test.clone(),
+                consequent: BlockId((i + 1) % count),
+                alternate: BlockId((i + 2) % count),
+            })
+            .collect();
+        terminals.push(Terminal::Return(None));
+        let hir = function_with_blocks(terminals);
+
+        let result = build_reactive_function(&hir, &ReactiveScopeResult::default());
+        assert!(
+            matches!(result, Err(CompilerError::ControlFlowTooComplex { ref name }) if name == "tangled"),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_merge_block_is_built_once_after_the_if() {
+        let test = crate::hir::Place {
+            identifier: Identifier { name: "c".to_string(), id: 0, temporary: false },
+        };
+        let hir = function_with_blocks(vec![
+            Terminal::If { test, consequent: BlockId(1), alternate: BlockId(2) },
+            Terminal::Goto(BlockId(3)),
+            Terminal::Goto(BlockId(3)),
+            Terminal::Return(None),
+        ]);
+
+        let func = build_reactive_function(&hir, &ReactiveScopeResult::default()).unwrap();
+        assert!(
+            matches!(
+                func.body.as_slice(),
+                [ReactiveStatement::If { consequent, alternate, .. }, ReactiveStatement::Return(None)]
+                    if consequent.is_empty() && alternate.is_empty()
+            ),
+            "{:?}",
+            func.body
+        );
+    }
+
+    #[test]
+    fn test_switch_without_merge_target_joins_at_post_dominator() {
+        let place = |name: &str| crate::hir::Place {
+            identifier: Identifier { name: name.to_string(), id: 0, temporary: false },
+        };
+        let hir = function_with_blocks(vec![
+            Terminal::Switch {
+                test: place("x"),
+                cases: vec![(place("a"), BlockId(1)), (place("b"), BlockId(2))],
+                default: BlockId(3),
+                merge_target: None,
+            },
+            Terminal::Goto(BlockId(4)),
+            Terminal::Goto(BlockId(4)),
+            Terminal::Goto(BlockId(4)),
+            Terminal::Return(None),
+        ]);
+
+        let func = build_reactive_function(&hir, &ReactiveScopeResult::default()).unwrap();
+        let [ReactiveStatement::Switch { cases, .. }, ReactiveStatement::Return(None)] = func.body.as_slice() else {
+            panic!("{:?}", func.body);
+        };
+        assert!(cases.iter().all(|case| matches!(case.body.as_slice(), [ReactiveStatement::Break(None)])), "{:?}", cases);
+    }
+
+    #[test]
+    fn test_deep_cfg_errors_instead_of_overflowing() {
+        let place = crate::hir::Place {
+            identifier: Identifier { name: "x".to_string(), id: 0, temporary: false },
+        };
+        // Each block branches into the next, nesting one `if` per block
+        let nested = |count: usize| {
+            let mut terminals: Vec<_> = (1..count)
+                .map(|i| Terminal::If {
+                    test: place.clone(),
+                    consequent: BlockId(i),
+                    alternate: BlockId(count),
+                })
+                .collect();
+            terminals.push(Terminal::Return(None));
+            terminals.push(Terminal::Return(None));
+            function_with_blocks(terminals)
+        };
+
+        let result = build_reactive_function(&nested(MAX_BUILD_DEPTH * 2), &ReactiveScopeResult::default());
+        assert!(matches!(result, Err(CompilerError::ControlFlowTooComplex { .. })), "{:?}", result);
+
+        // Nesting within the limit still builds
+        assert!(build_reactive_function(&nested(100), &ReactiveScopeResult::default()).is_ok());
+    }
+
+    #[test]
+    fn test_long_sequence_of_blocks_builds() {
+        let count = MAX_BUILD_DEPTH * 10;
+        let mut terminals: Vec<_> = (1..count).map(|i| Terminal::Goto(BlockId(i))).collect();
+        terminals.push(Terminal::Return(None));
+        let hir = function_with_blocks(terminals);
+
+        let func = build_reactive_function(&hir, &ReactiveScopeResult::default()).unwrap();
+        assert!(matches!(func.body.as_slice(), [ReactiveStatement::Return(None)]), "{:?}", func.body);
+    }
 }
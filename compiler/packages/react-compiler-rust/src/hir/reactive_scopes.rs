@@ -7,22 +7,28 @@
 //! 1. Infer initial scopes based on liveness ranges (values that need memoization)
 //! 2. Align scopes to safe boundaries (statement boundaries)
 //! 3. Merge overlapping scopes when dependencies are entangled
-//! 4. Propagate dependencies (inputs) for each scope
+//! 4. Split scopes around hook calls, which must never be memoized
+//! 5. Split scopes at early returns, so no scope spans several exit paths
+//! 6. Propagate dependencies (inputs) for each scope
+//! 7. Merge adjacent scopes that depend on the same inputs
 
+use crate::hir::dominators::PostDominatorTree;
 use crate::hir::inference::LivenessResult;
 use crate::hir::scope::{Declaration, Dependency, ReactiveScope, ScopeId};
 use crate::hir::{
-    BasicBlock, BlockId, HIRFunction, Identifier, Instruction, InstructionValue, Place,
+    BasicBlock, BlockId, HIRFunction, Identifier, Instruction, InstructionValue, Place, Terminal,
 };
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 /// Result of reactive scope construction
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ReactiveScopeResult {
     /// All reactive scopes in the function
     pub scopes: Vec<ReactiveScope>,
     /// Mapping from instruction index to scope ID (if any)
     pub instruction_scopes: HashMap<usize, ScopeId>,
+    /// Hooks that fell inside a liveness-derived scope, which was split around them
+    pub split_hooks: Vec<String>,
 }
 
 /// Context for scope inference
@@ -48,6 +54,7 @@ impl ScopeInferenceContext {
             range,
             dependencies: Vec::new(),
             declarations: Vec::new(),
+            cache_offset: 0,
         }
     }
 }
@@ -66,9 +73,21 @@ pub fn construct_reactive_scopes(
     // Step 3: Merge overlapping scopes
     let scopes = merge_scopes(scopes);
 
-    // Step 4: Propagate dependencies
+    // Step 4: Keep hook calls out of scopes
+    let (scopes, split_hooks) = split_scopes_at_hooks(func, scopes);
+
+    // Step 5: Keep each scope on one side of every early return
+    let scopes = split_scopes_at_returns(func, scopes);
+
+    // Step 6: Propagate dependencies
     let scopes = propagate_dependencies(func, scopes, liveness);
 
+    // Step 7: Guard scopes with the same inputs together
+    let mut scopes = merge_scopes_with_same_dependencies(func, scopes, liveness);
+
+    // Step 8: Give each scope its own range of the memo cache
+    assign_cache_offsets(&mut scopes);
+
     // Build instruction -> scope mapping
     let mut instruction_scopes = HashMap::new();
     for scope in &scopes {
@@ -80,6 +99,16 @@ pub fn construct_reactive_scopes(
     ReactiveScopeResult {
         scopes,
         instruction_scopes,
+        split_hooks,
+    }
+}
+
+/// Step 8: Lay scopes out back to back in the shared `$` cache, in order.
+fn assign_cache_offsets(scopes: &mut [ReactiveScope]) {
+    let mut offset = 0;
+    for scope in scopes {
+        scope.cache_offset = offset;
+        offset += scope.cache_slots();
     }
 }
 
@@ -102,7 +131,7 @@ fn infer_scopes(_func: &HIRFunction, liveness: &LivenessResult) -> Vec<ReactiveS
                 return false;
             }
             // Skip temporaries (t0, t1, etc.) - they're internal
-            if id.name.starts_with('t') && id.name[1..].chars().all(|c| c.is_ascii_digit()) {
+            if id.temporary {
                 return false;
             }
             true
@@ -126,11 +155,55 @@ fn infer_scopes(_func: &HIRFunction, liveness: &LivenessResult) -> Vec<ReactiveS
 ///
 /// Scopes should start and end at clean statement boundaries,
 /// not in the middle of expressions.
-fn align_scopes(scopes: &mut Vec<ReactiveScope>, _func: &HIRFunction) {
-    // For now, we use a simple alignment: scopes stay as-is
-    // since our instruction indices already correspond to statement-level operations.
-    // In a more advanced implementation, we'd analyze the CFG to find
-    // safe insertion points for useMemo.
+///
+/// A variable's scope starts where it is assigned, after the temporaries
+/// of the expression computing it. Left outside, those would be recomputed
+/// on every render and become dependencies: `items.map(f).filter(g)` would
+/// depend on the fresh array from `map`. So each scope's start moves back
+/// over temporaries only the scope reads, staying within its block. A hook
+/// call at either side of the start stops it, so the hook stays at the edge
+/// where step 4 can trim it off.
+fn align_scopes(scopes: &mut [ReactiveScope], func: &HIRFunction) {
+    let (instructions, rpo) = linearize_instructions(func);
+    let hooks: HashSet<usize> = find_hook_calls(&instructions).into_iter().map(|(idx, _)| idx).collect();
+
+    // Where each block's instructions start, and the last read of each
+    // identifier; a terminal reads at the end of its block
+    let mut block_starts = HashSet::new();
+    let mut last_use: HashMap<Identifier, usize> = HashMap::new();
+    let mut end = 0;
+    for block_id in &rpo {
+        let block = &func.blocks[block_id];
+        block_starts.insert(end);
+        for instr in &block.instructions {
+            for used in get_operand_identifiers(&instr.value) {
+                last_use.insert(used, end);
+            }
+            end += 1;
+        }
+        let tests: Vec<&Place> = match &block.terminal {
+            Terminal::If { test, .. } | Terminal::Return(Some(test)) => vec![test],
+            Terminal::Switch { test, cases, .. } => {
+                std::iter::once(test).chain(cases.iter().map(|(label, _)| label)).collect()
+            }
+            Terminal::Goto(_) | Terminal::Return(None) => vec![],
+        };
+        for test in tests {
+            last_use.insert(test.identifier.clone(), end);
+        }
+    }
+
+    for scope in scopes.iter_mut() {
+        let (mut start, end) = scope.range;
+        while start > 0 && !block_starts.contains(&start) && !hooks.contains(&start) && !hooks.contains(&(start - 1)) {
+            let id = &instructions[start - 1].lvalue.identifier;
+            if !id.temporary || last_use.get(id).is_none_or(|&idx| idx >= end) {
+                break;
+            }
+            start -= 1;
+        }
+        scope.range.0 = start;
+    }
 
     // Sort scopes by start position
     scopes.sort_by_key(|s| s.range.0);
@@ -169,42 +242,248 @@ fn merge_scopes(mut scopes: Vec<ReactiveScope>) -> Vec<ReactiveScope> {
     merged
 }
 
-/// Step 4: Propagate dependencies for each scope
+/// Step 4: Split scopes around hook calls
+///
+/// A scope's body only runs when its dependencies change, so a hook call
+/// inside one would be conditional and break the Rules of Hooks. Each scope
+/// containing hook calls is cut into the pieces before, between and after
+/// them.
+///
+/// A hook at either end of a scope only produces or consumes the scope's
+/// values (`const theme = useContext(...)`), so trimming it off is routine.
+/// Returns the names of the hooks that sat strictly inside a scope and so
+/// forced a real split.
+fn split_scopes_at_hooks(
+    func: &HIRFunction,
+    scopes: Vec<ReactiveScope>,
+) -> (Vec<ReactiveScope>, Vec<String>) {
+    let (instructions, _) = linearize_instructions(func);
+    let hooks = find_hook_calls(&instructions);
+    let mut next_id = scopes.iter().map(|s| s.id.0 + 1).max().unwrap_or(0);
+
+    let mut result = Vec::new();
+    let mut split_hooks = Vec::new();
+    for scope in scopes {
+        let (start, end) = scope.range;
+        let inner: Vec<&(usize, String)> =
+            hooks.iter().filter(|(idx, _)| (start..end).contains(idx)).collect();
+        if inner.is_empty() {
+            result.push(scope);
+            continue;
+        }
+
+        let mut bounds = vec![start];
+        for (idx, name) in inner {
+            bounds.push(*idx);
+            bounds.push(idx + 1);
+            if start < *idx && idx + 1 < end {
+                split_hooks.push(name.clone());
+            }
+        }
+        bounds.push(end);
+
+        let mut id = Some(scope.id);
+        for piece in bounds.chunks(2) {
+            let (piece_start, piece_end) = (piece[0], piece[1]);
+            if piece_start >= piece_end {
+                continue;
+            }
+            let id = id.take().unwrap_or_else(|| {
+                next_id += 1;
+                ScopeId(next_id - 1)
+            });
+            result.push(ReactiveScope {
+                id,
+                range: (piece_start, piece_end),
+                dependencies: Vec::new(),
+                declarations: Vec::new(),
+                cache_offset: 0,
+            });
+        }
+    }
+
+    (result, split_hooks)
+}
+
+/// Step 5: Split scopes at early returns
+///
+/// Instructions are linearized in reverse post order, so the blocks after a
+/// returning block belong to other paths through the function. A scope
+/// reaching past one would cache values from a path that returned alongside
+/// ones that were never computed on it, so it's cut where the block ends.
+fn split_scopes_at_returns(func: &HIRFunction, scopes: Vec<ReactiveScope>) -> Vec<ReactiveScope> {
+    let (_, rpo) = linearize_instructions(func);
+    let returns = return_boundaries(func, &rpo);
+    let mut next_id = scopes.iter().map(|s| s.id.0 + 1).max().unwrap_or(0);
+
+    let mut result = Vec::new();
+    for scope in scopes {
+        let (start, end) = scope.range;
+        let mut bounds: Vec<usize> = returns.iter().copied().filter(|&idx| start < idx && idx < end).collect();
+        if bounds.is_empty() {
+            result.push(scope);
+            continue;
+        }
+        bounds.insert(0, start);
+        bounds.push(end);
+
+        let mut id = Some(scope.id);
+        for piece in bounds.windows(2) {
+            let id = id.take().unwrap_or_else(|| {
+                next_id += 1;
+                ScopeId(next_id - 1)
+            });
+            result.push(ReactiveScope {
+                id,
+                range: (piece[0], piece[1]),
+                dependencies: Vec::new(),
+                declarations: Vec::new(),
+                cache_offset: 0,
+            });
+        }
+    }
+
+    result
+}
+
+/// Where each returning block ends in the linear instruction order.
+fn return_boundaries(func: &HIRFunction, rpo: &[BlockId]) -> Vec<usize> {
+    let mut returns = Vec::new();
+    let mut end = 0;
+    for block_id in rpo {
+        let block = &func.blocks[block_id];
+        end += block.instructions.len();
+        if matches!(block.terminal, Terminal::Return(_)) {
+            returns.push(end);
+        }
+    }
+    returns
+}
+
+/// Find calls to hooks (`useFoo(...)` or `React.useFoo(...)`), returning each
+/// call's linear instruction index and the hook's name.
+fn find_hook_calls(instructions: &[&Instruction]) -> Vec<(usize, String)> {
+    let mut globals: HashMap<&Identifier, &str> = HashMap::new();
+    let mut hooks = Vec::new();
+    for (idx, instr) in instructions.iter().enumerate() {
+        match &instr.value {
+            // Unversioned names are globals (or parameters)
+            InstructionValue::LoadLocal(place) if place.identifier.id == 0 => {
+                globals.insert(&instr.lvalue.identifier, &place.identifier.name);
+            }
+            InstructionValue::Call { callee, .. } => {
+                if let Some(name) = globals.get(&callee.identifier)
+                    && is_hook_name(name)
+                {
+                    hooks.push((idx, name.to_string()));
+                }
+            }
+            InstructionValue::MethodCall { property, .. } if is_hook_name(property) => {
+                hooks.push((idx, property.clone()));
+            }
+            _ => {}
+        }
+    }
+    hooks
+}
+
+/// `use`, or `use` followed by an uppercase letter.
+pub(crate) fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+/// Step 6: Propagate dependencies for each scope
 ///
 /// A dependency is a value that:
-/// - Is used inside the scope
-/// - Is defined outside the scope
+/// - Is used inside the scope, or tested by a branch the scope runs partly under
+/// - Is defined outside the scope (parameters always are)
+///
+/// Liveness gives every load of a variable the range of the variable itself,
+/// so a value counts as defined outside only if no instruction in the scope
+/// defines it. Otherwise the second `props` in `props.items.map(...)` would
+/// look like an input.
 fn propagate_dependencies(
     func: &HIRFunction,
     mut scopes: Vec<ReactiveScope>,
     liveness: &LivenessResult,
 ) -> Vec<ReactiveScope> {
     // Linearize instructions (RPO order)
-    let (instructions, _) = linearize_instructions(func);
+    let (instructions, rpo) = linearize_instructions(func);
 
-    for scope in &mut scopes {
-        let mut deps: BTreeSet<(String, usize)> = BTreeSet::new();
-        let mut decls: BTreeSet<(String, usize)> = BTreeSet::new();
-
-        // Collect all uses and definitions within the scope
-        for idx in scope.range.0..scope.range.1 {
-            if idx >= instructions.len() {
-                break;
-            }
+    // Where each block's instructions sit in the linear order
+    let mut block_ranges = Vec::new();
+    let mut end = 0;
+    for block_id in &rpo {
+        let start = end;
+        end += func.blocks[block_id].instructions.len();
+        block_ranges.push((*block_id, start, end));
+    }
+    let controlled = control_dependents(func);
+    let key_inputs = jsx_key_inputs(func);
+    let defined_before = |id: &Identifier, index: usize| match liveness.ranges.get(id) {
+        Some(&(def_start, _)) => def_start < index,
+        // Parameters are defined before the first instruction
+        None => func.params.contains(id),
+    };
 
-            let instr = &instructions[idx];
+    for scope in &mut scopes {
+        let mut deps: BTreeSet<(String, usize, bool)> = BTreeSet::new();
+        let scope_instructions = &instructions[scope.range.0.min(instructions.len())..scope.range.1.min(instructions.len())];
+        let decls: BTreeSet<(String, usize, bool)> = scope_instructions
+            .iter()
+            .map(|instr| {
+                let id = &instr.lvalue.identifier;
+                (id.name.clone(), id.id, id.temporary)
+            })
+            .collect();
+        let defined_outside = |id: &Identifier| {
+            defined_before(id, scope.range.0) && !decls.contains(&(id.name.clone(), id.id, id.temporary))
+        };
 
-            // Record definition (lvalue)
+        // Collect all uses within the scope
+        for instr in scope_instructions {
             let id = &instr.lvalue.identifier;
-            decls.insert((id.name.clone(), id.id));
+
+            // A new `key` or `ref` doesn't change what the element renders
+            if key_inputs.contains(id) {
+                continue;
+            }
 
             // Record uses (operands)
             for used in get_operand_identifiers(&instr.value) {
+                if key_inputs.contains(&used) {
+                    continue;
+                }
                 // If this use is defined outside the scope, it's a dependency
-                if let Some(&(def_start, _)) = liveness.ranges.get(&used) {
-                    if def_start < scope.range.0 {
-                        deps.insert((used.name.clone(), used.id));
-                    }
+                if defined_outside(&used) {
+                    deps.insert((used.name.clone(), used.id, used.temporary));
+                }
+            }
+        }
+
+        // A scope that runs partly under a branch needs the branch's test:
+        // it decides which of the scope's values get built
+        let scope_blocks: HashSet<BlockId> = block_ranges
+            .iter()
+            .filter(|&&(_, start, end)| start < end && start < scope.range.1 && scope.range.0 < end)
+            .map(|&(block_id, _, _)| block_id)
+            .collect();
+        for (branch, blocks) in &controlled {
+            if scope_blocks.is_disjoint(blocks) || scope_blocks.is_subset(blocks) {
+                continue;
+            }
+            let tests = match &func.blocks[branch].terminal {
+                Terminal::If { test, .. } => vec![test],
+                Terminal::Switch { test, cases, .. } => {
+                    std::iter::once(test).chain(cases.iter().map(|(label, _)| label)).collect()
+                }
+                _ => vec![],
+            };
+            for test in tests {
+                let used = &test.identifier;
+                if defined_outside(used) {
+                    deps.insert((used.name.clone(), used.id, used.temporary));
                 }
             }
         }
@@ -212,18 +491,18 @@ fn propagate_dependencies(
         // Convert to Dependency/Declaration structs (sorted for deterministic output)
         scope.dependencies = deps
             .into_iter()
-            .map(|(name, id)| Dependency {
+            .map(|(name, id, temporary)| Dependency {
                 place: Place {
-                    identifier: Identifier { name, id },
+                    identifier: Identifier { name, id, temporary },
                 },
             })
             .collect();
 
         scope.declarations = decls
             .into_iter()
-            .map(|(name, id)| Declaration {
+            .map(|(name, id, temporary)| Declaration {
                 place: Place {
-                    identifier: Identifier { name, id },
+                    identifier: Identifier { name, id, temporary },
                 },
             })
             .collect();
@@ -232,6 +511,175 @@ fn propagate_dependencies(
     scopes
 }
 
+/// Step 7: Merge adjacent scopes with identical dependencies
+///
+/// Two scopes in a row that read the same inputs are always invalidated
+/// together, so guarding them separately only repeats the same comparisons.
+/// Every `props.x` is loaded into its own temporary, so dependencies are
+/// compared by what they load rather than by identifier.
+///
+/// The combined scope takes in whatever ran between the two, so that may
+/// only be loads of the second scope's inputs, and the result must contain
+/// no hook call or early return and still have the same dependencies.
+fn merge_scopes_with_same_dependencies(
+    func: &HIRFunction,
+    scopes: Vec<ReactiveScope>,
+    liveness: &LivenessResult,
+) -> Vec<ReactiveScope> {
+    let (instructions, rpo) = linearize_instructions(func);
+    let hooks = find_hook_calls(&instructions);
+    let returns = return_boundaries(func, &rpo);
+    let definitions: HashMap<&Identifier, &InstructionValue> =
+        instructions.iter().map(|instr| (&instr.lvalue.identifier, &instr.value)).collect();
+    let path = |id: &Identifier| dependency_path(&definitions, id);
+    // Loading `props.x` reads `props` too; only the longest paths matter
+    let inputs = |scope: &ReactiveScope| -> BTreeSet<String> {
+        let paths: BTreeSet<String> = scope.dependencies.iter().map(|dep| path(&dep.place.identifier)).collect();
+        paths
+            .iter()
+            .filter(|p| !paths.iter().any(|other| other.len() > p.len() && extends_path(other, p)))
+            .cloned()
+            .collect()
+    };
+
+    let mut merged: Vec<ReactiveScope> = Vec::new();
+    for scope in scopes {
+        if let Some(last) = merged.last_mut()
+            && inputs(last) == inputs(&scope)
+        {
+            let range = (last.range.0, scope.range.1);
+            let contains_hook = hooks.iter().any(|(idx, _)| (range.0..range.1).contains(idx));
+            let crosses_return = returns.iter().any(|&idx| range.0 < idx && idx < range.1);
+            let scope_inputs = inputs(&scope);
+            let gap_reloads_inputs = instructions[last.range.1..scope.range.0].iter().all(|instr| {
+                matches!(instr.value, InstructionValue::LoadLocal(_) | InstructionValue::PropertyLoad { .. })
+                    && scope_inputs.iter().any(|input| extends_path(input, &path(&instr.lvalue.identifier)))
+            });
+            if !contains_hook && !crosses_return && gap_reloads_inputs {
+                let combined = ReactiveScope {
+                    id: last.id,
+                    range,
+                    dependencies: Vec::new(),
+                    declarations: Vec::new(),
+                    cache_offset: 0,
+                };
+                let mut combined = propagate_dependencies(func, vec![combined], liveness).remove(0);
+                if inputs(&combined) == inputs(last) {
+                    // The reloads are declared inside now; compare the originals
+                    combined.dependencies = std::mem::take(&mut last.dependencies);
+                    *last = combined;
+                    continue;
+                }
+            }
+        }
+        merged.push(scope);
+    }
+
+    merged
+}
+
+/// The value `id` holds, as the variable and properties it was loaded from
+/// (`props#0.x`), so separate loads of the same value compare equal.
+fn dependency_path(definitions: &HashMap<&Identifier, &InstructionValue>, id: &Identifier) -> String {
+    match definitions.get(id) {
+        Some(InstructionValue::LoadLocal(place)) => dependency_path(definitions, &place.identifier),
+        Some(InstructionValue::PropertyLoad { object, property }) => {
+            format!("{}.{}", dependency_path(definitions, &object.identifier), property)
+        }
+        _ => format!("{}#{}", id.name, id.id),
+    }
+}
+
+/// Whether `path` is `prefix` or one of its properties.
+fn extends_path(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Identifiers that are only read to compute a JSX element's `key` or `ref`,
+/// directly or through other such identifiers.
+///
+/// React uses both for reconciliation and the element's props never include
+/// them, so they aren't dependencies of the scope building the element.
+fn jsx_key_inputs(func: &HIRFunction) -> HashSet<Identifier> {
+    // Who reads each identifier: the instruction's lvalue, or `None` for a
+    // read by a terminal, or by a JSX element as anything but `key` or `ref`
+    let mut readers: HashMap<Identifier, Vec<Option<Identifier>>> = HashMap::new();
+    let mut key_reads: HashSet<Identifier> = HashSet::new();
+    for block in func.blocks.values() {
+        for instr in &block.instructions {
+            let mut operands = get_operand_identifiers(&instr.value);
+            let mut reader = Some(instr.lvalue.identifier.clone());
+            if let InstructionValue::JsxElement { key, ref_, .. } = &instr.value {
+                for place in key.iter().chain(ref_) {
+                    key_reads.insert(place.identifier.clone());
+                    // Drop one read; the same value may also be a prop
+                    if let Some(index) = operands.iter().position(|id| *id == place.identifier) {
+                        operands.remove(index);
+                    }
+                }
+                reader = None;
+            }
+            for operand in operands {
+                readers.entry(operand).or_default().push(reader.clone());
+            }
+        }
+        let tests: Vec<&Place> = match &block.terminal {
+            Terminal::If { test, .. } | Terminal::Return(Some(test)) => vec![test],
+            Terminal::Switch { test, cases, .. } => {
+                std::iter::once(test).chain(cases.iter().map(|(label, _)| label)).collect()
+            }
+            Terminal::Goto(_) | Terminal::Return(None) => vec![],
+        };
+        for test in tests {
+            readers.entry(test.identifier.clone()).or_default().push(None);
+        }
+    }
+
+    let mut inputs: HashSet<Identifier> = HashSet::new();
+    loop {
+        let found: Vec<Identifier> = key_reads
+            .iter()
+            .chain(readers.keys())
+            .filter(|id| !inputs.contains(*id))
+            .filter(|id| {
+                readers.get(*id).into_iter().flatten().all(|reader| {
+                    reader.as_ref().is_some_and(|reader| inputs.contains(reader))
+                })
+            })
+            .cloned()
+            .collect();
+        if found.is_empty() {
+            break;
+        }
+        inputs.extend(found);
+    }
+    inputs
+}
+
+/// For each block ending in a branch, the blocks that only run on some of
+/// its paths: those between it and the point where its paths join again.
+fn control_dependents(func: &HIRFunction) -> HashMap<BlockId, HashSet<BlockId>> {
+    let post_dominators = PostDominatorTree::compute(func);
+    let mut result: HashMap<BlockId, HashSet<BlockId>> = HashMap::new();
+    for (&id, block) in &func.blocks {
+        if !matches!(block.terminal, Terminal::If { .. } | Terminal::Switch { .. }) {
+            continue;
+        }
+        let join = post_dominators.immediate_post_dominator(id);
+        let blocks = result.entry(id).or_default();
+        for succ in block.successors() {
+            let mut runner = Some(succ);
+            while let Some(current) = runner
+                && runner != join
+                && blocks.insert(current)
+            {
+                runner = post_dominators.immediate_post_dominator(current);
+            }
+        }
+    }
+    result
+}
+
 /// Linearize instructions in Reverse Post Order (same as liveness analysis)
 fn linearize_instructions(func: &HIRFunction) -> (Vec<&Instruction>, Vec<BlockId>) {
     let entry = func.entry_block;
@@ -270,7 +718,7 @@ fn post_order(
 }
 
 /// Extract identifiers used as operands in an instruction
-fn get_operand_identifiers(value: &InstructionValue) -> Vec<Identifier> {
+pub(crate) fn get_operand_identifiers(value: &InstructionValue) -> Vec<Identifier> {
     let mut result = Vec::new();
 
     match value {
@@ -281,7 +729,7 @@ fn get_operand_identifiers(value: &InstructionValue) -> Vec<Identifier> {
         InstructionValue::UnaryOp { operand, .. } => {
             result.push(operand.identifier.clone());
         }
-        InstructionValue::Call { callee, args } => {
+        InstructionValue::Call { callee, args, .. } => {
             result.push(callee.identifier.clone());
             for arg in args {
                 match arg {
@@ -290,6 +738,39 @@ fn get_operand_identifiers(value: &InstructionValue) -> Vec<Identifier> {
                 }
             }
         }
+        InstructionValue::MethodCall { receiver, args, .. } => {
+            result.push(receiver.identifier.clone());
+            for arg in args {
+                match arg {
+                    crate::hir::Argument::Regular(p) => result.push(p.identifier.clone()),
+                    crate::hir::Argument::Spread(p) => result.push(p.identifier.clone()),
+                }
+            }
+        }
+        InstructionValue::JsxElement { tag, props, children, key, ref_ } => {
+            result.push(tag.identifier.clone());
+            result.extend(key.iter().chain(ref_).map(|p| p.identifier.clone()));
+            for prop in props {
+                match prop {
+                    crate::hir::ObjectProperty::KeyValue { value, .. } => result.push(value.identifier.clone()),
+                    crate::hir::ObjectProperty::Spread(p) => result.push(p.identifier.clone()),
+                }
+            }
+            for child in children {
+                match child {
+                    crate::hir::Argument::Regular(p) => result.push(p.identifier.clone()),
+                    crate::hir::Argument::Spread(p) => result.push(p.identifier.clone()),
+                }
+            }
+        }
+        InstructionValue::JsxFragment { children } => {
+            for child in children {
+                match child {
+                    crate::hir::Argument::Regular(p) => result.push(p.identifier.clone()),
+                    crate::hir::Argument::Spread(p) => result.push(p.identifier.clone()),
+                }
+            }
+        }
         InstructionValue::Object { properties } => {
             for prop in properties {
                 match prop {
@@ -312,14 +793,17 @@ fn get_operand_identifiers(value: &InstructionValue) -> Vec<Identifier> {
                 }
             }
         }
-        InstructionValue::PropertyLoad { object, .. } => {
+        InstructionValue::PropertyLoad { object, .. }
+        | InstructionValue::PropertyDelete { object, .. }
+        | InstructionValue::ForInKeys { object } => {
             result.push(object.identifier.clone());
         }
         InstructionValue::PropertyStore { object, value, .. } => {
             result.push(object.identifier.clone());
             result.push(value.identifier.clone());
         }
-        InstructionValue::ComputedLoad { object, property } => {
+        InstructionValue::ComputedLoad { object, property }
+        | InstructionValue::ComputedDelete { object, property } => {
             result.push(object.identifier.clone());
             result.push(property.identifier.clone());
         }
@@ -343,6 +827,14 @@ fn get_operand_identifiers(value: &InstructionValue) -> Vec<Identifier> {
                 result.push(place.identifier.clone());
             }
         }
+        InstructionValue::Yield { argument, .. } => {
+            if let Some(arg) = argument {
+                result.push(arg.identifier.clone());
+            }
+        }
+        InstructionValue::Await { argument } => {
+            result.push(argument.identifier.clone());
+        }
         InstructionValue::Constant(_) => {}
     }
 
@@ -361,18 +853,21 @@ mod tests {
                 range: (0, 5),
                 dependencies: vec![],
                 declarations: vec![],
+                cache_offset: 0,
             },
             ReactiveScope {
                 id: ScopeId(1),
                 range: (3, 8),
                 dependencies: vec![],
                 declarations: vec![],
+                cache_offset: 0,
             },
             ReactiveScope {
                 id: ScopeId(2),
                 range: (10, 15),
                 dependencies: vec![],
                 declarations: vec![],
+                cache_offset: 0,
             },
         ];
 
@@ -382,4 +877,186 @@ mod tests {
         assert_eq!(merged[0].range, (0, 8)); // First two merged
         assert_eq!(merged[1].range, (10, 15)); // Third unchanged
     }
+
+    #[test]
+    fn test_cache_offsets_follow_previous_scopes() {
+        use crate::hir::scope::{Declaration, Dependency};
+        use crate::hir::{Identifier, Place};
+
+        let place = |name: &str| Place {
+            identifier: Identifier { name: name.to_string(), id: 1, temporary: false },
+        };
+        let scope = |n: usize, deps: &[&str], decls: &[&str]| ReactiveScope {
+            id: ScopeId(n),
+            range: (0, 0),
+            dependencies: deps.iter().map(|d| Dependency { place: place(d) }).collect(),
+            declarations: decls.iter().map(|d| Declaration { place: place(d) }).collect(),
+            cache_offset: 0,
+        };
+        let mut scopes = vec![
+            scope(0, &["a", "b"], &["x"]),
+            scope(1, &["c"], &["y"]),
+            scope(2, &[], &[]),
+            scope(3, &[], &["z"]),
+        ];
+
+        assign_cache_offsets(&mut scopes);
+
+        let offsets: Vec<_> = scopes.iter().map(|s| s.cache_offset).collect();
+        assert_eq!(offsets, vec![0, 3, 5, 6]);
+    }
+
+    #[test]
+    fn test_scopes_are_split_around_hook_calls() {
+        use crate::hir::inference::infer_liveness;
+        use crate::hir::lowering::LoweringContext;
+        use crate::hir::ssa::enter_ssa;
+        use oxc_allocator::Allocator;
+        use oxc_ast::ast::Statement;
+        use oxc_parser::Parser;
+        use oxc_span::SourceType;
+
+        let allocator = Allocator::default();
+        let source = include_str!("../../fixtures/hook_in_scope.js");
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        let Statement::FunctionDeclaration(func) = &ret.program.body[0] else {
+            panic!("expected a function declaration");
+        };
+        let hir = enter_ssa(LoweringContext::default().build_checked(func).unwrap().0);
+        let liveness = infer_liveness(&hir);
+
+        // `label` is live across the hook call, so its scope would contain it
+        let unsplit = merge_scopes(infer_scopes(&hir, &liveness));
+        let (instructions, _) = linearize_instructions(&hir);
+        let hooks = find_hook_calls(&instructions);
+        assert_eq!(hooks.len(), 1);
+        let hook = hooks[0].0;
+        assert!(unsplit.iter().any(|s| (s.range.0..s.range.1).contains(&hook)));
+
+        let result = construct_reactive_scopes(&hir, &liveness);
+        assert_eq!(result.split_hooks, ["useContext"]);
+        assert!(!result.instruction_scopes.contains_key(&hook));
+        // Values computed from the hook's result are still memoized after it
+        assert!(result.scopes.iter().any(|s| s.range.0 > hook));
+    }
+
+    #[test]
+    fn test_scopes_are_split_at_early_returns() {
+        use crate::hir::inference::infer_liveness;
+        use crate::hir::lowering::LoweringContext;
+        use crate::hir::ssa::enter_ssa;
+        use oxc_allocator::Allocator;
+        use oxc_ast::ast::Statement;
+        use oxc_parser::Parser;
+        use oxc_span::SourceType;
+
+        let allocator = Allocator::default();
+        let source = include_str!("../../tests/sprout/status_returns.js");
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        let Statement::FunctionDeclaration(func) = &ret.program.body[0] else {
+            panic!("expected a function declaration");
+        };
+        let hir = enter_ssa(LoweringContext::default().build_checked(func).unwrap().0);
+        let liveness = infer_liveness(&hir);
+
+        // Where each returning block ends in the linear order
+        let (_, rpo) = linearize_instructions(&hir);
+        let mut returns = Vec::new();
+        let mut end = 0;
+        for block_id in &rpo {
+            end += hir.blocks[block_id].instructions.len();
+            if matches!(hir.blocks[block_id].terminal, Terminal::Return(_)) {
+                returns.push(end);
+            }
+        }
+        assert_eq!(returns.len(), 4);
+
+        // `status` is read after the first early return, so its scope spans it
+        let unsplit = merge_scopes(infer_scopes(&hir, &liveness));
+        let spans = |s: &ReactiveScope| returns.iter().any(|&idx| s.range.0 < idx && idx < s.range.1);
+        assert!(unsplit.iter().any(spans));
+
+        let result = construct_reactive_scopes(&hir, &liveness);
+        assert!(!result.scopes.is_empty());
+        assert!(!result.scopes.iter().any(spans), "{:?}", result.scopes);
+    }
+
+    #[test]
+    fn test_adjacent_scopes_with_same_dependencies_are_merged() {
+        use crate::hir::inference::infer_liveness;
+        use crate::hir::lowering::LoweringContext;
+        use crate::hir::ssa::enter_ssa;
+        use oxc_allocator::Allocator;
+        use oxc_ast::ast::Statement;
+        use oxc_parser::Parser;
+        use oxc_span::SourceType;
+
+        let allocator = Allocator::default();
+        let source = include_str!("../../fixtures/same_dependency_scopes.js");
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        let Statement::FunctionDeclaration(func) = &ret.program.body[0] else {
+            panic!("expected a function declaration");
+        };
+        let hir = enter_ssa(LoweringContext::default().build_checked(func).unwrap().0);
+        let liveness = infer_liveness(&hir);
+        let declares = |s: &ReactiveScope, name: &str| s.declarations.iter().any(|d| d.place.identifier.name == name);
+        let dependencies =
+            |s: &ReactiveScope| s.dependencies.iter().map(|d| d.place.identifier.clone()).collect::<Vec<_>>();
+
+        // `items` and `summary` each get a scope, both reading only `props`
+        let mut scopes = infer_scopes(&hir, &liveness);
+        align_scopes(&mut scopes, &hir);
+        let unmerged = propagate_dependencies(&hir, merge_scopes(scopes), &liveness);
+        assert_eq!(unmerged.len(), 2, "{:?}", unmerged);
+        assert_eq!(dependencies(&unmerged[0]), dependencies(&unmerged[1]));
+
+        let result = construct_reactive_scopes(&hir, &liveness);
+        assert_eq!(result.scopes.len(), 1, "{:?}", result.scopes);
+        let scope = &result.scopes[0];
+        assert!(declares(scope, "items") && declares(scope, "summary"), "{:?}", scope);
+        assert_eq!(dependencies(scope), dependencies(&unmerged[0]));
+    }
+
+    #[test]
+    fn test_method_chain_is_memoized_in_one_scope() {
+        use crate::hir::inference::infer_liveness;
+        use crate::hir::lowering::LoweringContext;
+        use crate::hir::ssa::enter_ssa;
+        use oxc_allocator::Allocator;
+        use oxc_ast::ast::Statement;
+        use oxc_parser::Parser;
+        use oxc_span::SourceType;
+
+        let allocator = Allocator::default();
+        let source = include_str!("../../tests/sprout/array_method_chain.js");
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        let Statement::FunctionDeclaration(func) = &ret.program.body[0] else {
+            panic!("expected a function declaration");
+        };
+        let hir = enter_ssa(LoweringContext::default().build_checked(func).unwrap().0);
+        let liveness = infer_liveness(&hir);
+
+        // The chain is the function's first statement, ending with `filter`
+        let (instructions, _) = linearize_instructions(&hir);
+        let filter = instructions
+            .iter()
+            .position(|instr| matches!(&instr.value, InstructionValue::MethodCall { property, .. } if property == "filter"))
+            .unwrap();
+
+        let result = construct_reactive_scopes(&hir, &liveness);
+        let scope = result.instruction_scopes[&0];
+        assert!((0..=filter).all(|idx| result.instruction_scopes.get(&idx) == Some(&scope)), "{:?}", result.scopes);
+        let scope = result.scopes.iter().find(|s| s.id == scope).unwrap();
+        let dependencies: Vec<_> = scope.dependencies.iter().map(|d| d.place.identifier.name.as_str()).collect();
+        assert_eq!(dependencies, ["props"]);
+    }
+
+    #[test]
+    fn test_hook_names() {
+        assert!(is_hook_name("use"));
+        assert!(is_hook_name("useContext"));
+        assert!(!is_hook_name("user"));
+        assert!(!is_hook_name("usefulThing"));
+        assert!(!is_hook_name("Use"));
+    }
 }
@@ -13,6 +13,19 @@ pub struct ReactiveScope {
     // Dependencies (inputs) and Declarations (outputs)
     pub dependencies: Vec<Dependency>,
     pub declarations: Vec<Declaration>,
+
+    // First `$` slot this scope uses. Every scope in a function shares one
+    // cache, so scopes are laid out back to back.
+    pub cache_offset: usize,
+}
+
+impl ReactiveScope {
+    /// Number of cache slots the scope needs: one per dependency and
+    /// declaration. A scope with neither still reserves a slot for its
+    /// sentinel check, so it never reads another scope's slot.
+    pub fn cache_slots(&self) -> usize {
+        (self.dependencies.len() + self.declarations.len()).max(1)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
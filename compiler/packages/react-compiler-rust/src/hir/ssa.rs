@@ -1,17 +1,17 @@
 use crate::hir::dominators::DominatorTree;
 use crate::hir::{
-    BlockId, HIRFunction, Identifier, InstrId, Instruction, InstructionValue, Place,
+    BlockId, HIRFunction, Identifier, InstrId, Instruction, InstructionValue, Place, UnaryOperator,
 };
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-pub fn enter_ssa(mut func: HIRFunction) -> HIRFunction {
-    // 0. Compute Predecessors
-    // We need to rebuild predecessors because lowering doesn't populate them fully/correctly
-    // or they might be stale.
+/// Rebuild each block's `preds` from the terminals, which is what the
+/// dominator computations read. Lowering doesn't populate them fully or
+/// correctly, and they go stale when blocks change.
+pub fn compute_predecessors(func: &mut HIRFunction) {
     for block in func.blocks.values_mut() {
         block.preds.clear();
     }
-    
+
     // We need to collect edges first to avoid double borrow
     let mut edges = Vec::new();
     for (id, block) in &func.blocks {
@@ -25,6 +25,11 @@ pub fn enter_ssa(mut func: HIRFunction) -> HIRFunction {
             block.preds.push(pred);
         }
     }
+}
+
+pub fn enter_ssa(mut func: HIRFunction) -> HIRFunction {
+    // 0. Compute Predecessors
+    compute_predecessors(&mut func);
 
     // 1. Compute Dominator Tree
     let dom_tree = DominatorTree::compute(&func);
@@ -35,15 +40,20 @@ pub fn enter_ssa(mut func: HIRFunction) -> HIRFunction {
     // Temporaries are just lvalues of other instructions.
     let mut globals = BTreeSet::new();
     let mut blocks_defining_global: BTreeMap<String, BTreeSet<BlockId>> = BTreeMap::new();
+    // Temporaries assigned on several paths, like a conditional's result
+    let mut temporaries = HashSet::new();
 
     for block in func.blocks.values() {
         for instr in &block.instructions {
             if let InstructionValue::StoreLocal(place, _) = &instr.value {
                 let name = place.identifier.name.clone();
+                if place.identifier.temporary {
+                    temporaries.insert(name.clone());
+                }
                 globals.insert(name.clone());
                 blocks_defining_global
                     .entry(name)
-                    .or_insert_with(BTreeSet::new)
+                    .or_default()
                     .insert(block.id);
             }
         }
@@ -79,7 +89,7 @@ pub fn enter_ssa(mut func: HIRFunction) -> HIRFunction {
                         
                         phi_placements
                             .entry(d)
-                            .or_insert_with(Vec::new)
+                            .or_default()
                             .push((var.clone(), phi_id));
                         
                         has_phi.insert(d);
@@ -105,7 +115,8 @@ pub fn enter_ssa(mut func: HIRFunction) -> HIRFunction {
                     lvalue: Place {
                         identifier: Identifier {
                             name: var_name.clone(),
-                            id: 0, 
+                            id: 0,
+                            temporary: temporaries.contains(&var_name),
                         },
                     },
                     value: InstructionValue::Phi {
@@ -222,12 +233,19 @@ fn rename_block(
                     identifier: Identifier {
                         name: name.clone(),
                         id: new_v,
+                        temporary: target.identifier.temporary,
                     }
                 };
                 instr.value = InstructionValue::LoadLocal(val_clone);
                 
                 pushed_vars.push(name);
             }
+            // `typeof x` reads the variable itself, see `lower_unary_expression`
+            InstructionValue::UnaryOp { op: UnaryOperator::TypeOf, operand }
+                if ctx.stacks.contains_key(&operand.identifier.name) =>
+            {
+                operand.identifier.id = ctx.current_version(&operand.identifier.name);
+            }
             // For other instructions (BinaryOp, Call, etc.), operands are Places.
             // If those Places refer to Promotable variables, we should rename them.
             // BUT, our lowering logic uses LoadLocal to read variables into temporaries.
@@ -266,6 +284,7 @@ fn rename_block(
             for instr in &mut succ_block.instructions {
                 if let InstructionValue::Phi { operands } = &mut instr.value {
                     let name = instr.lvalue.identifier.name.clone();
+                    let temporary = instr.lvalue.identifier.temporary;
                     // If this Phi is for one of our variables
                     // (It must be, if we inserted it)
                     // Check if we track this var
@@ -273,8 +292,9 @@ fn rename_block(
                          let v = ctx.current_version(&name);
                          let place = Place {
                              identifier: Identifier {
-                                 name: name,
+                                 name,
                                  id: v,
+                                 temporary,
                              }
                          };
                          operands.push((block_id_copy, place));
@@ -0,0 +1,65 @@
+//! HIR Validation
+//!
+//! Checks run on freshly lowered HIR to find code the compiler cannot model
+//! soundly. Functions that fail are emitted as-is instead of being memoized.
+
+use crate::hir::{HIRFunction, Identifier, InstructionValue};
+use std::collections::{HashMap, HashSet};
+
+/// Whether the function calls the global `eval`, which can read and create
+/// bindings the compiler never sees.
+pub fn calls_eval(func: &HIRFunction) -> bool {
+    // Temporaries holding the `eval` global; loads always precede their use
+    let mut eval_temps: HashSet<&Identifier> = HashSet::new();
+    for block in func.blocks.values() {
+        for instr in &block.instructions {
+            match &instr.value {
+                InstructionValue::LoadLocal(place)
+                    if place.identifier.name == "eval" && place.identifier.id == 0 =>
+                {
+                    eval_temps.insert(&instr.lvalue.identifier);
+                }
+                InstructionValue::Call { callee, .. } if eval_temps.contains(&callee.identifier) => {
+                    return true;
+                }
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
+/// Parameters the function writes to or deletes properties from, directly
+/// or through a nested property (`props.style.color = ...`). Each parameter
+/// is listed once, in the order it is first mutated.
+pub fn mutated_params(func: &HIRFunction) -> Vec<String> {
+    // Temporaries holding a parameter or a value read out of one
+    let mut param_temps: HashMap<&Identifier, &str> = HashMap::new();
+    let mut mutated: Vec<String> = Vec::new();
+    for block in func.blocks.values() {
+        for instr in &block.instructions {
+            match &instr.value {
+                InstructionValue::LoadLocal(place) if func.params.contains(&place.identifier) => {
+                    param_temps.insert(&instr.lvalue.identifier, &place.identifier.name);
+                }
+                InstructionValue::PropertyLoad { object, .. } | InstructionValue::ComputedLoad { object, .. } => {
+                    if let Some(&param) = param_temps.get(&object.identifier) {
+                        param_temps.insert(&instr.lvalue.identifier, param);
+                    }
+                }
+                InstructionValue::PropertyStore { object, .. }
+                | InstructionValue::ComputedStore { object, .. }
+                | InstructionValue::PropertyDelete { object, .. }
+                | InstructionValue::ComputedDelete { object, .. } => {
+                    if let Some(&param) = param_temps.get(&object.identifier)
+                        && !mutated.iter().any(|name| name == param)
+                    {
+                        mutated.push(param.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    mutated
+}
@@ -1,67 +1,625 @@
 pub mod codegen;
+pub mod diagnostics;
 pub mod error;
 pub mod hir;
 pub mod napi;
+pub mod options;
+#[cfg(feature = "sprout")]
 pub mod sprout;
+pub mod timings;
 
+pub use diagnostics::{Diagnostic, Diagnostics, Severity};
 pub use error::{CompilerError, CompilerResult};
+pub use hir::print::format_hir;
+pub use codegen::{CodegenOptions, JsxRuntime};
+pub use options::CompileOptions;
+pub use timings::PhaseTimings;
 
-use codegen::generate_code;
+use codegen::{generate_code, generate_code_with_options, generate_method_with_options, generate_runtime_import};
 use hir::inference::infer_liveness;
-use hir::lowering::LoweringContext;
-use hir::reactive_function::build_reactive_function;
-use hir::reactive_scopes::construct_reactive_scopes;
+use hir::lowering::{directive_source, LoweringContext};
+use hir::optimize::{
+    eliminate_common_subexpressions, eliminate_dead_phis, fold_constants, hoist_loop_invariants,
+};
+use hir::reactive_function::{build_reactive_function, ReactiveFunction};
+use hir::reactive_scopes::{construct_reactive_scopes, get_operand_identifiers, is_hook_name, ReactiveScopeResult};
+use hir::validation::{calls_eval, mutated_params};
 use hir::ssa::enter_ssa;
+use hir::HIRFunction;
 use miette::Result;
 use oxc_allocator::Allocator;
 use oxc_parser::Parser as OxcParser;
-use oxc_span::SourceType;
+use oxc_span::{GetSpan, SourceType};
+use std::time::Instant;
+
+/// Every parser error, joined into one [`CompilerError::ParseError`].
+fn parse_error(errors: &[impl ToString]) -> CompilerError {
+    let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+    CompilerError::ParseError { message: messages.join("; ") }
+}
 
 /// Compile JavaScript/TypeScript source code to optimized JavaScript with memoization.
+///
+/// Fails with [`CompilerError::ParseError`] if the source does not parse.
 pub fn compile(source_text: &str, source_type: SourceType) -> Result<String> {
+    compile_with_options(source_text, source_type, &CompileOptions::default())
+        .map(|output| output.code)
+}
+
+/// Output of [`compile_with_options`].
+#[derive(Debug)]
+pub struct CompileOutput {
+    /// The generated code for all compiled functions.
+    pub code: String,
+    /// Non-fatal problems encountered while compiling.
+    pub warnings: Vec<CompilerError>,
+}
+
+/// Compile source code with explicit [`CompileOptions`], also returning any warnings.
+pub fn compile_with_options(
+    source_text: &str,
+    source_type: SourceType,
+    options: &CompileOptions,
+) -> Result<CompileOutput> {
+    let mut diagnostics = Diagnostics::new();
+    let code = compile_program(source_text, source_type, options, &mut PhaseTimings::default(), &mut diagnostics)?;
+    Ok(CompileOutput {
+        code,
+        warnings: diagnostics.into_errors(),
+    })
+}
+
+/// Compile source code with explicit [`CompileOptions`], returning every
+/// problem found as a [`Diagnostic`] tagged with its function.
+pub fn compile_detailed(
+    source_text: &str,
+    source_type: SourceType,
+    options: &CompileOptions,
+) -> Result<(String, Vec<Diagnostic>)> {
+    let mut diagnostics = Diagnostics::new();
+    let code = compile_program(source_text, source_type, options, &mut PhaseTimings::default(), &mut diagnostics)?;
+    Ok((code, diagnostics.into_diagnostics()))
+}
+
+/// Compile each top-level function separately, returning its name and code
+/// in source order. Anonymous functions are named `"anonymous"`. Returns
+/// nothing if the source fails to parse.
+pub fn compile_all(source_text: &str, source_type: SourceType) -> Vec<(String, String)> {
+    let allocator = Allocator::default();
+    let ret = OxcParser::new(&allocator, source_text, source_type).parse();
+    if !ret.errors.is_empty() {
+        return Vec::new();
+    }
+
+    let options = CompileOptions::default();
+    let mut timings = PhaseTimings::default();
+    let mut diagnostics = Diagnostics::new();
+    ret.program
+        .body
+        .iter()
+        .filter_map(top_level_function)
+        .map(|(func, prefix)| {
+            compile_function(func, prefix, source_text, &options, &mut timings, &mut diagnostics)
+        })
+        .collect()
+}
+
+/// Compile source code, also reporting how long each compiler phase took.
+pub fn compile_with_timings(
+    source_text: &str,
+    source_type: SourceType,
+) -> Result<(String, PhaseTimings)> {
+    let mut timings = PhaseTimings::default();
+    let code = compile_program(
+        source_text,
+        source_type,
+        &CompileOptions::default(),
+        &mut timings,
+        &mut Diagnostics::new(),
+    )?;
+    Ok((code, timings))
+}
+
+fn compile_program(
+    source_text: &str,
+    source_type: SourceType,
+    options: &CompileOptions,
+    timings: &mut PhaseTimings,
+    diagnostics: &mut Diagnostics,
+) -> Result<String> {
     let allocator = Allocator::default();
 
     let ret = OxcParser::new(&allocator, source_text, source_type)
         .parse();
 
     if !ret.errors.is_empty() {
-        use std::fmt::Write;
-        let mut err_msg = String::new();
-        writeln!(&mut err_msg, "Parse Errors:").unwrap();
-        for error in ret.errors {
-            writeln!(&mut err_msg, "{:?}", error).unwrap();
-        }
-        return Ok(err_msg);
+        return Err(parse_error(&ret.errors).into());
     }
 
+    // Module directives have to come before the runtime import
     let mut output = String::new();
+    if options.codegen.preserve_directives {
+        for directive in &ret.program.directives {
+            output.push_str(&format!("{};\n", directive_source(directive)));
+        }
+    }
+    if let Some(module) = &options.codegen.emit_runtime_import {
+        output.push_str(&generate_runtime_import(module));
+    }
 
     for stmt in &ret.program.body {
-        if let oxc_ast::ast::Statement::FunctionDeclaration(func) = stmt {
-            // Phase 1-2: Lower AST to HIR
-            let ctx = LoweringContext::default();
-            let hir = ctx.build(func);
+        let code = if let Some((func, prefix)) = top_level_function(stmt) {
+            compile_function(func, prefix, source_text, options, timings, diagnostics).1
+        } else if let Some((class, render, prefix)) = top_level_class_component(stmt) {
+            compile_class(class, render, prefix, source_text, options, timings, diagnostics)
+        } else {
+            continue;
+        };
+        if options.codegen.preserve_comments {
+            output.push_str(&leading_comments(&ret.program.comments, stmt.span(), source_text));
+        }
+        output.push_str(&code);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// The comments attached to the start of a statement, each on its own line.
+fn leading_comments(comments: &[oxc_ast::Comment], span: oxc_span::Span, source_text: &str) -> String {
+    comments
+        .iter()
+        .filter(|comment| comment.is_leading() && comment.attached_to == span.start)
+        .map(|comment| format!("{}\n", comment.span.source_text(source_text)))
+        .collect()
+}
+
+/// Compile one top-level function, returning its name and output code. The
+/// code starts with `prefix`; functions the compiler bails out of are
+/// returned as written.
+fn compile_function(
+    func: &oxc_ast::ast::Function,
+    prefix: &str,
+    source_text: &str,
+    options: &CompileOptions,
+    timings: &mut PhaseTimings,
+    diagnostics: &mut Diagnostics,
+) -> (String, String) {
+    let name = func.id.as_ref().map_or_else(|| "anonymous".to_string(), |id| id.name.to_string());
+    let Some((reactive_func, scope_result)) =
+        build_function(func, LoweringContext::default(), &name, options, timings, diagnostics)
+    else {
+        let code = format!("{}{}\n", prefix, &source_text[func.span.start as usize..func.span.end as usize]);
+        return (name, code);
+    };
+
+    let start = Instant::now();
+    let code = generate_code_with_options(&reactive_func, &scope_result, &options.codegen);
+    timings.codegen += start.elapsed();
+
+    (name, format!("{}{}", prefix, code))
+}
+
+/// Compile a top-level class component, returning it with its `render`
+/// method compiled and every other member as written.
+///
+/// Class components cannot call hooks, so `render` is compiled without the
+/// memo cache.
+fn compile_class(
+    class: &oxc_ast::ast::Class,
+    render: &oxc_ast::ast::MethodDefinition,
+    prefix: &str,
+    source_text: &str,
+    options: &CompileOptions,
+    timings: &mut PhaseTimings,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    let class_name = class.id.as_ref().map_or("anonymous", |id| id.name.as_str());
+    let name = format!("{}.render", class_name);
+    let options = CompileOptions { memoize: false, ..options.clone() };
+    let method_start = render.span.start as usize;
+    let method_end = render.span.end as usize;
+
+    let method = match build_function(&render.value, LoweringContext::for_method(), &name, &options, timings, diagnostics) {
+        Some((reactive_func, scope_result)) => {
+            let start = Instant::now();
+            let code = generate_method_with_options("render", &reactive_func, &scope_result, &options.codegen);
+            timings.codegen += start.elapsed();
 
-            // Phase 3: SSA transformation
-            let ssa_hir = enter_ssa(hir);
+            // Line the generated body up with the method in the class
+            let line_start = source_text[..method_start].rfind('\n').map_or(0, |i| i + 1);
+            let indent = &source_text[line_start..method_start];
+            let indent = if indent.trim().is_empty() { indent } else { "" };
+            code.trim_end().replace('\n', &format!("\n{}", indent))
+        }
+        None => source_text[method_start..method_end].to_string(),
+    };
+
+    format!(
+        "{}{}{}{}\n",
+        prefix,
+        &source_text[class.span.start as usize..method_start],
+        method,
+        &source_text[method_end..class.span.end as usize],
+    )
+}
 
-            // Phase 4: Liveness analysis and scope construction
-            let liveness = infer_liveness(&ssa_hir);
-            let scope_result = construct_reactive_scopes(&ssa_hir, &liveness);
+/// Lower and analyze `func`, reporting problems under `name`. Returns `None`
+/// if the compiler bails out, in which case the function is emitted as
+/// written.
+fn build_function(
+    func: &oxc_ast::ast::Function,
+    ctx: LoweringContext,
+    name: &str,
+    options: &CompileOptions,
+    timings: &mut PhaseTimings,
+    diagnostics: &mut Diagnostics,
+) -> Option<(ReactiveFunction, ReactiveScopeResult)> {
+    // Phase 1-2: Lower AST to HIR
+    let hir = lower_function(func, ctx, name, options, timings, diagnostics)?;
 
-            // Phase 5: Build reactive function tree and generate code
-            let reactive_func = build_reactive_function(&ssa_hir, &scope_result);
-            let code = generate_code(&reactive_func, &scope_result);
+    // Phase 3: SSA transformation
+    let start = Instant::now();
+    let mut ssa_hir = enter_ssa(hir);
+    optimize(&mut ssa_hir);
+    timings.ssa += start.elapsed();
 
-            output.push_str(&code);
-            output.push('\n');
+    // Phase 4: Liveness analysis and scope construction
+    let scope_result = if options.memoize {
+        analyze(&ssa_hir, timings)
+    } else {
+        ReactiveScopeResult::default()
+    };
+    for hook in &scope_result.split_hooks {
+        diagnostics.report(name, CompilerError::HookInScope {
+            name: name.to_string(),
+            hook: hook.clone(),
+        });
+    }
+
+    // Phase 5: Build the reactive function tree
+    let start = Instant::now();
+    let reactive_func = build_reactive_function(&ssa_hir, &scope_result);
+    timings.reactive += start.elapsed();
+    match reactive_func {
+        Ok(reactive_func) => Some((reactive_func, scope_result)),
+        Err(error) => {
+            diagnostics.report(name, error);
+            None
         }
     }
+}
 
-    Ok(output)
+/// Lower `func` to HIR, reporting problems under `name`. Returns `None` if
+/// the function can't be lowered or is one the compiler bails out of: it
+/// uses unsupported syntax, is over `options.max_instructions`, or calls
+/// `eval`.
+fn lower_function(
+    func: &oxc_ast::ast::Function,
+    ctx: LoweringContext,
+    name: &str,
+    options: &CompileOptions,
+    timings: &mut PhaseTimings,
+    diagnostics: &mut Diagnostics,
+) -> Option<HIRFunction> {
+    let start = Instant::now();
+    let ctx = if options.strip_console { ctx.strip_console(&options.keep_console_methods) } else { ctx };
+    let lowered = ctx.build_checked(func);
+    timings.lowering += start.elapsed();
+    let name = name.to_string();
+    let (hir, mut unsupported) = match lowered {
+        Ok(lowered) => lowered,
+        Err(error) => {
+            diagnostics.report(&name, error);
+            return None;
+        }
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    unsupported.retain(|syntax| seen.insert(*syntax));
+    for syntax in &unsupported {
+        diagnostics.report(&name, CompilerError::UnsupportedSyntax {
+            name: name.clone(),
+            syntax: syntax.to_string(),
+        });
+    }
+    if is_component_or_hook(&name) {
+        for param in mutated_params(&hir) {
+            diagnostics.report(&name, CompilerError::PropMutation {
+                name: name.clone(),
+                param,
+            });
+        }
+    }
+
+    // The HIR is missing whatever could not be lowered
+    if !unsupported.is_empty() {
+        return None;
+    }
+
+    // Bail out before the superlinear passes if the function is too large
+    if let Some(limit) = options.max_instructions {
+        let instructions: usize = hir.blocks.values().map(|b| b.instructions.len()).sum();
+        if instructions > limit {
+            diagnostics.report(&name, CompilerError::FunctionTooLarge {
+                name: name.clone(),
+                instructions,
+                limit,
+            });
+            return None;
+        }
+    }
+
+    // `eval` defeats the static model the later phases rely on
+    if calls_eval(&hir) {
+        diagnostics.report(&name, CompilerError::EvalUnsupported { name: name.clone() });
+        return None;
+    }
+
+    Some(hir)
+}
+
+/// The optimization passes run over the SSA form, in pipeline order.
+fn optimize(hir: &mut HIRFunction) {
+    fold_constants(hir);
+    eliminate_common_subexpressions(hir);
+    hoist_loop_invariants(hir);
+    eliminate_dead_phis(hir);
+}
+
+/// Infer the reactive scopes of an optimized SSA function.
+fn analyze(hir: &HIRFunction, timings: &mut PhaseTimings) -> ReactiveScopeResult {
+    let start = Instant::now();
+    let liveness = infer_liveness(hir);
+    timings.liveness += start.elapsed();
+
+    let start = Instant::now();
+    let scope_result = construct_reactive_scopes(hir, &liveness);
+    timings.scopes += start.elapsed();
+    scope_result
+}
+
+/// Components are capitalized and hooks are named `useFoo`.
+fn is_component_or_hook(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase()) || is_hook_name(name)
+}
+
+/// The top-level function declared by `stmt`, if any, along with the text
+/// that has to precede it in the output: the `export` or `export default`
+/// it was declared with.
+///
+/// `export default function() {}` is the only place a function declaration
+/// can be anonymous, so it has to keep its `export default` to stay valid.
+fn top_level_function<'s, 'a>(
+    stmt: &'s oxc_ast::ast::Statement<'a>,
+) -> Option<(&'s oxc_ast::ast::Function<'a>, &'static str)> {
+    use oxc_ast::ast::{Declaration, ExportDefaultDeclarationKind, Statement};
+
+    match stmt {
+        Statement::FunctionDeclaration(func) => Some((func, "")),
+        Statement::ExportNamedDeclaration(export) => match &export.declaration {
+            Some(Declaration::FunctionDeclaration(func)) => Some((func, "export ")),
+            _ => None,
+        },
+        Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => Some((func, "export default ")),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The top-level class declared by `stmt` if it has a `render` method, along
+/// with that method and the text that has to precede the class.
+fn top_level_class_component<'s, 'a>(
+    stmt: &'s oxc_ast::ast::Statement<'a>,
+) -> Option<(&'s oxc_ast::ast::Class<'a>, &'s oxc_ast::ast::MethodDefinition<'a>, &'static str)> {
+    use oxc_ast::ast::{ClassElement, Declaration, ExportDefaultDeclarationKind, MethodDefinitionKind, Statement};
+
+    let (class, prefix) = match stmt {
+        Statement::ClassDeclaration(class) if !class.declare => (class, ""),
+        Statement::ExportNamedDeclaration(export) => match &export.declaration {
+            Some(Declaration::ClassDeclaration(class)) if !class.declare => (class, "export "),
+            _ => return None,
+        },
+        Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+            ExportDefaultDeclarationKind::ClassDeclaration(class) => (class, "export default "),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let render = class.body.body.iter().find_map(|element| match element {
+        ClassElement::MethodDefinition(method)
+            if method.kind == MethodDefinitionKind::Method
+                && !method.r#static
+                && !method.computed
+                && method.key.static_name().as_deref() == Some("render") =>
+        {
+            Some(&**method)
+        }
+        _ => None,
+    })?;
+    Some((class, render, prefix))
+}
+
+/// Report, for each top-level function, whether the compiler would memoize it.
+///
+/// Only components (capitalized names) and hooks (`useFoo`) are candidates,
+/// and a candidate is still skipped if it uses syntax the compiler cannot
+/// lower or calls `eval`. Anonymous functions are reported as `"anonymous"`.
+/// Returns nothing if the source fails to parse.
+pub fn would_compile(source_text: &str, source_type: SourceType) -> Vec<(String, bool)> {
+    let allocator = Allocator::default();
+    let ret = OxcParser::new(&allocator, source_text, source_type).parse();
+    if !ret.errors.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = Vec::new();
+    for stmt in &ret.program.body {
+        if let Some((func, _)) = top_level_function(stmt) {
+            let name = func.id.as_ref().map_or("anonymous", |id| id.name.as_str()).to_string();
+            let compiles = is_component_or_hook(&name)
+                && LoweringContext::default()
+                    .build_checked(func)
+                    .is_ok_and(|(hir, unsupported)| unsupported.is_empty() && !calls_eval(&hir));
+            result.push((name, compiles));
+        }
+    }
+    result
+}
+
+/// A reactive scope chosen by the compiler, with its inputs and outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeInfo {
+    /// Name of the function the scope belongs to.
+    pub function: Option<String>,
+    /// Scope id, unique within its function.
+    pub id: usize,
+    /// Bindings read by the scope but defined before it, sorted and deduplicated.
+    pub dependencies: Vec<String>,
+    /// Bindings the scope defines, sorted and deduplicated.
+    pub declarations: Vec<String>,
+}
+
+/// List the reactive scopes the compiler infers for each top-level function.
+///
+/// Compiler temporaries are resolved to the bindings they were loaded or
+/// computed from; temporaries holding only constants are left out. Returns
+/// nothing if the source fails to parse, and skips functions that
+/// [`compile`] would emit without memoizing, such as ones calling `eval`.
+pub fn analyze_scopes(source_text: &str, source_type: SourceType) -> Vec<ScopeInfo> {
+    use hir::{Identifier, InstructionValue};
+    use std::collections::{BTreeSet, HashMap, HashSet};
+
+    let allocator = Allocator::default();
+    let ret = OxcParser::new(&allocator, source_text, source_type).parse();
+    if !ret.errors.is_empty() {
+        return Vec::new();
+    }
+
+    let options = CompileOptions::default();
+
+    let mut scopes = Vec::new();
+    for stmt in &ret.program.body {
+        if let Some((func, _)) = top_level_function(stmt) {
+            let name = func.id.as_ref().map_or("anonymous", |id| id.name.as_str());
+            let Some(hir) = lower_function(
+                func,
+                LoweringContext::default(),
+                name,
+                &options,
+                &mut PhaseTimings::default(),
+                &mut Diagnostics::new(),
+            ) else {
+                continue;
+            };
+            let mut ssa_hir = enter_ssa(hir);
+            optimize(&mut ssa_hir);
+            let scope_result = analyze(&ssa_hir, &mut PhaseTimings::default());
+
+            let mut defined_by: HashMap<&Identifier, &InstructionValue> = HashMap::new();
+            for block in ssa_hir.blocks.values() {
+                for instr in &block.instructions {
+                    defined_by.insert(&instr.lvalue.identifier, &instr.value);
+                }
+            }
+
+            for scope in &scope_result.scopes {
+                // Follow temporaries back to the bindings they were computed from
+                let mut dependencies = BTreeSet::new();
+                let mut visited = HashSet::new();
+                let mut pending: Vec<Identifier> =
+                    scope.dependencies.iter().map(|dep| dep.place.identifier.clone()).collect();
+                while let Some(id) = pending.pop() {
+                    if !id.temporary {
+                        dependencies.insert(id.name);
+                    } else if let Some(value) = defined_by.get(&id)
+                        && visited.insert(id)
+                    {
+                        pending.extend(get_operand_identifiers(value));
+                    }
+                }
+                let declarations: BTreeSet<String> = scope
+                    .declarations
+                    .iter()
+                    .filter(|decl| !decl.place.identifier.temporary)
+                    .map(|decl| decl.place.identifier.name.clone())
+                    .collect();
+                scopes.push(ScopeInfo {
+                    function: ssa_hir.name.clone(),
+                    id: scope.id.0,
+                    dependencies: dependencies.into_iter().collect(),
+                    declarations: declarations.into_iter().collect(),
+                });
+            }
+        }
+    }
+
+    scopes
+}
+
+/// The dominator tree of one function's control flow graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DominatorInfo {
+    /// Name of the function the graph belongs to.
+    pub function: Option<String>,
+    /// Each reachable block other than the entry, paired with its immediate
+    /// dominator, sorted by block.
+    pub idoms: Vec<(hir::BlockId, hir::BlockId)>,
+    /// Each reachable block paired with its dominance frontier, sorted by
+    /// block. The frontier is where SSA places phis for values the block
+    /// defines.
+    pub frontiers: Vec<(hir::BlockId, Vec<hir::BlockId>)>,
+}
+
+/// Compute the dominator tree of each top-level function, as lowered before
+/// SSA construction. Returns nothing if the source fails to parse, and skips
+/// functions that fail to lower.
+pub fn compute_dominators(source_text: &str, source_type: SourceType) -> Vec<DominatorInfo> {
+    use hir::dominators::DominatorTree;
+    use hir::ssa::compute_predecessors;
+
+    let allocator = Allocator::default();
+    let ret = OxcParser::new(&allocator, source_text, source_type).parse();
+    if !ret.errors.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = Vec::new();
+    for stmt in &ret.program.body {
+        if let Some((func, _)) = top_level_function(stmt) {
+            let Ok((mut hir, _)) = LoweringContext::default().build_checked(func) else {
+                continue;
+            };
+            compute_predecessors(&mut hir);
+            let tree = DominatorTree::compute(&hir);
+            let idoms = tree
+                .idoms
+                .iter()
+                .filter(|&(block, _)| *block != hir.entry_block)
+                .map(|(&block, &idom)| (block, idom))
+                .collect();
+            let frontiers = tree
+                .dominance_frontiers
+                .into_iter()
+                .filter(|(block, _)| tree.idoms.contains_key(block))
+                .map(|(block, frontier)| (block, frontier.into_iter().collect()))
+                .collect();
+            result.push(DominatorInfo {
+                function: hir.name,
+                idoms,
+                frontiers,
+            });
+        }
+    }
+    result
 }
 
 /// Debug function that shows intermediate representations.
+///
+/// Fails with [`CompilerError::ParseError`] if the source does not parse,
+/// like [`compile`].
 pub fn debug_hir(source_text: &str, source_type: SourceType) -> Result<String> {
     let allocator = Allocator::default();
 
@@ -69,56 +627,56 @@ pub fn debug_hir(source_text: &str, source_type: SourceType) -> Result<String> {
         .parse();
 
     if !ret.errors.is_empty() {
-        use std::fmt::Write;
-        let mut err_msg = String::new();
-        writeln!(&mut err_msg, "Parse Errors:").unwrap();
-        for error in ret.errors {
-            writeln!(&mut err_msg, "{:?}", error).unwrap();
-        }
-        return Ok(err_msg);
+        return Err(parse_error(&ret.errors).into());
     }
 
     let mut output = String::new();
 
     for stmt in &ret.program.body {
-        if let oxc_ast::ast::Statement::FunctionDeclaration(func) = stmt {
-             let ctx = LoweringContext::default();
-             let hir = ctx.build(func);
-             let ssa_hir = enter_ssa(hir);
-
-             let liveness = infer_liveness(&ssa_hir);
-             let scope_result = construct_reactive_scopes(&ssa_hir, &liveness);
-
-             use std::fmt::Write;
-             writeln!(&mut output, "=== HIR (SSA) ===").unwrap();
-             write!(&mut output, "{:#?}\n", ssa_hir).unwrap();
-
-             if !scope_result.scopes.is_empty() {
-                 writeln!(&mut output, "\n=== Reactive Scopes ===").unwrap();
-                 for scope in &scope_result.scopes {
-                     writeln!(&mut output, "Scope {:?}: range {:?}", scope.id, scope.range).unwrap();
-                     if !scope.dependencies.is_empty() {
-                         write!(&mut output, "  Dependencies: ").unwrap();
-                         for dep in &scope.dependencies {
-                             write!(&mut output, "{} ", dep.place.identifier.name).unwrap();
-                         }
-                         writeln!(&mut output).unwrap();
-                     }
-                     if !scope.declarations.is_empty() {
-                         write!(&mut output, "  Declarations: ").unwrap();
-                         for decl in &scope.declarations {
-                             write!(&mut output, "{} ", decl.place.identifier.name).unwrap();
-                         }
-                         writeln!(&mut output).unwrap();
-                     }
-                 }
-             }
-
-             // Also show generated code
-             let reactive_func = build_reactive_function(&ssa_hir, &scope_result);
-             let code = generate_code(&reactive_func, &scope_result);
-             writeln!(&mut output, "\n=== Generated Code ===").unwrap();
-             write!(&mut output, "{}", code).unwrap();
+        if let Some((func, _)) = top_level_function(stmt) {
+            use std::fmt::Write;
+
+            let hir = match LoweringContext::default().build_checked(func) {
+                Ok((hir, _)) => hir,
+                Err(error) => {
+                    writeln!(&mut output, "{}", error).unwrap();
+                    continue;
+                }
+            };
+            let mut ssa_hir = enter_ssa(hir);
+            optimize(&mut ssa_hir);
+            let scope_result = analyze(&ssa_hir, &mut PhaseTimings::default());
+
+            writeln!(&mut output, "=== HIR (SSA) ===").unwrap();
+            writeln!(&mut output, "{:#?}", ssa_hir).unwrap();
+
+            if !scope_result.scopes.is_empty() {
+                writeln!(&mut output, "\n=== Reactive Scopes ===").unwrap();
+                for scope in &scope_result.scopes {
+                    writeln!(&mut output, "Scope {:?}: range {:?}", scope.id, scope.range).unwrap();
+                    if !scope.dependencies.is_empty() {
+                        write!(&mut output, "  Dependencies: ").unwrap();
+                        for dep in &scope.dependencies {
+                            write!(&mut output, "{} ", dep.place.identifier.name).unwrap();
+                        }
+                        writeln!(&mut output).unwrap();
+                    }
+                    if !scope.declarations.is_empty() {
+                        write!(&mut output, "  Declarations: ").unwrap();
+                        for decl in &scope.declarations {
+                            write!(&mut output, "{} ", decl.place.identifier.name).unwrap();
+                        }
+                        writeln!(&mut output).unwrap();
+                    }
+                }
+            }
+
+            // Also show generated code
+            writeln!(&mut output, "\n=== Generated Code ===").unwrap();
+            match build_reactive_function(&ssa_hir, &scope_result) {
+                Ok(reactive_func) => write!(&mut output, "{}", generate_code(&reactive_func, &scope_result)).unwrap(),
+                Err(error) => writeln!(&mut output, "{}", error).unwrap(),
+            }
         }
     }
 
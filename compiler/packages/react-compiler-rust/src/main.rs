@@ -1,22 +1,62 @@
 use clap::Parser;
 use miette::{IntoDiagnostic, Result};
 use oxc_span::SourceType;
-use std::path::PathBuf;
-use react_compiler_rust::debug_hir;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use react_compiler_rust::{compile, compile_with_timings, debug_hir};
+
+/// Extensions of the files compiled when `--input` is a directory
+const SOURCE_EXTENSIONS: [&str; 4] = ["js", "jsx", "ts", "tsx"];
 
 /// React Compiler (Rust Edition)
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Input file to compile
+    /// Input file to compile, or a directory to compile recursively
     #[arg(short, long)]
     input: PathBuf,
+
+    /// Where to write the compiled files when `--input` is a directory,
+    /// mirroring its layout
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+
+    /// Print the compiled output followed by the time spent in each phase
+    #[arg(long)]
+    time: bool,
+
+    /// After compiling the `--input` directory, keep recompiling files as
+    /// they change
+    #[arg(long)]
+    watch: bool,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let source_path = args.input;
-    
+
+    if source_path.is_dir() {
+        let Some(out_dir) = args.out_dir else {
+            miette::bail!("--out-dir is required when --input is a directory");
+        };
+        // Absolute paths, so an `out_dir` inside the input can be recognized
+        // and skipped
+        let source_path = source_path.canonicalize().into_diagnostic()?;
+        std::fs::create_dir_all(&out_dir).into_diagnostic()?;
+        let out_dir = out_dir.canonicalize().into_diagnostic()?;
+        let failed = compile_directory(&source_path, &out_dir)?;
+        if args.watch {
+            return watch_directory(&source_path, &out_dir);
+        }
+        if failed > 0 {
+            miette::bail!("{} files failed to compile", failed);
+        }
+        return Ok(());
+    }
+    if args.watch {
+        miette::bail!("--watch needs --input to be a directory");
+    }
+
     let source_text = std::fs::read_to_string(&source_path)
         .into_diagnostic()?;
 
@@ -24,9 +64,130 @@ fn main() -> Result<()> {
 
     let source_type = SourceType::from_path(&source_path).unwrap_or_default();
     
+    if args.time {
+        let (code, timings) = compile_with_timings(&source_text, source_type)?;
+        print!("{}", code);
+        println!("Phase timings:");
+        for (phase, duration) in timings.phases() {
+            println!("  {:<10} {:?}", phase, duration);
+        }
+        println!("  {:<10} {:?}", "total", timings.total());
+        return Ok(());
+    }
+
     let output = debug_hir(&source_text, source_type)?;
     
     println!("{}", output);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Compile every source file under `input_dir` into the same relative path
+/// under `out_dir`, returning how many failed. A file that fails is
+/// reported and skipped. If `out_dir` is inside `input_dir`, the files
+/// already compiled into it are left alone.
+fn compile_directory(input_dir: &Path, out_dir: &Path) -> Result<usize> {
+    let mut sources = Vec::new();
+    collect_sources(input_dir, out_dir, &mut sources).into_diagnostic()?;
+    sources.sort();
+
+    let failed = sources
+        .iter()
+        .filter(|source_path| !compile_and_report(input_dir, out_dir, source_path))
+        .count();
+    println!("{} of {} files compiled", sources.len() - failed, sources.len());
+    Ok(failed)
+}
+
+/// Recompile each source file under `input_dir` whenever it is created or
+/// saved, until the process is stopped. Writes under `out_dir` are ignored,
+/// so an `out_dir` inside the input doesn't trigger compiles of its own.
+#[cfg(feature = "watch")]
+fn watch_directory(input_dir: &Path, out_dir: &Path) -> Result<()> {
+    use notify::{EventKind, RecursiveMode, Watcher};
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+
+    // Event paths are absolute, so match them against the absolute input
+    let input_dir = input_dir.canonicalize().into_diagnostic()?;
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).into_diagnostic()?;
+    watcher.watch(&input_dir, RecursiveMode::Recursive).into_diagnostic()?;
+    println!("Watching: {}", input_dir.display());
+
+    while let Ok(event) = receiver.recv() {
+        // One save can raise several events; let them settle and compile once
+        let mut changed = BTreeSet::new();
+        let mut pending = Some(event);
+        while let Some(event) = pending {
+            match event {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    changed.extend(event.paths);
+                }
+                Ok(_) => {}
+                Err(error) => eprintln!("Error: {}", error),
+            }
+            pending = receiver.recv_timeout(Duration::from_millis(50)).ok();
+        }
+
+        for path in changed {
+            if path.is_file() && is_source_file(&path) && !path.starts_with(out_dir) {
+                compile_and_report(&input_dir, out_dir, &path);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch_directory(_input_dir: &Path, _out_dir: &Path) -> Result<()> {
+    miette::bail!("--watch needs the compiler to be built with the `watch` feature");
+}
+
+/// Compile one file under `input_dir`, printing the outcome and how long
+/// it took. Returns whether it succeeded.
+fn compile_and_report(input_dir: &Path, out_dir: &Path, source_path: &Path) -> bool {
+    let relative = source_path.strip_prefix(input_dir).unwrap_or(source_path);
+    let start = Instant::now();
+    match compile_file(source_path, &out_dir.join(relative)) {
+        Ok(()) => {
+            println!("Compiled: {} ({:?})", relative.display(), start.elapsed());
+            true
+        }
+        Err(error) => {
+            eprintln!("Error: {}: {}", relative.display(), error);
+            false
+        }
+    }
+}
+
+fn compile_file(source_path: &Path, out_path: &Path) -> Result<()> {
+    let source_text = std::fs::read_to_string(source_path).into_diagnostic()?;
+    let source_type = SourceType::from_path(source_path).unwrap_or_default();
+    let code = compile(&source_text, source_type)?;
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent).into_diagnostic()?;
+    }
+    std::fs::write(out_path, code).into_diagnostic()
+}
+
+fn collect_sources(dir: &Path, out_dir: &Path, sources: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path == out_dir {
+            continue;
+        }
+        if path.is_dir() {
+            collect_sources(&path, out_dir, sources)?;
+        } else if is_source_file(&path) {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_source_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
@@ -34,14 +34,7 @@ pub fn compile(source: String) -> CompileResult {
 #[cfg(feature = "napi")]
 #[napi]
 pub fn compile_with_options(source: String, file_type: Option<String>) -> CompileResult {
-    let source_type = match file_type.as_deref() {
-        Some("ts") => SourceType::ts(),
-        Some("tsx") => SourceType::tsx(),
-        Some("jsx") => SourceType::jsx(),
-        _ => SourceType::mjs(),
-    };
-
-    match crate::compile(&source, source_type) {
+    match crate::compile(&source, source_type(file_type.as_deref())) {
         Ok(code) => CompileResult {
             code,
             success: true,
@@ -55,6 +48,75 @@ pub fn compile_with_options(source: String, file_type: Option<String>) -> Compil
     }
 }
 
+/// Map a `fileType` argument ("js", "jsx", "ts", "tsx") to how the source is parsed.
+#[cfg(feature = "napi")]
+fn source_type(file_type: Option<&str>) -> SourceType {
+    match file_type {
+        Some("ts") => SourceType::ts(),
+        Some("tsx") => SourceType::tsx(),
+        Some("jsx") => SourceType::jsx(),
+        _ => SourceType::mjs(),
+    }
+}
+
+/// Milliseconds spent in each compiler phase
+#[cfg_attr(feature = "napi", napi(object))]
+pub struct PhaseTimingsResult {
+    pub lowering: f64,
+    pub ssa: f64,
+    pub liveness: f64,
+    pub scopes: f64,
+    pub reactive: f64,
+    pub codegen: f64,
+}
+
+/// Result from compiling with per-phase timings
+#[cfg_attr(feature = "napi", napi(object))]
+pub struct TimedCompileResult {
+    /// The compiled output code
+    pub code: String,
+    /// Whether compilation was successful
+    pub success: bool,
+    /// Error message if compilation failed
+    pub error: Option<String>,
+    /// Time spent in each phase (absent if compilation failed)
+    pub timings: Option<PhaseTimingsResult>,
+}
+
+/// Compile and report how long each compiler phase took, in milliseconds.
+///
+/// @param source - The source code to compile
+/// @param fileType - File type: "js", "jsx", "ts", "tsx"
+/// @returns TimedCompileResult with compiled code and phase timings
+#[cfg(feature = "napi")]
+#[napi]
+pub fn compile_with_timings(source: String, file_type: Option<String>) -> TimedCompileResult {
+    match crate::compile_with_timings(&source, source_type(file_type.as_deref())) {
+        Ok((code, timings)) => {
+            let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+            TimedCompileResult {
+                code,
+                success: true,
+                error: None,
+                timings: Some(PhaseTimingsResult {
+                    lowering: ms(timings.lowering),
+                    ssa: ms(timings.ssa),
+                    liveness: ms(timings.liveness),
+                    scopes: ms(timings.scopes),
+                    reactive: ms(timings.reactive),
+                    codegen: ms(timings.codegen),
+                }),
+            }
+        }
+        Err(e) => TimedCompileResult {
+            code: String::new(),
+            success: false,
+            error: Some(format!("{}", e)),
+            timings: None,
+        },
+    }
+}
+
 /// Get version information
 #[cfg(feature = "napi")]
 #[napi]
@@ -0,0 +1,37 @@
+//! Compiler configuration.
+
+use crate::codegen::CodegenOptions;
+
+/// Options controlling how functions are compiled.
+#[derive(Debug, Clone)]
+pub struct CompileOptions {
+    /// Maximum number of HIR instructions a function may lower to before the
+    /// compiler bails out of memoizing it. Functions over the limit are emitted
+    /// unchanged and a warning is reported. `None` means no limit.
+    pub max_instructions: Option<usize>,
+    /// Options for the generated output.
+    pub codegen: CodegenOptions,
+    /// Whether to infer reactive scopes and emit the `_c` memo cache. When
+    /// `false`, functions are only lowered and printed back out, which helps
+    /// tell codegen bugs apart from memoization bugs. Defaults to `true`.
+    pub memoize: bool,
+    /// Whether to remove `console.<method>(...)` calls whose result is
+    /// unused, i.e. that are statements of their own. Their arguments are
+    /// not evaluated either. Defaults to `false`.
+    pub strip_console: bool,
+    /// `console` methods whose calls are kept when `strip_console` is on.
+    /// Defaults to `["error"]`.
+    pub keep_console_methods: Vec<String>,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            max_instructions: None,
+            codegen: CodegenOptions::default(),
+            memoize: true,
+            strip_console: false,
+            keep_console_methods: vec!["error".to_string()],
+        }
+    }
+}
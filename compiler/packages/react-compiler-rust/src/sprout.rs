@@ -40,8 +40,21 @@ fn execute_js(code: &str) -> Result<(String, Option<String>), std::io::Error> {
     }
 }
 
-/// Generate runner code that executes the fixture and captures results
-fn generate_runner(fixture_code: &str) -> String {
+/// Mock of React's memo cache function, `_c`, for running compiled code.
+/// Like a component that re-renders, each call site keeps one cache across
+/// calls, filled with the sentinel React uses for empty slots.
+pub const MOCK_MEMO_CACHE: &str = r#"const __memoCaches = new Map();
+function _c(size) {
+    const site = new Error().stack.split("\n")[2];
+    if (!__memoCaches.has(site)) {
+        __memoCaches.set(site, new Array(size).fill(Symbol.for("react.memo_cache_sentinel")));
+    }
+    return __memoCaches.get(site);
+}"#;
+
+/// Generate runner code that executes the fixture entrypoint `runs` times
+/// and captures every result
+fn generate_runner(fixture_code: &str, runs: usize) -> String {
     format!(
         r#"
 {fixture_code}
@@ -50,7 +63,11 @@ fn generate_runner(fixture_code: &str) -> String {
 if (typeof FIXTURE_ENTRYPOINT !== 'undefined') {{
     const {{ fn, params }} = FIXTURE_ENTRYPOINT;
     try {{
-        const result = fn(...params);
+        const results = [];
+        for (let run = 0; run < {runs}; run++) {{
+            results.push(fn(...params));
+        }}
+        const result = results.length === 1 ? results[0] : results;
         console.log(JSON.stringify({{ success: true, result }}));
     }} catch (error) {{
         console.log(JSON.stringify({{ success: false, error: error.message }}));
@@ -64,9 +81,16 @@ if (typeof FIXTURE_ENTRYPOINT !== 'undefined') {{
 
 /// Verify a fixture by comparing original and compiled outputs
 pub fn verify_fixture(original_code: &str, compiled_code: &str) -> SproutResult {
+    verify_fixture_runs(original_code, compiled_code, 1)
+}
+
+/// Verify a fixture by calling its entrypoint `runs` times in each version
+/// and comparing all the results. Compiled code that uses
+/// [`MOCK_MEMO_CACHE`] reads back what earlier runs memoized.
+pub fn verify_fixture_runs(original_code: &str, compiled_code: &str, runs: usize) -> SproutResult {
     // Generate runner code for both versions
-    let original_runner = generate_runner(original_code);
-    let compiled_runner = generate_runner(compiled_code);
+    let original_runner = generate_runner(original_code, runs);
+    let compiled_runner = generate_runner(compiled_code, runs);
 
     // Execute both
     let (original_output, original_error) = execute_js(&original_runner)
@@ -99,6 +123,90 @@ pub fn verify_fixture_file(fixture_path: &Path, compile_fn: impl Fn(&str) -> Str
     verify_fixture(&original_code, &compiled_code)
 }
 
+/// Result of running [`verify_memoization`]
+#[derive(Debug)]
+pub struct MemoizationResult {
+    /// For each value the entrypoint returns, whether calling it again with
+    /// the same `params` returned the same object
+    pub reused: Vec<bool>,
+    /// For each value, whether calling it with `changedParams` returned a
+    /// different object
+    pub recomputed: Vec<bool>,
+    /// Whether the `changedParams` call returned what a call with an empty
+    /// cache returns, i.e. no value was read from another scope's slots
+    pub consistent: bool,
+    pub output: String,
+    pub error: Option<String>,
+}
+
+/// Generate runner code that calls the entrypoint against one persistent
+/// memo cache, then once more against an empty one.
+fn generate_memoization_runner(compiled_code: &str) -> String {
+    format!(
+        r#"
+{MOCK_MEMO_CACHE}
+
+{compiled_code}
+
+const {{ fn, params, changedParams }} = FIXTURE_ENTRYPOINT;
+const values = (result) => (Array.isArray(result) ? result : [result]);
+try {{
+    const first = values(fn(...params));
+    const second = values(fn(...params));
+    const changed = values(fn(...changedParams));
+    __memoCaches.clear();
+    const expected = values(fn(...changedParams));
+    console.log(JSON.stringify({{
+        success: true,
+        reused: first.map((value, i) => value === second[i]),
+        recomputed: second.map((value, i) => value !== changed[i]),
+        consistent: JSON.stringify(changed) === JSON.stringify(expected),
+    }}));
+}} catch (error) {{
+    console.log(JSON.stringify({{ success: false, error: error.message }}));
+}}
+"#
+    )
+}
+
+/// Verify that compiled code reuses and recomputes its memoized values.
+///
+/// `compiled_code` must define `FIXTURE_ENTRYPOINT` with `fn`, `params` and
+/// `changedParams`, but not `_c`, which the runner provides. The entrypoint
+/// is called twice with `params` and then once with `changedParams`; if it
+/// returns an array, each element is checked separately.
+pub fn verify_memoization(compiled_code: &str) -> MemoizationResult {
+    let (output, error) = execute_js(&generate_memoization_runner(compiled_code))
+        .unwrap_or_else(|e| (String::new(), Some(e.to_string())));
+
+    let mut result = MemoizationResult {
+        reused: vec![],
+        recomputed: vec![],
+        consistent: false,
+        output,
+        error,
+    };
+    if result.error.is_some() {
+        return result;
+    }
+    match serde_json::from_str::<serde_json::Value>(result.output.trim()) {
+        Ok(report) if report["success"] == true => {
+            let flags = |key: &str| -> Vec<bool> {
+                report[key]
+                    .as_array()
+                    .map(|values| values.iter().map(|v| v.as_bool() == Some(true)).collect())
+                    .unwrap_or_default()
+            };
+            result.reused = flags("reused");
+            result.recomputed = flags("recomputed");
+            result.consistent = report["consistent"] == true;
+        }
+        Ok(report) => result.error = Some(report["error"].to_string()),
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,4 +294,45 @@ const FIXTURE_ENTRYPOINT = {
         let result = verify_fixture(original, compiled);
         assert!(!result.passed, "Different results should fail");
     }
+
+    #[test]
+    fn test_repeated_runs_share_the_memo_cache() {
+        let original = r#"
+let calls = 0;
+function count() {
+    calls++;
+    return calls;
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: count,
+    params: [],
+};
+"#;
+
+        // Memoizes a value that changes on every call
+        let compiled = format!(
+            r#"{MOCK_MEMO_CACHE}
+let calls = 0;
+function count() {{
+    const $ = _c(1);
+    if ($[0] === Symbol.for("react.memo_cache_sentinel")) {{
+        calls++;
+        $[0] = calls;
+    }}
+    return $[0];
+}}
+
+const FIXTURE_ENTRYPOINT = {{
+    fn: count,
+    params: [],
+}};
+"#
+        );
+
+        assert!(verify_fixture_runs(original, &compiled, 1).passed);
+        let result = verify_fixture_runs(original, &compiled, 3);
+        assert!(!result.passed, "Stale memoized values should fail: {:?}", result);
+        assert!(result.original_output.contains("[1,2,3]"), "{}", result.original_output);
+    }
 }
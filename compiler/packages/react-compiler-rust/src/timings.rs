@@ -0,0 +1,40 @@
+//! Per-phase compile timings.
+
+use std::time::Duration;
+
+/// Wall-clock time spent in each compiler phase, summed over every function
+/// in the compiled source. Parsing is not included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    /// AST to HIR lowering.
+    pub lowering: Duration,
+    /// SSA construction, including the SSA-level optimization passes.
+    pub ssa: Duration,
+    /// Liveness analysis.
+    pub liveness: Duration,
+    /// Reactive scope construction.
+    pub scopes: Duration,
+    /// Conversion of the CFG into a reactive function tree.
+    pub reactive: Duration,
+    /// JavaScript code generation.
+    pub codegen: Duration,
+}
+
+impl PhaseTimings {
+    /// Each phase's name and duration, in pipeline order.
+    pub fn phases(&self) -> [(&'static str, Duration); 6] {
+        [
+            ("lowering", self.lowering),
+            ("ssa", self.ssa),
+            ("liveness", self.liveness),
+            ("scopes", self.scopes),
+            ("reactive", self.reactive),
+            ("codegen", self.codegen),
+        ]
+    }
+
+    /// Time spent across all phases.
+    pub fn total(&self) -> Duration {
+        self.phases().iter().map(|(_, duration)| *duration).sum()
+    }
+}
@@ -0,0 +1,102 @@
+use oxc_span::SourceType;
+use react_compiler_rust::{analyze_scopes, compile};
+
+#[test]
+fn test_analyze_two_scopes() {
+    let scopes = analyze_scopes(
+        r#"
+function Profile(props, theme) {
+    const user = props.user;
+    const name = user.first + " " + user.last;
+    props.onName(name + "!");
+    const style = { color: theme.color };
+    const size = style.color + theme.size;
+    return { style, size };
+}
+"#,
+        SourceType::jsx(),
+    );
+
+    assert_eq!(scopes.len(), 2, "{:#?}", scopes);
+    assert!(scopes.iter().all(|scope| scope.function.as_deref() == Some("Profile")));
+
+    assert_eq!(scopes[0].id, 0);
+    assert_eq!(scopes[0].dependencies, ["props"]);
+    assert_eq!(scopes[0].declarations, ["name", "user"]);
+
+    assert_eq!(scopes[1].id, 2);
+    // `theme.color` is loaded inside the scope, not passed in from before it
+    assert_eq!(scopes[1].dependencies, ["theme"]);
+    assert_eq!(scopes[1].declarations, ["size", "style"]);
+}
+
+#[test]
+fn test_analyze_scopes_parse_error() {
+    assert!(analyze_scopes("function (", SourceType::jsx()).is_empty());
+}
+
+#[test]
+fn test_unread_params_are_not_dependencies() {
+    let source = r#"
+function Component(a, b, c) {
+    const items = [a, a + 1];
+    const label = { text: items };
+    return label;
+}
+"#;
+    let scopes = analyze_scopes(source, SourceType::jsx());
+    assert!(!scopes.is_empty(), "{:#?}", scopes);
+    for scope in &scopes {
+        assert!(scope.dependencies.iter().all(|dep| dep == "a"), "{:#?}", scopes);
+    }
+    assert!(scopes.iter().any(|scope| scope.dependencies == ["a"]), "{:#?}", scopes);
+
+    // Dropping the unread params doesn't shrink the cache
+    let cache = |source: &str| {
+        let output = compile(source, SourceType::jsx()).unwrap();
+        output.lines().find(|line| line.contains("_c(")).unwrap().to_string()
+    };
+    assert_eq!(cache(source), cache(&source.replace("(a, b, c)", "(a)")));
+}
+
+#[test]
+fn test_instanceof_and_in_operands_are_dependencies() {
+    let source = r#"
+function useShape(x, key, obj) {
+    const kind = x instanceof Circle ? "circle" : "other";
+    const shape = { kind, has: key in obj };
+    return shape;
+}
+"#;
+    let scopes = analyze_scopes(source, SourceType::jsx());
+    assert_eq!(scopes.len(), 1, "{:#?}", scopes);
+    assert_eq!(scopes[0].dependencies, ["Circle", "key", "obj", "x"]);
+    assert_eq!(scopes[0].declarations, ["kind", "shape"]);
+}
+
+#[test]
+fn test_jsx_key_and_ref_are_not_dependencies() {
+    let scopes = analyze_scopes(include_str!("../fixtures/jsx_key_in_list.jsx"), SourceType::jsx());
+    let list: Vec<_> = scopes.iter().filter(|scope| scope.function.as_deref() == Some("TodoList")).collect();
+    assert!(!list.is_empty(), "{:#?}", scopes);
+    // `ids` only feeds the keys; `props` is still read for `todos`
+    assert!(list.iter().all(|scope| !scope.dependencies.contains(&"ids".to_string())), "{:#?}", list);
+    assert!(list.iter().any(|scope| scope.dependencies.contains(&"todos".to_string())), "{:#?}", list);
+}
+
+#[test]
+fn test_functions_compile_bails_out_of_have_no_scopes() {
+    let source = r#"
+function Dynamic(props) {
+    const value = { count: props.count };
+    return eval("value");
+}
+function Static(props) {
+    const value = { count: props.count };
+    return value;
+}
+"#;
+    let scopes = analyze_scopes(source, SourceType::jsx());
+    assert!(!scopes.is_empty(), "{:#?}", scopes);
+    assert!(scopes.iter().all(|scope| scope.function.as_deref() == Some("Static")), "{:#?}", scopes);
+}
@@ -0,0 +1,57 @@
+//! Bailout Tests
+//!
+//! Functions the compiler can't model are emitted unchanged with a warning.
+
+use oxc_span::SourceType;
+use react_compiler_rust::{compile_with_options, CompileOptions, CompilerError};
+
+const EVAL_FIXTURE: &str = r#"function Dynamic(props) {
+    const value = props.value;
+    const result = eval("value + 1");
+    return { result, value };
+}
+function Static(props) {
+    const value = props.value;
+    const doubled = value * 2;
+    return { doubled, value };
+}
+"#;
+
+#[test]
+fn eval_call_emits_function_uncompiled() {
+    let output = compile_with_options(EVAL_FIXTURE, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    let dynamic_end = EVAL_FIXTURE.find("function Static").unwrap();
+    let (dynamic, compiled) = output.code.split_at(output.code.find("function Static").unwrap());
+    assert_eq!(dynamic.trim(), EVAL_FIXTURE[..dynamic_end].trim());
+    assert!(!dynamic.contains("_c("), "{}", output.code);
+    // The function without eval is still memoized
+    assert!(compiled.contains("_c("), "{}", output.code);
+
+    assert_eq!(output.warnings.len(), 1);
+    match &output.warnings[0] {
+        CompilerError::EvalUnsupported { name } => assert_eq!(name, "Dynamic"),
+        other => panic!("unexpected warning: {:?}", other),
+    }
+}
+
+#[test]
+fn eval_property_is_not_a_bailout() {
+    let source = "function Safe(props) {\n    const x = props.eval;\n    return props.run(x);\n}\n";
+    let output = compile_with_options(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+}
+
+#[test]
+fn long_flat_sequence_of_ifs_still_compiles() {
+    let mut source = String::from("function Flags(props) {\n    let count = 0;\n");
+    for i in 0..250 {
+        source.push_str(&format!("    if (props.f{}) {{\n        count = count + 1;\n    }}\n", i));
+    }
+    source.push_str("    return { count };\n}\n");
+
+    let output = compile_with_options(&source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+    assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+    assert!(output.code.contains("_c("), "{}", output.code);
+}
@@ -0,0 +1,218 @@
+//! CLI Tests
+//!
+//! Runs the compiled `react-compiler-rust` binary against fixture files.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name)
+}
+
+#[test]
+fn test_time_flag_reports_every_phase() {
+    let output = Command::new(env!("CARGO_BIN_EXE_react-compiler-rust"))
+        .arg("--input")
+        .arg(fixture("if_else.js"))
+        .arg("--time")
+        .output()
+        .expect("Failed to run the CLI");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let report = &stdout[stdout.find("Phase timings:").expect("missing timing report")..];
+    for phase in ["lowering", "ssa", "liveness", "scopes", "reactive", "codegen"] {
+        assert!(report.contains(phase), "missing {} in:\n{}", phase, report);
+    }
+}
+
+#[test]
+fn test_unparsable_input_fails() {
+    let input = tempfile::tempdir().unwrap();
+    let source = input.path().join("Broken.js");
+    std::fs::write(&source, "function Broken( {\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_react-compiler-rust"))
+        .arg("--input")
+        .arg(&source)
+        .output()
+        .expect("Failed to run the CLI");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Parse error"), "{}", stderr);
+}
+
+#[test]
+fn test_directory_input_mirrors_into_out_dir() {
+    let input = tempfile::tempdir().unwrap();
+    let out = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(input.path().join("components/forms")).unwrap();
+    std::fs::write(
+        input.path().join("components/Button.jsx"),
+        "function Button(props) {\n    return <button>{props.label}</button>;\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        input.path().join("components/forms/useField.ts"),
+        "function useField(name: string) {\n    return { name };\n}\n",
+    )
+    .unwrap();
+    std::fs::write(input.path().join("components/Broken.js"), "function Broken( {\n").unwrap();
+    std::fs::write(input.path().join("README.md"), "# not a source file\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_react-compiler-rust"))
+        .arg("--input")
+        .arg(input.path())
+        .arg("--out-dir")
+        .arg(out.path())
+        .output()
+        .expect("Failed to run the CLI");
+
+    // The broken file fails the run, but only after the others are written
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Broken.js"), "{}", stderr);
+
+    let button = std::fs::read_to_string(out.path().join("components/Button.jsx")).unwrap();
+    assert!(button.contains("function Button(props) {"), "{}", button);
+    assert!(button.contains("React.createElement"), "{}", button);
+    let field = std::fs::read_to_string(out.path().join("components/forms/useField.ts")).unwrap();
+    assert!(field.contains("function useField(name) {"), "{}", field);
+    assert!(!out.path().join("components/Broken.js").exists());
+    assert!(!out.path().join("README.md").exists());
+}
+
+#[test]
+fn test_out_dir_inside_input_is_not_recompiled() {
+    let input = tempfile::tempdir().unwrap();
+    std::fs::write(input.path().join("App.js"), "function App(props) {\n    return props.title;\n}\n").unwrap();
+    let out = input.path().join("out");
+
+    // The second run finds the first run's output inside the input
+    for _ in 0..2 {
+        let output = Command::new(env!("CARGO_BIN_EXE_react-compiler-rust"))
+            .arg("--input")
+            .arg(input.path())
+            .arg("--out-dir")
+            .arg(&out)
+            .output()
+            .expect("Failed to run the CLI");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("1 of 1 files compiled"), "{}", stdout);
+    }
+
+    assert!(out.join("App.js").exists());
+    assert!(!out.join("out").exists());
+}
+
+#[test]
+fn test_directory_input_requires_out_dir() {
+    let output = Command::new(env!("CARGO_BIN_EXE_react-compiler-rust"))
+        .arg("--input")
+        .arg(fixture(""))
+        .output()
+        .expect("Failed to run the CLI");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--out-dir"));
+}
+
+/// Kills the child process when dropped, so a failed assertion doesn't leave
+/// a watcher running.
+#[cfg(feature = "watch")]
+struct KillOnDrop(std::process::Child);
+
+#[cfg(feature = "watch")]
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Start `--watch` on `input` and return the process with a receiver for its
+/// stdout lines, once it reports that it is watching.
+#[cfg(feature = "watch")]
+fn spawn_watch(
+    input: &std::path::Path,
+    out: &std::path::Path,
+) -> (KillOnDrop, std::sync::mpsc::Receiver<String>) {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let mut child = KillOnDrop(
+        Command::new(env!("CARGO_BIN_EXE_react-compiler-rust"))
+            .arg("--input")
+            .arg(input)
+            .arg("--out-dir")
+            .arg(out)
+            .arg("--watch")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to run the CLI"),
+    );
+    let (sender, lines) = std::sync::mpsc::channel();
+    let stdout = BufReader::new(child.0.stdout.take().unwrap());
+    std::thread::spawn(move || {
+        for line in stdout.lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    while !lines.recv_timeout(Duration::from_secs(10)).expect("CLI went quiet").starts_with("Watching: ") {}
+    (child, lines)
+}
+
+#[test]
+#[cfg(feature = "watch")]
+fn test_watch_recompiles_changed_files() {
+    use std::time::Duration;
+
+    let input = tempfile::tempdir().unwrap();
+    let out = tempfile::tempdir().unwrap();
+    let source = input.path().join("Greeting.jsx");
+    std::fs::write(&source, "function Greeting(props) {\n    return <p>{props.name}</p>;\n}\n").unwrap();
+
+    let (_child, lines) = spawn_watch(input.path(), out.path());
+    let next_line = || lines.recv_timeout(Duration::from_secs(10)).expect("CLI went quiet");
+
+    std::fs::write(&source, "function Greeting(props) {\n    return <h1>{props.name}</h1>;\n}\n").unwrap();
+    let report = loop {
+        let line = next_line();
+        if line.starts_with("Compiled: Greeting.jsx") {
+            break line;
+        }
+    };
+
+    assert!(report.ends_with(')'), "missing timing in {}", report);
+    let compiled = std::fs::read_to_string(out.path().join("Greeting.jsx")).unwrap();
+    assert!(compiled.contains("\"h1\""), "{}", compiled);
+}
+
+#[test]
+#[cfg(feature = "watch")]
+fn test_watch_ignores_writes_to_nested_out_dir() {
+    use std::time::Duration;
+
+    let input = tempfile::tempdir().unwrap();
+    let out = input.path().join("out");
+    let source = input.path().join("A.js");
+    std::fs::write(&source, "function A(props) {\n    return props.a;\n}\n").unwrap();
+
+    let (_child, lines) = spawn_watch(input.path(), &out);
+
+    std::fs::write(&source, "function A(props) {\n    return props.b;\n}\n").unwrap();
+    let mut compiled = Vec::new();
+    // Collect everything reported until the CLI has been quiet for a while
+    while let Ok(line) = lines.recv_timeout(Duration::from_secs(2)) {
+        if let Some(report) = line.strip_prefix("Compiled: ") {
+            compiled.push(report.to_string());
+        }
+    }
+
+    assert!(!compiled.is_empty(), "the change to A.js was never compiled");
+    assert!(compiled.iter().all(|report| report.starts_with("A.js ")), "{:?}", compiled);
+    assert!(!out.join("out").exists());
+}
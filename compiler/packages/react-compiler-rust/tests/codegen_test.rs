@@ -0,0 +1,477 @@
+//! Codegen Snapshot Tests
+//!
+//! Snapshots the generated JavaScript for each fixture, so codegen changes
+//! show up independently of the HIR dump in `fixtures_test.rs`.
+
+use oxc_span::SourceType;
+use react_compiler_rust::{compile, compile_detailed, compile_with_options, CodegenOptions, CompileOptions, JsxRuntime};
+use std::fs;
+
+#[test]
+fn test_codegen() {
+    insta::glob!("../fixtures", "*.{js,ts}", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let output = compile(&input, source_type).unwrap();
+        insta::assert_snapshot!(output);
+    });
+}
+
+#[test]
+fn test_sprout_codegen() {
+    insta::glob!("sprout", "*.js", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let output = compile(&input, source_type).unwrap();
+        insta::assert_snapshot!(output);
+    });
+}
+
+#[test]
+fn test_empty_object_literals() {
+    let source = include_str!("../fixtures/empty_literals.js");
+    let output = compile(source, SourceType::mjs()).unwrap();
+    assert!(output.contains(" = {};"), "{}", output);
+    assert!(output.contains(" = [];"), "{}", output);
+    assert!(output.contains(" = { items: "), "{}", output);
+}
+
+#[test]
+fn test_jsx_classic_runtime() {
+    insta::glob!("../fixtures", "*.{jsx,tsx}", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let output = compile(&input, source_type).unwrap();
+        insta::assert_snapshot!(output);
+    });
+}
+
+#[test]
+fn test_jsx_automatic_runtime() {
+    let options = CompileOptions {
+        codegen: CodegenOptions {
+            jsx_runtime: JsxRuntime::Automatic,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    insta::glob!("../fixtures", "*.{jsx,tsx}", |path| {
+        let input = fs::read_to_string(path).unwrap();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let output = compile_with_options(&input, source_type, &options).unwrap();
+        insta::assert_snapshot!(output.code);
+    });
+}
+
+#[test]
+fn test_automatic_runtime_passes_key_separately() {
+    let source = r#"
+function Item(props) {
+    return <li key={props.id}>{props.label}</li>;
+}
+"#;
+    let options = CompileOptions {
+        codegen: CodegenOptions {
+            jsx_runtime: JsxRuntime::Automatic,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let output = compile_with_options(source, SourceType::jsx(), &options).unwrap();
+    assert!(
+        output.code.contains("_jsx(t0, { children: t4 }, t2);"),
+        "{}",
+        output.code
+    );
+}
+
+#[test]
+fn test_output_is_deterministic() {
+    // Every `HashMap` gets its own random seed, so repeated runs in one
+    // process still see different iteration orders
+    let source = [
+        include_str!("../fixtures/reactive_scope_basic.js"),
+        include_str!("../fixtures/hook_in_scope.js"),
+        include_str!("../fixtures/loop_invariant.js"),
+        include_str!("sprout/switch_in_loop.js"),
+        include_str!("sprout/nested_destructuring.js"),
+    ]
+    .join("\n");
+    let first = compile(&source, SourceType::mjs()).unwrap();
+    for _ in 0..100 {
+        assert_eq!(compile(&source, SourceType::mjs()).unwrap(), first);
+    }
+}
+
+#[test]
+fn test_pure_annotation_is_preserved() {
+    let source = r#"
+function config(options) {
+    const base = /* @__PURE__ */ makeConfig(options);
+    const merged = /* #__PURE__ */ base.merge(options);
+    const plain = makeConfig(merged);
+    return plain;
+}
+"#;
+    let output = compile(source, SourceType::mjs()).unwrap();
+    assert!(output.contains("= /* @__PURE__ */ t0(t1);"), "{}", output);
+    assert!(output.contains("= /* @__PURE__ */ t4.merge(t5);"), "{}", output);
+    // Unannotated calls stay unannotated
+    assert_eq!(output.matches("/* @__PURE__ */").count(), 2, "{}", output);
+}
+
+#[test]
+fn test_anonymous_default_export_stays_exported() {
+    let source = include_str!("../fixtures/export_default_anonymous.js");
+    let output = compile(source, SourceType::mjs()).unwrap();
+    assert!(output.starts_with("export default function(props) {\n"), "{}", output);
+
+    let allocator = oxc_allocator::Allocator::default();
+    let reparsed = oxc_parser::Parser::new(&allocator, &output, SourceType::mjs()).parse();
+    assert!(reparsed.errors.is_empty(), "{:?}\n{}", reparsed.errors, output);
+}
+
+#[test]
+fn test_mixed_coalesce_and_logical_output_parses() {
+    let source = r#"
+function pick(a, b, c) {
+    const first = (a ?? b) || c;
+    const second = a ?? (b && c);
+    const third = (a || b) ?? c;
+    return [first, second, third];
+}
+"#;
+    let output = compile(source, SourceType::mjs()).unwrap();
+    // Logical operators become branches, so none of them reach the output
+    assert!(!output.contains("??") && !output.contains("||") && !output.contains("&&"), "{}", output);
+
+    let allocator = oxc_allocator::Allocator::default();
+    let reparsed = oxc_parser::Parser::new(&allocator, &output, SourceType::mjs()).parse();
+    assert!(reparsed.errors.is_empty(), "{:?}\n{}", reparsed.errors, output);
+}
+
+#[test]
+fn test_numeric_separators_keep_their_value() {
+    let source = "function separators() {\n    return [1_000_000, 0x1_00, 1_000.5];\n}\n";
+    let output = compile(source, SourceType::mjs()).unwrap();
+    assert!(output.contains("= 1000000;"), "{}", output);
+    assert!(output.contains("= 256;"), "{}", output);
+    assert!(output.contains("= 1000.5;"), "{}", output);
+}
+
+/// A function with `count` chained ternaries followed by `tail` uses of the result.
+fn ternary_chain(count: usize, tail: usize) -> String {
+    let params: Vec<_> = (0..count).map(|i| format!("c{}", i)).collect();
+    let mut chain = String::new();
+    for (i, param) in params.iter().enumerate() {
+        chain.push_str(&format!("{} ? {} : ", param, i));
+    }
+    chain.push_str(&count.to_string());
+    let mut body = format!("    const x0 = {};\n", chain);
+    for i in 1..=tail {
+        body.push_str(&format!("    const x{} = x{} * 2;\n", i, i - 1));
+    }
+    format!("function chain({}) {{\n{}    return x{};\n}}\n", params.join(", "), body, tail)
+}
+
+#[test]
+fn test_ternary_chain_size_is_linear() {
+    let lines = |count| compile(&ternary_chain(count, 3), SourceType::mjs()).unwrap().lines().count();
+
+    // The code after the chain is emitted once, not once per leaf
+    let output = compile(&ternary_chain(4, 3), SourceType::mjs()).unwrap();
+    assert_eq!(output.matches(" * ").count(), 3, "{}", output);
+
+    // Each extra level adds the same number of lines
+    let step = lines(5) - lines(4);
+    for count in 5..12 {
+        assert_eq!(lines(count + 1) - lines(count), step, "{}", ternary_chain(count + 1, 3));
+    }
+}
+
+#[test]
+fn test_sequential_ternaries_do_not_multiply_code() {
+    // Each conditional's join is built once, so the later ones aren't copied
+    // into every combination of the earlier ones' branches
+    let mut body = String::new();
+    for i in 0..16 {
+        body.push_str(&format!("    const x{i} = c{i} ? {i} : -{i};\n"));
+    }
+    let names: Vec<_> = (0..16).map(|i| format!("x{}", i)).collect();
+    let params: Vec<_> = (0..16).map(|i| format!("c{}", i)).collect();
+    let source = format!(
+        "function sequential({}) {{\n{}    return [{}];\n}}\n",
+        params.join(", "),
+        body,
+        names.join(", ")
+    );
+    let output = compile(&source, SourceType::mjs()).unwrap();
+    assert_eq!(output.matches("if (").count(), 16, "{}", output);
+    assert_eq!(output.matches("return ").count(), 1, "{}", output);
+}
+
+#[test]
+fn test_code_after_if_else_is_emitted_once_after_it() {
+    let source = r#"
+function after(a, log) {
+    let x;
+    if (a) {
+        x = 1;
+    } else {
+        x = 2;
+    }
+    log(x);
+    return x;
+}
+"#;
+    let output = compile(source, SourceType::mjs()).unwrap();
+    let calls: Vec<_> = output.lines().filter(|line| !line.contains('=') && line.ends_with(");")).collect();
+    // At function body level, not inside either branch
+    assert_eq!(calls.len(), 1, "{}", output);
+    assert!(calls[0].starts_with("  t") && !calls[0].starts_with("   "), "{}", output);
+    assert_eq!(output.matches("return ").count(), 1, "{}", output);
+}
+
+#[test]
+fn test_typescript_wrappers_lower_to_the_wrapped_expression() {
+    let source = "function strip(y: number | null) {\n    const x = (y as number)!;\n    return x;\n}\n";
+    let (output, diagnostics) =
+        react_compiler_rust::compile_detailed(source, SourceType::ts(), &CompileOptions::default()).unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(output.contains("function strip(y) {"), "{}", output);
+    assert!(output.contains("const t0 = y;\n  x_1 = t0;"), "{}", output);
+    assert!(!output.contains(" as ") && !output.contains('!'), "{}", output);
+}
+
+#[test]
+fn test_type_declarations_are_erased_and_enums_become_objects() {
+    let source = include_str!("../fixtures/interface_component.ts");
+    let (output, diagnostics) =
+        react_compiler_rust::compile_detailed(source, SourceType::ts(), &CompileOptions::default()).unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(output.contains("function Badge(props) {"), "{}", output);
+    for erased in ["interface", "Shown", "declare", "format", "DEFAULT_LABEL"] {
+        assert!(!output.contains(erased), "{} in {}", erased, output);
+    }
+    assert!(output.contains("{ Quiet: t0, Loud: t1, Alert: t2, Custom: t3 }"), "{}", output);
+    assert!(output.contains("const t2 = 11;"), "{}", output);
+}
+
+#[test]
+fn test_non_null_assertion_reads_the_inner_value() {
+    let source = include_str!("../fixtures/non_null_assertion.tsx");
+    let (output, diagnostics) =
+        react_compiler_rust::compile_detailed(source, SourceType::tsx(), &CompileOptions::default()).unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(output.contains("const t3 = data_1;\n  const t4 = t3.items;"), "{}", output);
+    assert!(!output.contains('!'), "{}", output);
+}
+
+#[test]
+fn test_as_const_keeps_the_asserted_literal() {
+    let source = include_str!("../fixtures/as_const.ts");
+    let (output, diagnostics) =
+        react_compiler_rust::compile_detailed(source, SourceType::ts(), &CompileOptions::default()).unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(output.contains("const t3 = [t1, t2];\n  const t4 = { a: t0, sizes: t3 };\n  config_1 = t4;"), "{}", output);
+    assert!(output.contains("const t6 = scale;\n  factor_1 = t6;"), "{}", output);
+    assert!(!output.contains("const;") && !output.contains("<number>"), "{}", output);
+}
+
+#[test]
+fn test_switch_case_labels_are_literals() {
+    let output = compile(include_str!("sprout/switch_case_labels.js"), SourceType::mjs()).unwrap();
+    let labels: Vec<_> = output.lines().map(str::trim).filter(|line| line.starts_with("case ")).collect();
+    assert_eq!(
+        labels,
+        ["case 0: {", "case -1: {", "case \"0\": {", "case null: {", "case true: {"],
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_switch_case_expressions_are_evaluated_in_the_label() {
+    let output = compile(include_str!("sprout/switch_expression_labels.js"), SourceType::mjs()).unwrap();
+    let labels: Vec<_> = output.lines().map(str::trim).filter(|line| line.starts_with("case ")).collect();
+    assert_eq!(
+        labels,
+        ["case t3(t4): {", "case t6.second: {", "case t9 + t10: {", "case t12(t13): {"],
+        "{}",
+        output
+    );
+    // The calls only run when their case is compared
+    assert!(!output.contains("= t3(t4)") && !output.contains("= t12(t13)"), "{}", output);
+}
+
+#[test]
+fn test_functions_without_a_return() {
+    let empty = compile("function Empty() {}\n", SourceType::mjs()).unwrap();
+    assert_eq!(empty.trim_end(), "function Empty() {\n}");
+
+    // Nothing is memoized, so there is no cache
+    let declarations = compile("function Constant() { const x = 1; }\n", SourceType::mjs()).unwrap();
+    assert!(!declarations.contains("_c("), "{}", declarations);
+    assert!(!declarations.contains("return"), "{}", declarations);
+}
+
+#[test]
+fn test_async_and_generator_modifiers_are_independent() {
+    let source = r#"
+async function load(source) {
+    const response = await source.fetch();
+    return response.body;
+}
+
+async function* pages(source) {
+    const first = await source.next();
+    yield first;
+}
+
+function* counter(start) {
+    yield start;
+}
+"#;
+    let output = compile(source, SourceType::mjs()).unwrap();
+    assert!(output.contains("async function load(source) {"), "{}", output);
+    assert!(output.contains("async function* pages(source) {"), "{}", output);
+    assert!(output.contains("\nfunction* counter(start) {"), "{}", output);
+    assert_eq!(output.matches("= await ").count(), 2, "{}", output);
+
+    let allocator = oxc_allocator::Allocator::default();
+    let reparsed = oxc_parser::Parser::new(&allocator, &output, SourceType::mjs()).parse();
+    assert!(reparsed.errors.is_empty(), "{:?}\n{}", reparsed.errors, output);
+}
+
+#[test]
+fn test_object_method_shorthand() {
+    use react_compiler_rust::codegen::generate_code;
+    use react_compiler_rust::hir::reactive_function::{
+        ReactiveFunction, ReactiveInstruction, ReactiveObjectKey, ReactiveObjectProperty, ReactiveStatement,
+        ReactiveValue,
+    };
+    use react_compiler_rust::hir::reactive_scopes::ReactiveScopeResult;
+    use react_compiler_rust::hir::Identifier;
+
+    let id = |name: &str, id: usize| Identifier { name: name.to_string(), id, temporary: false };
+    let temp = |id: usize| Identifier { name: format!("t{}", id), id, temporary: true };
+    let instruction = |lvalue: Identifier, value: ReactiveValue| {
+        ReactiveStatement::Instruction(ReactiveInstruction { lvalue, value, scope: None })
+    };
+    let function = |name: Option<&str>, params: Vec<Identifier>, body: Vec<ReactiveStatement>| ReactiveFunction {
+        name: name.map(str::to_string),
+        params,
+        generator: false,
+        is_async: false,
+        directives: vec![],
+        body,
+    };
+
+    // { prefix, greet(name) { return prefix + name; } }
+    let greet = function(
+        None,
+        vec![id("name", 0)],
+        vec![
+            instruction(
+                temp(0),
+                ReactiveValue::BinaryOp { op: "+".to_string(), left: id("prefix", 0), right: id("name", 0) },
+            ),
+            ReactiveStatement::Return(Some(temp(0))),
+        ],
+    );
+    let greeter = function(
+        Some("makeGreeter"),
+        vec![id("prefix", 0)],
+        vec![
+            instruction(
+                temp(0),
+                ReactiveValue::Object {
+                    properties: vec![
+                        ReactiveObjectProperty::KeyValue {
+                            key: ReactiveObjectKey::Identifier("prefix".to_string()),
+                            value: id("prefix", 0),
+                        },
+                        ReactiveObjectProperty::Method {
+                            key: ReactiveObjectKey::Identifier("greet".to_string()),
+                            function: Box::new(greet),
+                        },
+                    ],
+                },
+            ),
+            ReactiveStatement::Return(Some(temp(0))),
+        ],
+    );
+
+    let output = generate_code(&greeter, &ReactiveScopeResult::default());
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn test_class_component_render_is_compiled() {
+    let source = r#"
+export default class Profile extends React.Component {
+    render() {
+        const user = this.props.user;
+        return <p title={user.name}>{this.state.status}</p>;
+    }
+}
+
+function Label(props) {
+    return this.prefix + props.text;
+}
+"#;
+    let (output, diagnostics) = compile_detailed(source, SourceType::jsx(), &CompileOptions::default()).unwrap();
+    assert!(output.starts_with("export default class Profile extends React.Component {\n    render() {\n"), "{}", output);
+    assert!(output.contains(".props;"), "{}", output);
+    // Class components can't call hooks, so there is no memo cache
+    assert!(!output.contains("_c("), "{}", output);
+
+    // `this` is only lowered inside a class method
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, ["Function `Label` uses unsupported syntax: `this`"]);
+
+    let allocator = oxc_allocator::Allocator::default();
+    let reparsed = oxc_parser::Parser::new(&allocator, &output, SourceType::jsx()).parse();
+    assert!(reparsed.errors.is_empty(), "{:?}\n{}", reparsed.errors, output);
+}
+
+#[test]
+fn test_exported_functions_keep_their_export() {
+    let source = include_str!("../fixtures/exports.js");
+    let output = compile(source, SourceType::mjs()).unwrap();
+
+    let heads: Vec<_> = output.lines().filter(|line| line.contains("function ")).collect();
+    assert_eq!(
+        heads,
+        ["export function Button(props) {", "function helper(value) {", "export default function App(props) {"],
+        "{}",
+        output
+    );
+
+    let allocator = oxc_allocator::Allocator::default();
+    let reparsed = oxc_parser::Parser::new(&allocator, &output, SourceType::mjs()).parse();
+    assert!(reparsed.errors.is_empty(), "{:?}\n{}", reparsed.errors, output);
+}
+
+#[test]
+fn test_variables_named_like_temporaries() {
+    let source = r#"
+function Component(props) {
+  const t1 = props.a + 1;
+  const t0 = props.b ? t1 : 0;
+  return <div>{t0}{t1}</div>;
+}
+"#;
+    let output = compile(source, SourceType::jsx()).unwrap();
+
+    // The source's `t0` and `t1` are variables, not temporaries, so their
+    // assignments are kept and the compiler's own temporaries avoid the names
+    assert!(output.contains("t1_1 = t3;"), "{}", output);
+    assert!(output.contains("t0_1 = t12;"), "{}", output);
+    assert!(!output.contains("const t0 ") && !output.contains("const t1 "), "{}", output);
+    assert!(!output.contains("undefined ="), "{}", output);
+
+    let allocator = oxc_allocator::Allocator::default();
+    let reparsed = oxc_parser::Parser::new(&allocator, &output, SourceType::jsx()).parse();
+    assert!(reparsed.errors.is_empty(), "{:?}\n{}", reparsed.errors, output);
+}
@@ -0,0 +1,42 @@
+use oxc_span::SourceType;
+use react_compiler_rust::{compile, compile_all};
+
+const TWO_FUNCTIONS: &str = r#"
+function Greeting(props) {
+    const message = "Hello, " + props.name;
+    return { message };
+}
+
+export default function(props) {
+    return props.value * 2;
+}
+"#;
+
+#[test]
+fn test_compile_all_keeps_functions_separate() {
+    let result = compile_all(TWO_FUNCTIONS, SourceType::mjs());
+
+    let names: Vec<_> = result.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["Greeting", "anonymous"]);
+
+    let (_, greeting) = &result[0];
+    assert!(greeting.starts_with("function Greeting(props) {"), "{}", greeting);
+    assert!(!greeting.contains("* 2"), "{}", greeting);
+    let (_, default) = &result[1];
+    assert!(default.starts_with("export default function(props) {"), "{}", default);
+    assert!(!default.contains("Hello"), "{}", default);
+}
+
+#[test]
+fn test_compile_all_matches_compile() {
+    let joined: String = compile_all(TWO_FUNCTIONS, SourceType::mjs())
+        .into_iter()
+        .map(|(_, code)| code + "\n")
+        .collect();
+    assert_eq!(joined, compile(TWO_FUNCTIONS, SourceType::mjs()).unwrap());
+}
+
+#[test]
+fn test_compile_all_parse_error() {
+    assert!(compile_all("function (", SourceType::mjs()).is_empty());
+}
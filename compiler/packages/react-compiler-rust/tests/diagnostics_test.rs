@@ -0,0 +1,93 @@
+//! Diagnostics Tests
+//!
+//! Every advisory the pipeline raises comes back through `compile_detailed`.
+
+use oxc_span::SourceType;
+use react_compiler_rust::{compile_detailed, CompileOptions, Diagnostic, Severity};
+
+#[test]
+fn prop_mutation_and_unsupported_syntax_are_both_reported() {
+    let source = r#"function Profile(props) {
+    props.seen = true;
+    const label = () => props.name;
+    return { label };
+}
+"#;
+    let (_, diagnostics) = compile_detailed(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic {
+                severity: Severity::Warning,
+                message: "Function `Profile` uses unsupported syntax: arrow function".to_string(),
+                function: "Profile".to_string(),
+            },
+            Diagnostic {
+                severity: Severity::Warning,
+                message: "Function `Profile` mutates its parameter `props`".to_string(),
+                function: "Profile".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn unsupported_syntax_emits_the_function_as_written() {
+    let source = r#"function Value(props) {
+    if (!props.value) {
+        throw new Error("missing");
+    }
+    const f = (x) => x + 1;
+    return f(props.value);
+}
+"#;
+    let (code, diagnostics) = compile_detailed(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(diagnostics.iter().any(|d| d.message.ends_with("arrow function")), "{:?}", diagnostics);
+    assert_eq!(code.trim(), source.trim());
+}
+
+#[test]
+fn nested_prop_mutation_is_reported_once() {
+    let source = r#"function useStyle(options) {
+    options.style.color = "red";
+    delete options.style.margin;
+    return options.style;
+}
+"#;
+    let (_, diagnostics) = compile_detailed(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].message, "Function `useStyle` mutates its parameter `options`");
+}
+
+#[test]
+fn helpers_may_mutate_their_arguments() {
+    let source = "function reset(state) {\n    state.count = 0;\n    return state;\n}\n";
+    let (_, diagnostics) = compile_detailed(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn local_objects_are_not_prop_mutations() {
+    let source = "function Card(props) {\n    const style = { color: props.color };\n    style.margin = 0;\n    return style;\n}\n";
+    let (_, diagnostics) = compile_detailed(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn compound_assignment_to_unsupported_target_is_reported() {
+    let source = "function Counter(props) {\n    let count = props.start;\n    (count as number) += 1;\n    return count;\n}\n";
+    let (_, diagnostics) = compile_detailed(source, SourceType::ts(), &CompileOptions::default()).unwrap();
+
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Warning && d.message.ends_with("compound assignment to this target")),
+        "{:?}",
+        diagnostics
+    );
+}
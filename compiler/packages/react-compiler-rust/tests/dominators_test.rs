@@ -0,0 +1,44 @@
+use oxc_span::SourceType;
+use react_compiler_rust::compute_dominators;
+use react_compiler_rust::hir::BlockId;
+
+#[test]
+fn test_diamond_idoms_and_frontiers() {
+    let result = compute_dominators(
+        r#"
+function pick(c) {
+    let x;
+    if (c) {
+        x = 1;
+    } else {
+        x = 2;
+    }
+    return x;
+}
+"#,
+        SourceType::mjs(),
+    );
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].function.as_deref(), Some("pick"));
+    // bb0 branches to bb1 and bb2, which join at bb3
+    assert_eq!(
+        result[0].idoms,
+        [(BlockId(1), BlockId(0)), (BlockId(2), BlockId(0)), (BlockId(3), BlockId(0))]
+    );
+    // Both branches assign `x`, so the phi goes in the join block
+    assert_eq!(
+        result[0].frontiers,
+        [
+            (BlockId(0), vec![]),
+            (BlockId(1), vec![BlockId(3)]),
+            (BlockId(2), vec![BlockId(3)]),
+            (BlockId(3), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_compute_dominators_parse_error() {
+    assert!(compute_dominators("function (", SourceType::mjs()).is_empty());
+}
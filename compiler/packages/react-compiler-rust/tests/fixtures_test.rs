@@ -4,7 +4,7 @@ use std::fs;
 
 #[test]
 fn test_fixtures() {
-    insta::glob!("../fixtures", "*.js", |path| {
+    insta::glob!("../fixtures", "*.{js,ts}", |path| {
         let input = fs::read_to_string(path).unwrap();
         let source_type = SourceType::from_path(path).unwrap_or_default();
         let output = debug_hir(&input, source_type).unwrap();
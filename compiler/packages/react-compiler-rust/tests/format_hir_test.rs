@@ -0,0 +1,42 @@
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Statement;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use react_compiler_rust::format_hir;
+use react_compiler_rust::hir::lowering::LoweringContext;
+use react_compiler_rust::hir::ssa::enter_ssa;
+
+fn format_first_function(source: &str) -> String {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+    assert!(ret.errors.is_empty());
+    let func = ret
+        .program
+        .body
+        .iter()
+        .find_map(|stmt| match stmt {
+            Statement::FunctionDeclaration(func) => Some(func),
+            _ => None,
+        })
+        .unwrap();
+    let hir = enter_ssa(LoweringContext::default().build_checked(func).unwrap().0);
+    format_hir(&hir)
+}
+
+#[test]
+fn test_format_hir_if_else() {
+    let output = format_first_function(
+        r#"
+function choose(cond, a) {
+    let x = 0;
+    if (cond) {
+        x = a.value;
+    } else {
+        x = a.other(1);
+    }
+    return x;
+}
+"#,
+    );
+    insta::assert_snapshot!(output);
+}
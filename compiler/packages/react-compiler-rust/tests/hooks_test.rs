@@ -0,0 +1,29 @@
+//! Hook Tests
+//!
+//! Hook calls must stay unconditional, so they never end up inside a memoized block.
+
+use oxc_span::SourceType;
+use react_compiler_rust::{compile_with_options, CompileOptions, CompilerError};
+
+#[test]
+fn hook_inside_scope_is_reported() {
+    let source = include_str!("../fixtures/hook_in_scope.js");
+    let output = compile_with_options(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert_eq!(output.warnings.len(), 1, "{:?}", output.warnings);
+    match &output.warnings[0] {
+        CompilerError::HookInScope { name, hook } => {
+            assert_eq!(name, "ThemedButton");
+            assert_eq!(hook, "useContext");
+        }
+        other => panic!("unexpected warning: {:?}", other),
+    }
+}
+
+#[test]
+fn hook_outside_scopes_is_not_reported() {
+    let source = "function Title(props) {\n    const theme = useContext(ThemeContext);\n    return { color: theme.color };\n}\n";
+    let output = compile_with_options(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(output.warnings.is_empty(), "{:?}", output.warnings);
+}
@@ -0,0 +1,136 @@
+//! Memoization Regression Tests
+//!
+//! Runs functions with several reactive scopes against one persistent memo
+//! cache, checking that each scope reuses its value while its dependencies
+//! are unchanged and recomputes it when they change. Scopes that shared
+//! cache slots would read each other's values here.
+//!
+//! Lowering does not group instructions into scope blocks yet, so the
+//! fixtures are built as reactive functions and go through codegen only.
+
+use react_compiler_rust::codegen::generate_code;
+use react_compiler_rust::hir::reactive_function::{
+    ReactiveArrayElement, ReactiveFunction, ReactiveInstruction, ReactiveObjectKey, ReactiveObjectProperty,
+    ReactiveStatement, ReactiveValue,
+};
+use react_compiler_rust::hir::reactive_scopes::ReactiveScopeResult;
+use react_compiler_rust::hir::scope::{Declaration, Dependency, ReactiveScope, ScopeId};
+use react_compiler_rust::hir::{Identifier, Place};
+use react_compiler_rust::sprout::verify_memoization;
+
+fn id(name: &str) -> Identifier {
+    // Params keep id 0; values the scopes declare are version 1
+    let version = if name.len() == 1 { 0 } else { 1 };
+    Identifier { name: name.to_string(), id: version, temporary: false }
+}
+
+fn array(elements: &[&str]) -> ReactiveValue {
+    ReactiveValue::Array {
+        elements: elements.iter().map(|name| ReactiveArrayElement::Regular(id(name))).collect(),
+    }
+}
+
+/// A multi-scope function: each `(dependencies, declaration, value)` is one
+/// scope, and the function returns every declaration in an array.
+struct Fixture {
+    params: Vec<&'static str>,
+    scopes: Vec<(Vec<&'static str>, &'static str, ReactiveValue)>,
+}
+
+impl Fixture {
+    fn compile(self, entrypoint: &str) -> String {
+        let mut scopes = vec![];
+        let mut body = vec![];
+        let mut offset = 0;
+        let mut results = vec![];
+        for (n, (dependencies, declaration, value)) in self.scopes.into_iter().enumerate() {
+            let scope = ReactiveScope {
+                id: ScopeId(n),
+                range: (0, 0),
+                dependencies: dependencies
+                    .iter()
+                    .map(|dep| Dependency { place: Place { identifier: id(dep) } })
+                    .collect(),
+                declarations: vec![Declaration { place: Place { identifier: id(declaration) } }],
+                cache_offset: offset,
+            };
+            offset += scope.cache_slots();
+            scopes.push(scope);
+            body.push(ReactiveStatement::Scope {
+                id: ScopeId(n),
+                dependencies: dependencies.iter().map(|dep| id(dep)).collect(),
+                declarations: vec![id(declaration)],
+                body: vec![ReactiveStatement::Instruction(ReactiveInstruction {
+                    lvalue: id(declaration),
+                    value,
+                    scope: Some(ScopeId(n)),
+                })],
+            });
+            results.push(declaration);
+        }
+        body.push(ReactiveStatement::Instruction(ReactiveInstruction {
+            lvalue: id("t0"),
+            value: array(&results),
+            scope: None,
+        }));
+        body.push(ReactiveStatement::Return(Some(id("t0"))));
+
+        let func = ReactiveFunction {
+            name: Some("f".to_string()),
+            params: self.params.iter().map(|param| id(param)).collect(),
+            generator: false,
+            is_async: false,
+            directives: vec![],
+            body,
+        };
+        let scopes = ReactiveScopeResult { scopes, ..Default::default() };
+        format!("{}\nconst FIXTURE_ENTRYPOINT = {};\n", generate_code(&func, &scopes), entrypoint)
+    }
+}
+
+#[test]
+fn test_independent_scopes_keep_their_own_values() {
+    let code = Fixture {
+        params: vec!["a", "b"],
+        scopes: vec![
+            (vec!["a"], "first", array(&["a"])),
+            (vec!["b"], "second", array(&["b"])),
+        ],
+    }
+    .compile("{ fn: f, params: [1, 2], changedParams: [1, 3] }");
+
+    let result = verify_memoization(&code);
+    assert!(result.error.is_none(), "{:?}\n{}", result.error, code);
+    assert_eq!(result.reused, [true, true], "{}", code);
+    assert_eq!(result.recomputed, [false, true], "{}", code);
+    assert!(result.consistent, "{}", code);
+}
+
+#[test]
+fn test_scopes_with_different_slot_counts() {
+    let pair = ReactiveValue::Object {
+        properties: ["a", "b"]
+            .iter()
+            .map(|name| ReactiveObjectProperty::KeyValue {
+                key: ReactiveObjectKey::Identifier(name.to_string()),
+                value: id(name),
+            })
+            .collect(),
+    };
+    // The last scope has no dependencies and is computed once
+    let code = Fixture {
+        params: vec!["a", "b", "c"],
+        scopes: vec![
+            (vec!["a", "b"], "pair", pair),
+            (vec!["c"], "single", array(&["c"])),
+            (vec![], "constant", array(&[])),
+        ],
+    }
+    .compile("{ fn: f, params: [1, 2, 3], changedParams: [1, 5, 3] }");
+
+    let result = verify_memoization(&code);
+    assert!(result.error.is_none(), "{:?}\n{}", result.error, code);
+    assert_eq!(result.reused, [true, true, true], "{}", code);
+    assert_eq!(result.recomputed, [true, false, false], "{}", code);
+    assert!(result.consistent, "{}", code);
+}
@@ -0,0 +1,223 @@
+//! Compile Options Tests
+//!
+//! Tests behavior controlled by `CompileOptions`.
+
+use oxc_span::SourceType;
+use react_compiler_rust::{compile_with_options, CodegenOptions, CompileOptions, CompilerError};
+
+/// Build a function with `count` accumulating statements.
+fn large_function(count: usize) -> String {
+    let mut source = String::from("function Large(props) {\n    let x = props.start;\n");
+    for i in 0..count {
+        source.push_str(&format!("    x = x + {};\n", i));
+    }
+    source.push_str("    return x;\n}\n");
+    source
+}
+
+#[test]
+fn max_instructions_under_limit_compiles() {
+    let source = large_function(10);
+    let options = CompileOptions {
+        max_instructions: Some(1000),
+        ..Default::default()
+    };
+    let output = compile_with_options(&source, SourceType::mjs(), &options).unwrap();
+
+    assert!(output.warnings.is_empty());
+    assert!(output.code.contains("const t0"), "{}", output.code);
+}
+
+#[test]
+fn max_instructions_over_limit_emits_uncompiled() {
+    let source = large_function(500);
+    let options = CompileOptions {
+        max_instructions: Some(1000),
+        ..Default::default()
+    };
+    let output = compile_with_options(&source, SourceType::mjs(), &options).unwrap();
+
+    assert_eq!(output.code.trim(), source.trim());
+    assert!(!output.code.contains("_c("));
+    assert_eq!(output.warnings.len(), 1);
+    match &output.warnings[0] {
+        CompilerError::FunctionTooLarge { name, instructions, limit } => {
+            assert_eq!(name, "Large");
+            assert!(*instructions > 1000);
+            assert_eq!(*limit, 1000);
+        }
+        other => panic!("unexpected warning: {:?}", other),
+    }
+}
+
+#[test]
+fn max_instructions_defaults_to_unlimited() {
+    let source = large_function(500);
+    let output = compile_with_options(&source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(output.warnings.is_empty());
+    assert_ne!(output.code.trim(), source.trim());
+}
+
+#[test]
+fn runtime_import_is_emitted_once_at_top() {
+    let source = "function A(props) {\n    const x = props.a;\n    return { x };\n}\nfunction B(props) {\n    const y = props.b;\n    return { y };\n}\n";
+    let options = CompileOptions {
+        codegen: CodegenOptions {
+            emit_runtime_import: Some("react/compiler-runtime".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let output = compile_with_options(source, SourceType::mjs(), &options).unwrap();
+
+    let import = "import { c as _c } from \"react/compiler-runtime\";";
+    assert_eq!(output.code.lines().next(), Some(import), "{}", output.code);
+    assert_eq!(output.code.matches(import).count(), 1, "{}", output.code);
+}
+
+#[test]
+fn runtime_import_is_off_by_default() {
+    let source = "function A(props) {\n    const x = props.a;\n    return { x };\n}\n";
+    let output = compile_with_options(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+
+    assert!(!output.code.contains("import"), "{}", output.code);
+}
+
+#[test]
+fn memoize_false_emits_no_cache() {
+    let source = "function Counter(props) {\n    const doubled = props.count * 2;\n    if (doubled > 10) {\n        return { label: \"big\", doubled };\n    }\n    return { label: \"small\", doubled };\n}\n";
+    let options = CompileOptions {
+        memoize: false,
+        ..Default::default()
+    };
+    let output = compile_with_options(source, SourceType::mjs(), &options).unwrap();
+
+    assert!(!output.code.contains("_c("), "{}", output.code);
+    assert!(!output.code.contains("$["), "{}", output.code);
+    assert!(output.code.contains("if ("), "{}", output.code);
+    assert!(output.code.contains("\"big\""), "{}", output.code);
+
+    // Apart from the cache, the memoized output runs the same logic
+    let memoized = compile_with_options(source, SourceType::mjs(), &CompileOptions::default()).unwrap();
+    assert!(memoized.code.contains("_c("), "{}", memoized.code);
+    let without_cache: Vec<_> = memoized.code.lines().filter(|line| !line.contains("_c(")).collect();
+    let plain: Vec<_> = output.code.lines().collect();
+    assert_eq!(plain, without_cache);
+}
+
+#[test]
+fn preserve_directives_keeps_module_and_function_directives() {
+    let source = include_str!("../fixtures/use_client.jsx");
+    let options = CompileOptions {
+        codegen: CodegenOptions {
+            emit_runtime_import: Some("react/compiler-runtime".to_string()),
+            preserve_directives: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let output = compile_with_options(source, SourceType::jsx(), &options).unwrap();
+
+    // Before the runtime import, which would otherwise end the prologue
+    let mut lines = output.code.lines();
+    assert_eq!(lines.next(), Some("\"use client\";"), "{}", output.code);
+    assert!(lines.next().unwrap().starts_with("import "), "{}", output.code);
+    assert!(output.code.contains("function save(data) {\n  \"use server\";\n"), "{}", output.code);
+}
+
+#[test]
+fn directives_are_dropped_by_default() {
+    let source = include_str!("../fixtures/use_client.jsx");
+    let output = compile_with_options(source, SourceType::jsx(), &CompileOptions::default()).unwrap();
+
+    assert!(!output.code.contains("use client"), "{}", output.code);
+    assert!(!output.code.contains("use server"), "{}", output.code);
+}
+
+const CONSOLE_CALLS: &str = r#"function report(x) {
+    console.log("value", x.compute());
+    console.debug(x);
+    console.error(x);
+    const logged = console.log(x);
+    return logged;
+}
+"#;
+
+#[test]
+fn strip_console_removes_unused_console_calls() {
+    let options = CompileOptions {
+        strip_console: true,
+        ..Default::default()
+    };
+    let output = compile_with_options(CONSOLE_CALLS, SourceType::mjs(), &options).unwrap();
+
+    // Arguments of a removed call are not evaluated
+    assert!(!output.code.contains(".debug("), "{}", output.code);
+    assert!(!output.code.contains(".compute("), "{}", output.code);
+    assert!(!output.code.contains("\"value\""), "{}", output.code);
+    assert!(output.code.contains(".error("), "{}", output.code);
+    // A call whose result is used stays
+    assert_eq!(output.code.matches(".log(").count(), 1, "{}", output.code);
+}
+
+#[test]
+fn strip_console_keeps_configured_methods() {
+    let options = CompileOptions {
+        strip_console: true,
+        keep_console_methods: vec!["debug".to_string()],
+        ..Default::default()
+    };
+    let output = compile_with_options(CONSOLE_CALLS, SourceType::mjs(), &options).unwrap();
+
+    assert!(output.code.contains(".debug("), "{}", output.code);
+    assert!(!output.code.contains(".error("), "{}", output.code);
+
+    let unstripped = compile_with_options(CONSOLE_CALLS, SourceType::mjs(), &CompileOptions::default()).unwrap();
+    assert_eq!(unstripped.code.matches(".log(").count(), 2, "{}", unstripped.code);
+    assert!(unstripped.code.contains(".debug("), "{}", unstripped.code);
+}
+
+const DOCUMENTED: &str = r#"// Not attached: a statement comes between
+const unrelated = 1;
+
+/**
+ * Shows a greeting.
+ * @param {{ name: string }} props
+ */
+function Greeting(props) {
+  // Dropped: not above a function
+  return <p>{props.name}</p>;
+}
+
+// Line comment
+/* block */ export default function App(props) {
+  return <Greeting name={props.name} />;
+}
+"#;
+
+#[test]
+fn preserve_comments_keeps_comments_above_functions() {
+    let options = CompileOptions {
+        codegen: CodegenOptions {
+            preserve_comments: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let output = compile_with_options(DOCUMENTED, SourceType::jsx(), &options).unwrap();
+
+    let jsdoc = "/**\n * Shows a greeting.\n * @param {{ name: string }} props\n */\nfunction Greeting(props) {\n";
+    assert!(output.code.starts_with(jsdoc), "{}", output.code);
+    assert!(output.code.contains("// Line comment\n/* block */\nexport default function App(props) {"), "{}", output.code);
+    assert!(!output.code.contains("Not attached"), "{}", output.code);
+    assert!(!output.code.contains("Dropped"), "{}", output.code);
+}
+
+#[test]
+fn comments_are_dropped_by_default() {
+    let output = compile_with_options(DOCUMENTED, SourceType::jsx(), &CompileOptions::default()).unwrap();
+
+    assert!(!output.code.contains("@param"), "{}", output.code);
+    assert!(!output.code.contains("//"), "{}", output.code);
+}
@@ -0,0 +1,34 @@
+// Sprout Test: The `arguments` object
+// Tests variadic functions that read `arguments` directly
+
+function sum() {
+    let total = 0;
+    for (let i = 0; i < arguments.length; i++) {
+        total += arguments[i];
+    }
+    return total;
+}
+
+function describeArgs(first) {
+    const rest = [];
+    for (let i = 1; i < arguments.length; i++) {
+        rest.push(arguments[i]);
+    }
+    return { first, count: arguments.length, rest };
+}
+
+function main() {
+    return {
+        none: sum(),
+        one: sum(5),
+        three: sum(1, 2, 3),
+        many: sum(1, 2, 3, 4, 5, 6, 7, 8),
+        describeOne: describeArgs("a"),
+        describeThree: describeArgs("a", "b", "c"),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: main,
+    params: [],
+};
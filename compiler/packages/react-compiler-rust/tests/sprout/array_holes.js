@@ -0,0 +1,25 @@
+// Sprout Test: Array literal holes (elisions)
+// Tests that leading, middle, and trailing holes keep length and element presence
+
+function describe(arr) {
+    const present = [];
+    for (let i = 0; i < arr.length; i++) {
+        present.push(i in arr);
+    }
+    return { length: arr.length, present };
+}
+
+function holes(x) {
+    return {
+        middle: describe([1, , 3]),
+        leading: describe([, , x]),
+        trailing: describe([x, ,]),
+        single: describe([,]),
+        empty: describe([]),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: holes,
+    params: [7],
+};
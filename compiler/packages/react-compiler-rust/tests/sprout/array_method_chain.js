@@ -0,0 +1,30 @@
+// Sprout Test: chained array methods
+// Tests that a map/filter/reduce chain over a prop computes the same
+// values, with the chain read from props in one memoized scope
+
+function LargeDoubles(props) {
+    const values = props.items.map(double).filter(isLarge);
+    const total = values.reduce(add, 0);
+    return { values, total, first: values.find(isLarge) ?? null };
+}
+
+function double(value) {
+    return value * 2;
+}
+
+function isLarge(value) {
+    return value > 4;
+}
+
+function add(sum, value) {
+    return sum + value;
+}
+
+function arrayMethodChain(items) {
+    return [LargeDoubles({ items }), LargeDoubles({ items: items.slice(1) }), LargeDoubles({ items: [] })];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: arrayMethodChain,
+    params: [[1, 2, 3, 4, 5]],
+};
@@ -0,0 +1,42 @@
+// Sprout Test: Assignment expressions used as loop/if conditions
+// Tests that the assignment in the test is re-evaluated on every iteration
+
+function decrement(state) {
+    state.n = state.n - 1;
+    return state.n;
+}
+
+function drain(start) {
+    const state = { n: start };
+    const seen = [];
+    let x;
+    while ((x = decrement(state))) {
+        seen.push(x);
+    }
+    return { seen, last: x, n: state.n };
+}
+
+function firstTruthy(items) {
+    let i = 0;
+    let found = null;
+    if ((found = items[i])) {
+        return found;
+    }
+    while (!(found = items[++i])) {
+    }
+    return found;
+}
+
+function main() {
+    return {
+        drain5: drain(5),
+        drain1: drain(1),
+        first: firstTruthy([0, "", null, "x", "y"]),
+        firstImmediate: firstTruthy(["a"]),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: main,
+    params: [],
+};
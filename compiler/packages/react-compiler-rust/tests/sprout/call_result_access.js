@@ -0,0 +1,34 @@
+// Sprout Test: Property access on call results
+// Tests that each call expression runs exactly once, even when only a property is read
+
+function makeObj(counter) {
+    counter.calls = counter.calls + 1;
+    return { a: counter.calls, b: counter.calls * 10 };
+}
+
+function callResultAccess(start) {
+    const twice = { calls: start };
+    const separate = makeObj(twice).a + makeObj(twice).b;
+
+    const once = { calls: start };
+    const o = makeObj(once);
+    const shared = o.a + o.b;
+
+    const computed = { calls: start };
+    const key = "b";
+    const fromComputed = makeObj(computed)[key];
+
+    return {
+        separate,
+        twiceCalls: twice.calls,
+        shared,
+        onceCalls: once.calls,
+        fromComputed,
+        computedCalls: computed.calls,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: callResultAccess,
+    params: [0],
+};
@@ -0,0 +1,43 @@
+// Sprout Test: Evaluation order of member assignment targets
+// Tests that object, key, and value are each evaluated once, in JS order
+
+function track(log, label, value) {
+    log.push(label);
+    return value;
+}
+
+function sideEffectKey() {
+    const arr = [0, 0, 0];
+    let i = 0;
+    arr[i++] = 10;
+    arr[i++] = 20;
+    return { arr, i };
+}
+
+function orderOfEvaluation() {
+    const log = [];
+    const target = {};
+    track(log, "object", target)[track(log, "key", "k")] = track(log, "value", 1);
+    track(log, "object2", target).s = track(log, "value2", 2);
+    return { log, target };
+}
+
+function compoundOrder() {
+    const log = [];
+    const counts = { a: 1 };
+    track(log, "object", counts)[track(log, "key", "a")] += track(log, "value", 5);
+    return { log, counts };
+}
+
+function main() {
+    return {
+        key: sideEffectKey(),
+        order: orderOfEvaluation(),
+        compound: compoundOrder(),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: main,
+    params: [],
+};
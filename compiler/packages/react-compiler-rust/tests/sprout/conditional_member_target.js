@@ -0,0 +1,30 @@
+// Sprout Test: Assignment to a property of a ternary-selected object
+// Tests that only the selected object is mutated, and that the condition is
+// evaluated once per assignment
+
+function check(log, label, cond) {
+    log.push(label);
+    return cond;
+}
+
+function assignSelected(cond, a, b, key, value) {
+    const log = [];
+    (check(log, "plain", cond) ? a : b).x = value;
+    (check(log, "computed", cond) ? b : a)[key] = value;
+    (check(log, "compound", cond) ? a : b).total += value;
+    (check(log, "nested", cond) ? a : cond === null ? b : a)[key] += 1;
+    return { a, b, log };
+}
+
+function conditionalMemberTarget() {
+    return [
+        assignSelected(true, { total: 1 }, { total: 10 }, "k", 5),
+        assignSelected(false, { total: 1 }, { total: 10 }, "k", 7),
+        assignSelected(null, { total: 1, k: 2 }, { total: 10, k: 20 }, "k", 3),
+    ];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: conditionalMemberTarget,
+    params: [],
+};
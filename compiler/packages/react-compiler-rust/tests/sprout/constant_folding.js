@@ -0,0 +1,29 @@
+// Sprout Test: Constant folding edge cases
+// Tests that folded arithmetic keeps JS number semantics (NaN, Infinity, -0)
+
+function constantFolding(x) {
+    const negZero = -1 * 0;
+    const negLiteral = -0;
+    const nan = 0 / 0;
+    const inf = 1 / 0;
+    const negInf = -1 / 0;
+    const remainder = -5 % 3;
+    const limit = 2 * 3 + 1;
+    return {
+        negZero: Object.is(-0, negZero),
+        negLiteral: Object.is(-0, negLiteral),
+        divByNegZero: 1 / negZero,
+        nan: Number.isNaN(nan),
+        nanEquals: nan === nan,
+        nanNotEquals: nan !== nan,
+        inf: inf === Infinity,
+        negInf: negInf === -Infinity,
+        remainder,
+        belowLimit: x < limit,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: constantFolding,
+    params: [4],
+};
@@ -0,0 +1,25 @@
+// Sprout Test: delete on computed members
+// Tests that `delete obj[key]` removes the dynamically-keyed property
+
+function deleteComputed(key, index) {
+    const obj = { a: 1, b: 2, c: 3 };
+    const removed = delete obj[key];
+    const list = [10, 20, 30];
+    delete list[index + 1];
+    const nested = { inner: { x: 1, y: 2 } };
+    delete nested.inner["x"];
+    delete nested.inner.y;
+    return {
+        removed,
+        keys: Object.keys(obj),
+        hasKey: key in obj,
+        hasIndex: (index + 1) in list,
+        length: list.length,
+        inner: nested.inner,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: deleteComputed,
+    params: ["b", 0],
+};
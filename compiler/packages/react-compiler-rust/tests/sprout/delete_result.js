@@ -0,0 +1,28 @@
+// Sprout Test: Using the result of `delete`
+// Tests that `delete` evaluates to a boolean that can be stored and returned.
+// Fixtures run as modules, where deleting a non-configurable property throws
+// instead of returning false, so every delete here succeeds.
+
+function deleteResult(obj, key) {
+    const removed = delete obj.a;
+    const missing = delete obj.notThere;
+    const computed = delete obj[key];
+    const list = [1, 2, 3];
+    const element = delete list[1];
+    const both = (delete obj.c) && (delete obj.c);
+    return {
+        removed,
+        missing,
+        computed,
+        element,
+        both,
+        types: [typeof removed, typeof computed],
+        remaining: Object.keys(obj),
+        hole: 1 in list,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: deleteResult,
+    params: [{ a: 1, b: 2, c: 3, d: 4 }, "b"],
+};
@@ -0,0 +1,30 @@
+// Sprout Test: Defaults in destructuring assignments
+// Tests that `[a = 1, b] = arr` applies the default only for `undefined`
+
+function fallback(log, value) {
+    log.push(value);
+    return value;
+}
+
+function destructuringDefaults(short) {
+    const log = [];
+    let a;
+    let b;
+    let c;
+    [a = 1, b, c = fallback(log, "c")] = short;
+
+    let present;
+    let nullish;
+    [present = fallback(log, "present"), nullish = 2] = [0, null];
+
+    let x;
+    let renamed;
+    ({ x = 10, y: renamed = 20 } = { y: undefined });
+
+    return { a, b, c, present, nullish, x, renamed, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: destructuringDefaults,
+    params: [[undefined, 5]],
+};
@@ -0,0 +1,19 @@
+// Sprout Test: Functions without a return
+// Tests that an empty body and a body with only declarations compile to
+// functions that still return undefined
+
+function Empty() {}
+
+function DeclarationsOnly(props) {
+    const greeting = "Hello, " + props.name;
+    const style = { color: props.color, greeting };
+}
+
+function emptyBodies(props) {
+    return [Empty(), DeclarationsOnly(props), typeof Empty()];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: emptyBodies,
+    params: [{ name: "Ada", color: "red" }],
+};
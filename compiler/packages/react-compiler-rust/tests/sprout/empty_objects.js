@@ -0,0 +1,21 @@
+// Sprout Test: Empty object literals
+// Tests that `{}` compiles to a fresh, empty object each time, including nested ones
+
+function emptyObjects(flag) {
+    const empty = {};
+    const nested = { inner: {}, list: [{}] };
+    const other = {};
+    empty.added = flag;
+    return {
+        empty,
+        nested,
+        keys: Object.keys(other).length,
+        distinct: empty !== other && nested.inner !== nested.list[0],
+        plain: Object.getPrototypeOf(other) === Object.prototype,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: emptyObjects,
+    params: [true],
+};
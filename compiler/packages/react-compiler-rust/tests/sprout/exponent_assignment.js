@@ -0,0 +1,22 @@
+// Sprout Test: Exponentiation and `**=`
+// Tests that `x **= n` matches `x = x ** n`, including on members
+
+function exponentAssignment(base) {
+    let x = base;
+    x **= 3;
+    let y = base;
+    y = y ** 3;
+    const box = { value: base, list: [2] };
+    box.value **= 2;
+    box.list[0] **= 10;
+    const chained = 2 ** 3 ** 2;
+    const negative = (-2) ** 3;
+    let fraction = 9;
+    fraction **= 0.5;
+    return { x, y, same: x === y, box, chained, negative, fraction };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: exponentAssignment,
+    params: [4],
+};
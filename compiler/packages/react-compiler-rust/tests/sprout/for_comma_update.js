@@ -0,0 +1,22 @@
+// Sprout Test: Comma-separated for-loop update
+// Tests that every expression in the update runs on each iteration, and
+// that a comma expression's value is its last part
+
+function forCommaUpdate(items) {
+    const pairs = [];
+    for (let i = 0, j = items.length - 1; i < j; i++, j--) {
+        pairs.push([items[i], items[j]]);
+    }
+    let steps = 0;
+    let total = 0;
+    for (let k = 0; k < items.length; k += 2, steps++) {
+        total += items[k];
+    }
+    const last = (steps++, total * 2);
+    return [pairs, steps, total, last];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: forCommaUpdate,
+    params: [[1, 2, 3, 4, 5]],
+};
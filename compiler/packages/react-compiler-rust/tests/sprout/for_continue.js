@@ -0,0 +1,28 @@
+// Sprout Test: `continue` inside a for-loop
+// Tests that `continue` still runs the loop's update, so the counter advances
+
+function forContinue(limit) {
+    let odds = 0;
+    let visited = 0;
+    for (let i = 0; i < limit; i++) {
+        visited = visited + 1;
+        if (i % 2 === 0) {
+            continue;
+        }
+        odds = odds + i;
+    }
+    let skipped = 0;
+    for (let j = 10; j > 0; j -= 3) {
+        if (j > 5) {
+            skipped = skipped + 1;
+            continue;
+        }
+        skipped = skipped + 100;
+    }
+    return { odds, visited, skipped };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: forContinue,
+    params: [7],
+};
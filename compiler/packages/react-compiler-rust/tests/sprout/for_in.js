@@ -0,0 +1,29 @@
+// Sprout Test: for...in next to the binary `in` operator
+// Tests that `key in obj` checks and `for (k in obj)` loops don't interfere
+
+function forIn(obj, extra) {
+    const keys = [];
+    let total = 0;
+    if ("x" in obj) {
+        keys.push("has x");
+    }
+    for (const k in obj) {
+        keys.push(k);
+        if (k in extra) {
+            continue;
+        }
+        total += obj[k];
+    }
+    let last = null;
+    for (last in extra) {
+        if ("skip" in extra) {
+            break;
+        }
+    }
+    return { keys, total, last, hasY: "y" in obj };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: forIn,
+    params: [{ x: 1, y: 2, z: 3 }, { y: true, skip: true }],
+};
@@ -0,0 +1,42 @@
+// Sprout Test: Generator functions
+// Tests yield, yield*, and values sent back into a generator
+
+function* range(n) {
+    for (let i = 0; i < n; i++) {
+        yield i;
+    }
+}
+
+function* evensThenOdds(n) {
+    let i = 0;
+    while (i < n) {
+        if (i % 2 === 0) {
+            yield i;
+        }
+        i++;
+    }
+    yield* [1, 3];
+    yield;
+}
+
+function* echo() {
+    const first = yield "ready";
+    const second = yield first * 2;
+    return first + second;
+}
+
+function main() {
+    const it = echo();
+    const steps = [it.next(), it.next(5), it.next(7)];
+    return {
+        range: [...range(4)],
+        mixed: [...evensThenOdds(5)],
+        delegated: [...range(0)],
+        steps,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: main,
+    params: [],
+};
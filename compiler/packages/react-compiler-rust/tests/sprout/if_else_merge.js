@@ -0,0 +1,34 @@
+// Sprout Test: Code after an if/else
+// Tests that the code following a conditional runs exactly once on every path
+
+function describe(value, log) {
+    let kind;
+    if (typeof value === "number") {
+        kind = "number";
+        log.push("n");
+    } else if (typeof value === "string") {
+        kind = "string";
+    } else {
+        kind = "other";
+        log.push("o");
+    }
+    log.push(kind);
+    if (kind !== "other") {
+        log.push("known");
+    }
+    return kind + ":" + log.length;
+}
+
+function ifElseMerge(values) {
+    const log = [];
+    const kinds = [];
+    for (let i = 0; i < values.length; i++) {
+        kinds.push(describe(values[i], log));
+    }
+    return { kinds, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: ifElseMerge,
+    params: [[1, "a", null, 2.5, undefined]],
+};
@@ -0,0 +1,25 @@
+// Sprout Test: Objects built through Object.assign / Object.defineProperty
+// Tests that mutations made by opaque calls are visible to later reads, so
+// the object is never treated as unchanged after it was first built
+
+function imperativeObjects(props) {
+    const target = { a: props.a };
+    const before = target.a;
+    Object.assign(target, { a: props.a * 10, b: props.b });
+    const after = target.a;
+    Object.defineProperty(target, "hidden", { value: props.b + 1, enumerable: false });
+    const merged = Object.assign({}, target, { c: 3 });
+    return {
+        before,
+        after,
+        hidden: target.hidden,
+        keys: Object.keys(target),
+        merged,
+        same: Object.assign(target, {}) === target,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: imperativeObjects,
+    params: [{ a: 1, b: 2 }],
+};
@@ -0,0 +1,25 @@
+// Sprout Test: Independent memoized values
+// Tests that two values with unrelated inputs keep their own results when
+// the component is re-invoked with one input changed at a time
+
+function Summary(items, label) {
+    let total = 0;
+    for (let i = 0; i < items.length; i++) {
+        total += items[i];
+    }
+    const heading = label.toUpperCase();
+    return { total, heading };
+}
+
+function independentScopes(items, label) {
+    const first = Summary(items, label);
+    const second = Summary(items, "other");
+    const third = Summary([4, 5], "other");
+    const fourth = Summary(items, label);
+    return [first, second, third, fourth];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: independentScopes,
+    params: [[1, 2, 3], "totals"],
+};
@@ -0,0 +1,47 @@
+// Sprout Test: Labeled blocks
+// Tests `break label` out of a plain block, including from nested ifs and loops
+
+function classify(n) {
+    let kind = "unknown";
+    check: {
+        if (n < 0) {
+            kind = "negative";
+            break check;
+        }
+        if (n === 0) {
+            kind = "zero";
+            break check;
+        }
+        kind = "positive";
+    }
+    return kind;
+}
+
+function firstEven(items) {
+    let found = -1;
+    search: {
+        for (let i = 0; i < items.length; i++) {
+            if (items[i] % 2 === 0) {
+                found = items[i];
+                break search;
+            }
+        }
+        found = null;
+    }
+    return found;
+}
+
+function labeledBlocks(a, b) {
+    return {
+        negative: classify(-a),
+        zero: classify(0),
+        positive: classify(b),
+        even: firstEven([1, 3, b, 5]),
+        none: firstEven([1, 3, 5]),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: labeledBlocks,
+    params: [3, 4],
+};
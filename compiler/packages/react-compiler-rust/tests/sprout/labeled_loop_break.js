@@ -0,0 +1,60 @@
+// Sprout Test: Labeled break out of nested loops
+// Tests `break outer` from an inner loop and from a switch inside a loop
+
+function breakOuter(limit) {
+    let r = 0;
+    let i = 0;
+    outer: while (i < limit) {
+        let j = 0;
+        while (j < limit) {
+            r++;
+            if (j === 3) break outer;
+            j++;
+        }
+        r += 100;
+        i++;
+    }
+    return r;
+}
+
+function findPair(rows, target) {
+    let found = null;
+    rows: for (let i = 0; i < rows.length; i++) {
+        for (let j = 0; j < rows[i].length; j++) {
+            if (rows[i][j] === target) {
+                found = [i, j];
+                break rows;
+            }
+        }
+    }
+    return found;
+}
+
+function untilStop(commands) {
+    let seen = 0;
+    loop: for (let i = 0; i < commands.length; i++) {
+        switch (commands[i]) {
+            case "stop":
+                break loop;
+            case "skip":
+                break;
+            default:
+                seen++;
+        }
+    }
+    return seen;
+}
+
+function labeledLoopBreak(limit, target) {
+    return {
+        outer: breakOuter(limit),
+        pair: findPair([[1, 2], [3, target], [5, 6]], target),
+        missing: findPair([[1, 2]], target),
+        commands: untilStop(["go", "skip", "go", "stop", "go"]),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: labeledLoopBreak,
+    params: [10, 4],
+};
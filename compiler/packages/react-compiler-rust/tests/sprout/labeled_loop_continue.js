@@ -0,0 +1,41 @@
+// Sprout Test: Labeled continue to an outer loop
+// Tests `continue outer` from inner while and for loops, running the outer update
+
+function continueOuter(limit) {
+    let r = 0;
+    let i = 0;
+    outer: while (i < limit) {
+        i++;
+        let j = 0;
+        while (j < limit) {
+            j++;
+            if (j === 2) continue outer;
+            r += 10;
+        }
+        r += 1000;
+    }
+    return r;
+}
+
+function rowsWithoutNegatives(rows) {
+    let count = 0;
+    rows: for (let i = 0; i < rows.length; i++) {
+        for (let j = 0; j < rows[i].length; j++) {
+            if (rows[i][j] < 0) continue rows;
+        }
+        count++;
+    }
+    return count;
+}
+
+function labeledLoopContinue(limit) {
+    return {
+        outer: continueOuter(limit),
+        rows: rowsWithoutNegatives([[1, 2], [3, -1], [], [-5], [6]]),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: labeledLoopContinue,
+    params: [3],
+};
@@ -0,0 +1,39 @@
+// Sprout Test: logical assignment operators
+// Tests that the right-hand side of `??=`, `||=` and `&&=` only runs, and
+// the target is only assigned, when the current value doesn't short-circuit
+
+function sideEffect(log, value) {
+    log.calls = log.calls + 1;
+    return value;
+}
+
+function withDefault(log, value) {
+    let x = value;
+    x ??= sideEffect(log, "default");
+    return x;
+}
+
+function flags(log, state) {
+    state.ready ||= sideEffect(log, true);
+    state.count &&= sideEffect(log, state.count * 2);
+    const keys = ["label"];
+    state[keys[0]] ??= sideEffect(log, "untitled");
+    return state;
+}
+
+function logicalAssignment() {
+    const log = { calls: 0 };
+    const kept = withDefault(log, 0);
+    const keptCalls = log.calls;
+    const filled = withDefault(log, null);
+    const filledCalls = log.calls;
+    const first = flags(log, { ready: true, count: 0, label: "a" });
+    const firstCalls = log.calls;
+    const second = flags(log, { ready: false, count: 3 });
+    return { kept, keptCalls, filled, filledCalls, first, firstCalls, second, calls: log.calls };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: logicalAssignment,
+    params: [],
+};
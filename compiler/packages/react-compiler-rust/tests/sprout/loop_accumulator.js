@@ -0,0 +1,41 @@
+// Sprout Test: Loop-carried accumulators
+// Tests that values reassigned in a loop body carry over to the next iteration
+
+function loopAccumulator(items, n) {
+    let sum = 0;
+    for (let i = 0; i < items.length; i++) {
+        sum += items[i];
+    }
+
+    let product = 1;
+    let count = 0;
+    let j = 1;
+    while (j <= n) {
+        product = product * j;
+        count++;
+        j++;
+    }
+
+    let evens = 0;
+    for (let k = 0; k < items.length; k++) {
+        if (items[k] % 2 !== 0) {
+            continue;
+        }
+        evens += items[k];
+    }
+
+    let prev = 0;
+    let curr = 1;
+    for (let f = 0; f < n; f++) {
+        const next = prev + curr;
+        prev = curr;
+        curr = next;
+    }
+
+    return { sum, product, count, evens, fib: prev };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: loopAccumulator,
+    params: [[1, 2, 3, 4, 5, 6], 6],
+};
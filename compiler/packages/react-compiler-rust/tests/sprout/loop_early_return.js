@@ -0,0 +1,79 @@
+// Sprout Test: return from inside loops
+// Tests that a return in a loop body exits the function with the value
+// computed in the loop, without running further iterations
+
+function findIndex(items, target, log) {
+    for (let i = 0; i < items.length; i++) {
+        log.visited = log.visited + 1;
+        const item = items[i];
+        if (item === target) {
+            return i * 10;
+        }
+    }
+    return -1;
+}
+
+function firstLarge(values, log) {
+    let i = 0;
+    while (true) {
+        const value = values[i];
+        log.visited = log.visited + 1;
+        if (value > 100) {
+            return { value, at: i };
+        }
+        i += 1;
+    }
+}
+
+function nestedSearch(grid, log) {
+    for (let row = 0; row < grid.length; row++) {
+        for (let col = 0; col < grid[row].length; col++) {
+            log.visited = log.visited + 1;
+            if (grid[row][col] === "x") {
+                return [row, col];
+            }
+        }
+    }
+    return null;
+}
+
+function sumUntilNegative(values, log) {
+    let total = 0;
+    for (const key in values) {
+        log.visited = log.visited + 1;
+        switch (Math.sign(values[key])) {
+            case -1:
+                return total;
+            case 0:
+                continue;
+            default:
+                total += values[key];
+        }
+    }
+    return "no negatives";
+}
+
+function loopEarlyReturn() {
+    const found = { visited: 0 };
+    const missing = { visited: 0 };
+    const large = { visited: 0 };
+    const nested = { visited: 0 };
+    const sums = { visited: 0 };
+    return {
+        found: findIndex(["a", "b", "c", "d"], "b", found),
+        foundVisited: found.visited,
+        missing: findIndex(["a", "b"], "z", missing),
+        missingVisited: missing.visited,
+        large: firstLarge([5, 50, 500, 5000], large),
+        largeVisited: large.visited,
+        nested: nestedSearch([["a", "b"], ["c", "x", "x"], ["x"]], nested),
+        nestedVisited: nested.visited,
+        sum: sumUntilNegative({ a: 1, b: 0, c: 2, d: -1, e: 4 }, sums),
+        sumVisited: sums.visited,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: loopEarlyReturn,
+    params: [],
+};
@@ -0,0 +1,14 @@
+function accumulate(a, n) {
+    let s = 0;
+    let i = 0;
+    while (i < n) {
+        s = s + (a + 1);
+        i = i + 1;
+    }
+    return s;
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: accumulate,
+    params: [1n, 0],
+};
@@ -0,0 +1,29 @@
+// Sprout Test: Chained computed member access
+// Tests `matrix[i][j]` and mixed static/computed chains like `a.b[i].c`
+
+function matrixIndexing(matrix, data) {
+    const diagonal = [];
+    let sum = 0;
+    for (let i = 0; i < matrix.length; i++) {
+        diagonal.push(matrix[i][i]);
+        for (let j = 0; j < matrix[i].length; j++) {
+            sum += matrix[i][j];
+        }
+    }
+    const last = matrix.length - 1;
+    return {
+        diagonal,
+        sum,
+        corner: matrix[last][matrix[last].length - 1],
+        mixed: data.rows[1].cells[0].value,
+        deep: [[[1, 2], [3, 4]], [[5, 6], [7, 8]]][1][0][1],
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: matrixIndexing,
+    params: [
+        [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+        { rows: [{ cells: [] }, { cells: [{ value: "hit" }] }] },
+    ],
+};
@@ -0,0 +1,20 @@
+// Sprout Test: `??` mixed with `||` and `&&`
+// Tests that parenthesized mixes keep their grouping for every combination
+// of nullish, falsy and truthy operands
+
+function mixedLogical(values) {
+    const results = [];
+    for (let i = 0; i < values.length; i++) {
+        const a = values[i];
+        for (let j = 0; j < values.length; j++) {
+            const b = values[j];
+            results.push([(a ?? b) || "c", a ?? (b && "c"), (a || b) ?? "c", (a && b) ?? "c"]);
+        }
+    }
+    return results;
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: mixedLogical,
+    params: [[null, undefined, 0, "", "x"]],
+};
@@ -0,0 +1,16 @@
+// Sprout Test: Multiple declarators in one declaration
+// Tests `let a = 1, b, c = 3;` including the uninitialized binding
+
+function multipleDeclarators(x) {
+    let a = 1, b, c = 3;
+    const before = b === undefined;
+    b = a + c + x;
+    let d, e;
+    const f = 2, g = f * 2;
+    return { a, b, c, before, d, eIsUndefined: e === undefined, f, g };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: multipleDeclarators,
+    params: [10],
+};
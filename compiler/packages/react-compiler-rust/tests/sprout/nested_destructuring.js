@@ -0,0 +1,30 @@
+// Sprout Test: Nested destructuring assignments
+// Tests that every leaf binding of a nested pattern is assigned
+
+function nestedDestructuring(input) {
+    let a;
+    let b;
+    let c;
+    let e;
+    [{ a }, b, [c, { d: e }]] = input.items;
+
+    let first;
+    let label;
+    let size;
+    ({ point: [first], meta: { label, size = 3 } } = input.shape);
+
+    let deep;
+    [[[deep] = ["fallback"]]] = [[]];
+
+    return { a, b, c, e, first, label, size, deep };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: nestedDestructuring,
+    params: [
+        {
+            items: [{ a: 1 }, 2, [3, { d: 4 }]],
+            shape: { point: [5, 6], meta: { label: "box" } },
+        },
+    ],
+};
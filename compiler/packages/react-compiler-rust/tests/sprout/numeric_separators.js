@@ -0,0 +1,27 @@
+// Sprout Test: Numeric separators
+// Tests that literals written with `_` separators keep their exact values
+
+function numericSeparators(scale) {
+    const million = 1_000_000;
+    const hex = 0x1_00;
+    const binary = 0b1010_0001;
+    const octal = 0o7_7;
+    const fraction = 1_000.000_5;
+    const exponent = 1e1_0;
+    const large = 9_007_199_254_740_991;
+    return {
+        million,
+        hex,
+        binary,
+        octal,
+        fraction,
+        exponent,
+        large,
+        scaled: million * scale + hex,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: numericSeparators,
+    params: [3],
+};
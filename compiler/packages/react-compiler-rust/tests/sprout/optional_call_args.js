@@ -0,0 +1,30 @@
+// Sprout Test: Optional chains skip argument evaluation
+// Tests that a short-circuited `a?.b(arg())` never evaluates `arg()`
+
+function expensiveArg(log, label) {
+    log.push(label);
+    return label;
+}
+
+function echo(value) {
+    return value;
+}
+
+function optionalCallArgs(present) {
+    const log = [];
+    const missing = null;
+    const api = { b: echo };
+
+    const skipped = missing?.b(expensiveArg(log, "skipped")) === undefined;
+    const skippedKey = missing?.[expensiveArg(log, "key")] === undefined;
+    const skippedCall = api.c?.(expensiveArg(log, "noMethod")) === undefined;
+    const called = api?.b(expensiveArg(log, "called"));
+    const conditional = (present ? api : missing)?.b(expensiveArg(log, "conditional"));
+
+    return { skipped, skippedKey, skippedCall, called, conditional, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: optionalCallArgs,
+    params: [true],
+};
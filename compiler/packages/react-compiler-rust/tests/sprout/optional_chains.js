@@ -0,0 +1,29 @@
+// Sprout Test: Optional chains
+// Tests that a multi-level optional chain short-circuits at the first nullish link
+
+function record(log) {
+    log.push(log.length);
+    return log.length;
+}
+
+function optionalChains(start) {
+    const log = [start];
+    const full = { b: { c: record } };
+    const missing = { b: null };
+    const noMethod = { b: {} };
+
+    return {
+        called: full?.b?.c(log),
+        computed: full?.["b"]?.c(log),
+        nullishLink: missing?.b?.c(log) === undefined,
+        nullishRoot: missing.a?.b?.c(log) === undefined,
+        optionalCall: noMethod.b.c?.(log) === undefined,
+        nested: missing.b?.c(log).d.e === undefined,
+        log,
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: optionalChains,
+    params: [0],
+};
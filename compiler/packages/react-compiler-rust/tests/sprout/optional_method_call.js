@@ -0,0 +1,27 @@
+// Sprout Test: Optional method calls
+// Tests that `a.b?.()` reads `a.b` once and still calls it with `a` as `this`
+
+function counted(log) {
+    return {
+        name: "counted",
+        get greet() {
+            log.push("read");
+            return function (suffix) {
+                return this.name + suffix;
+            };
+        },
+    };
+}
+
+function optionalMethodCall(suffix) {
+    const log = [];
+    const target = counted(log);
+    const greeting = target.greet?.(suffix);
+    const missing = { name: "missing" }.greet?.(suffix);
+    return { greeting, missing, reads: log.length };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: optionalMethodCall,
+    params: ["!"],
+};
@@ -0,0 +1,34 @@
+// Sprout Test: Recursive functions
+// Tests that a function's own name still refers to itself after compilation
+
+function factorial(n) {
+    if (n <= 1) {
+        return 1;
+    }
+    return n * factorial(n - 1);
+}
+
+function countNodes(tree) {
+    if (tree == null) {
+        return 0;
+    }
+    let count = 1;
+    const children = tree.children;
+    for (let i = 0; i < children.length; i++) {
+        count += countNodes(children[i]);
+    }
+    return count;
+}
+
+function recursion(n) {
+    const tree = { children: [{ children: [] }, { children: [{ children: [] }] }] };
+    return {
+        factorial: factorial(n),
+        nodes: countNodes(tree),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: recursion,
+    params: [5],
+};
@@ -0,0 +1,29 @@
+// Sprout Test: Repeated operators over objects
+// Tests that `o + p` written twice calls `valueOf` twice, so it is not merged
+
+function counter() {
+    let count = 0;
+    return {
+        valueOf() {
+            count++;
+            return count;
+        },
+    };
+}
+
+function repeatedCoercion(start) {
+    const o = counter();
+    const p = start;
+    const first = o + p;
+    const second = o + p;
+    const negated = -o;
+    const negatedAgain = -o;
+    const same = o === o;
+    const sameAgain = o === o;
+    return [first, second, negated, negatedAgain, same, sameAgain];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: repeatedCoercion,
+    params: [1],
+};
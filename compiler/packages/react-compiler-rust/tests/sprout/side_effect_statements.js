@@ -0,0 +1,23 @@
+// Sprout Test: Side-effect-only expression statements
+// Tests that unused results of calls and stores are still evaluated
+
+function collect(x) {
+    const arr = [];
+    arr.push(x);
+    arr.push(x * 2);
+
+    const obj = {};
+    obj.y = 1;
+    obj["z"] = arr.length;
+
+    let i = 0;
+    i++;
+    i++;
+
+    return { arr, obj, i };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: collect,
+    params: [21],
+};
@@ -0,0 +1,24 @@
+// Sprout Test: Spreading a call's result into another call
+// Tests that the spread source is called once and its values are forwarded
+
+function collect() {
+    return Array.from(arguments);
+}
+
+function getArgs(calls, values) {
+    calls.push(values.length);
+    return values;
+}
+
+function spreadCallResult(values) {
+    const calls = [];
+    const forwarded = collect(...getArgs(calls, values));
+    const mixed = collect(0, ...getArgs(calls, values), calls.length);
+    const method = Math.max(...getArgs(calls, values));
+    return [forwarded, mixed, method, calls];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: spreadCallResult,
+    params: [[1, 2, 3]],
+};
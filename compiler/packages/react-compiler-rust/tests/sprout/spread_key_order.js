@@ -0,0 +1,17 @@
+// Sprout Test: Object spread vs. explicit keys
+// Tests that later properties win, whether they come from a spread or a key
+
+function spreadKeyOrder(base) {
+    const keyWins = { ...{ x: 1 }, x: 2 };
+    const spreadWins = { x: 2, ...{ x: 1 } };
+    const fromParam = { ...base, y: "explicit" };
+    const paramWins = { y: "explicit", ...base };
+    const sandwich = { x: "first", ...base, x: "last" };
+    const order = Object.keys({ z: 0, ...base, a: 1 });
+    return { keyWins, spreadWins, fromParam, paramWins, sandwich, order };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: spreadKeyOrder,
+    params: [{ x: "base", y: "base" }],
+};
@@ -0,0 +1,22 @@
+// Sprout Test: Spread arguments in the middle of a call
+// Tests that regular arguments around a spread keep their order
+
+function collect() {
+    return Array.from(arguments);
+}
+
+function spreadMiddleArgs(rest) {
+    const obj = { collect };
+    return {
+        literal: collect(1, ...[2, 3], 4),
+        param: collect(0, ...rest, 9),
+        multiple: collect(...rest, 5, ...rest),
+        method: obj.collect(1, ...rest, 4),
+        empty: collect(1, ...[], 2),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: spreadMiddleArgs,
+    params: [[2, 3]],
+};
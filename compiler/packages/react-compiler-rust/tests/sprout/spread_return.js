@@ -0,0 +1,29 @@
+// Sprout Test: Spread arrays in return position
+// Tests that `return [...a, b]` keeps the spread source, order, and length
+
+function appendExtra(items, extra) {
+    return [...items, extra];
+}
+
+function surround(items, first, last) {
+    return [first, ...items, last];
+}
+
+function spreadReturn(items) {
+    const appended = appendExtra(items, 4);
+    const wrapped = surround(items, 0, 9);
+    const doubled = [...appended, ...wrapped];
+    const empty = appendExtra([], "only");
+    return {
+        appended,
+        wrapped,
+        doubled,
+        empty,
+        lengths: [appended.length, wrapped.length, doubled.length, empty.length],
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: spreadReturn,
+    params: [[1, 2, 3]],
+};
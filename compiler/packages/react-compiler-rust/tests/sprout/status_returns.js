@@ -0,0 +1,42 @@
+// Sprout Test: A component-like function with an early return per status
+// Tests that each loading/error/data path returns its own shape
+
+function StatusView(props) {
+    const status = props.status;
+    if (status === "loading") {
+        const spinner = { type: "spinner", size: props.size ?? "medium" };
+        return { kind: "loading", children: [spinner] };
+    }
+    if (status === "error") {
+        const message = "Failed: " + props.error;
+        return { kind: "error", message, retry: props.retry === true };
+    }
+    if (status === "empty") {
+        return null;
+    }
+    const rows = [];
+    for (let i = 0; i < props.items.length; i++) {
+        rows.push({ key: i, label: props.items[i] });
+    }
+    return { kind: "data", rows, count: rows.length };
+}
+
+function statusReturns(cases) {
+    const results = [];
+    for (let i = 0; i < cases.length; i++) {
+        results.push(StatusView(cases[i]));
+    }
+    return results;
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: statusReturns,
+    params: [[
+        { status: "loading" },
+        { status: "loading", size: "large" },
+        { status: "error", error: "timeout", retry: true },
+        { status: "empty" },
+        { status: "ready", items: ["a", "b"] },
+        { status: "ready", items: [] },
+    ]],
+};
@@ -0,0 +1,46 @@
+// Sprout Test: Control flow that rejoins after switches and nested ifs
+// Tests that the code after each structured statement runs once, with the
+// values assigned on whichever path was taken
+
+function score(kind, amount, log) {
+    let base = 0;
+    switch (kind) {
+        case "small":
+            base = 1;
+            if (amount > 10) {
+                base = 2;
+            }
+            break;
+        case "large":
+            if (amount > 100) {
+                base = 20;
+            } else if (amount > 50) {
+                base = 10;
+            } else {
+                base = 5;
+            }
+            break;
+        default:
+            log.push("unknown " + kind);
+    }
+    log.push(kind + "=" + base);
+    let bonus = amount > 0 ? (base > 5 ? 3 : 1) : 0;
+    if (bonus > 1) {
+        log.push("bonus");
+    }
+    return base * amount + bonus;
+}
+
+function structuredJoins(inputs) {
+    const log = [];
+    const totals = [];
+    for (let i = 0; i < inputs.length; i++) {
+        totals.push(score(inputs[i][0], inputs[i][1], log));
+    }
+    return { totals, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: structuredJoins,
+    params: [[["small", 5], ["small", 20], ["large", 200], ["large", 60], ["large", 1], ["other", 3], ["small", 0]]],
+};
@@ -0,0 +1,37 @@
+// Sprout Test: Switch with literal case labels
+// Tests that number, string, negative and null case tests are matched
+
+function describe(value) {
+    let label = "other";
+    switch (value) {
+        case 0:
+            label = "zero";
+            break;
+        case -1:
+            label = "minus one";
+            break;
+        case "0":
+            label = "zero string";
+            break;
+        case null:
+            label = "null";
+            break;
+        case true:
+            label = "true";
+            break;
+    }
+    return label;
+}
+
+function switchCaseLabels(values) {
+    const results = [];
+    for (let i = 0; i < values.length; i++) {
+        results.push(describe(values[i]));
+    }
+    return results;
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: switchCaseLabels,
+    params: [[0, -1, "0", null, true, 1, undefined, "true"]],
+};
@@ -0,0 +1,34 @@
+// Sprout Test: `default` before other cases
+// Tests that a default in the middle of a switch is only taken when no case
+// matches, and that it falls through into the cases written after it
+
+function route(value, log) {
+    let result = "";
+    switch (value) {
+        case 1:
+            result += "one;";
+        default:
+            result += "default;";
+            log.push("default " + value);
+        case 2:
+            result += "two;";
+            break;
+        case 3:
+            result += "three;";
+    }
+    return result;
+}
+
+function switchDefaultFirst(values) {
+    const log = [];
+    const results = [];
+    for (let i = 0; i < values.length; i++) {
+        results.push(route(values[i], log));
+    }
+    return { results, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: switchDefaultFirst,
+    params: [[1, 2, 3, 4, "2"]],
+};
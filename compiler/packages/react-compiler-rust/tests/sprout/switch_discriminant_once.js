@@ -0,0 +1,51 @@
+// Sprout Test: switch discriminant with side effects
+// Tests that the discriminant is evaluated once, whichever case matches,
+// including when no case matches and when the switch runs in a loop
+
+function next(counter) {
+    counter.calls = counter.calls + 1;
+    return counter.values[counter.calls - 1];
+}
+
+function label(counter) {
+    switch (next(counter)) {
+        case "a":
+            return "first";
+        case "b":
+        case "c":
+            return "second or third";
+        case next.name:
+            return "function name";
+        default:
+            return "other";
+    }
+}
+
+function countMatches(counter) {
+    let matches = 0;
+    for (let i = 0; i < 4; i++) {
+        switch (next(counter)) {
+            case 1:
+                matches += 1;
+                break;
+            case 2:
+                matches += 10;
+            default:
+                matches += 100;
+        }
+    }
+    return matches;
+}
+
+function switchDiscriminantOnce() {
+    const labels = { calls: 0, values: ["a", "c", "next", "z", "b"] };
+    const results = [label(labels), label(labels), label(labels), label(labels), label(labels)];
+    const loop = { calls: 0, values: [2, 1, 3, 1] };
+    const matches = countMatches(loop);
+    return { results, labelCalls: labels.calls, matches, loopCalls: loop.calls };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: switchDiscriminantOnce,
+    params: [],
+};
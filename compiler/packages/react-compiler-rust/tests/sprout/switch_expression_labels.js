@@ -0,0 +1,46 @@
+// Sprout Test: Switch with expression case labels
+// Tests that call, member and binary case tests are matched, and that a
+// case test is only evaluated when the cases before it didn't match
+
+function first(calls) {
+    calls.push("first");
+    return "a";
+}
+
+function last(calls) {
+    calls.push("last");
+    return "z";
+}
+
+function classify(value, keys, calls) {
+    let label = "other";
+    switch (value) {
+        case first(calls):
+            label = "first";
+            break;
+        case keys.second:
+            label = "second";
+            break;
+        case keys.base + 1:
+            label = "after base";
+            break;
+        case last(calls):
+            label = "last";
+            break;
+    }
+    return label;
+}
+
+function switchExpressionLabels(values, keys) {
+    const calls = [];
+    const results = [];
+    for (let i = 0; i < values.length; i++) {
+        results.push(classify(values[i], keys, calls));
+    }
+    return [results, calls];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: switchExpressionLabels,
+    params: [["a", "b", 11, "z", "q"], { second: "b", base: 10 }],
+};
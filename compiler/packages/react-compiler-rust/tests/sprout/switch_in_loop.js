@@ -0,0 +1,40 @@
+// Sprout Test: break and continue in a switch inside a loop
+// Tests that `continue` in a case continues the enclosing loop, skipping the
+// rest of the body, while `break` only leaves the switch
+
+function switchInLoop(limit) {
+    let res = 0;
+    let after = 0;
+    for (let i = 0; i < limit; i++) {
+        switch (i % 3) {
+            case 0:
+                res += 1;
+                break;
+            case 1:
+                res += 10;
+                continue;
+            case 2:
+                res += 100;
+                break;
+        }
+        after += 1;
+    }
+    let n = 0;
+    let log = "";
+    while (n < 4) {
+        n++;
+        switch (n) {
+            case 2:
+                continue;
+            default:
+                log += n;
+        }
+        log += ";";
+    }
+    return { res, after, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: switchInLoop,
+    params: [5],
+};
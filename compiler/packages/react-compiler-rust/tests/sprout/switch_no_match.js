@@ -0,0 +1,30 @@
+// Sprout Test: Switch with no matching case and no default
+// Tests that an unmatched discriminant runs no case body and continues after the switch
+
+function classify(value, log) {
+    let label = "none";
+    switch (value) {
+        case 1:
+            log.push("one");
+            label = "one";
+            break;
+        case 2:
+            log.push("two");
+            label = "two";
+    }
+    log.push("after");
+    return label;
+}
+
+function switchNoMatch(value) {
+    const log = [];
+    const unmatched = classify(value, log);
+    const matched = classify(2, log);
+    const other = classify("1", log);
+    return { unmatched, matched, other, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: switchNoMatch,
+    params: [7],
+};
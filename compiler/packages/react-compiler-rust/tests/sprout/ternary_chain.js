@@ -0,0 +1,23 @@
+// Sprout Test: A 4-level ternary chain followed by shared code
+// Tests that every leaf of the chain reaches the code after it with its own value
+
+function classify(a, b, c, d) {
+    const x = a ? 1 : b ? 2 : c ? 3 : d ? 4 : 5;
+    const y = x * 2;
+    const label = y > 6 ? "high" : y > 2 ? "mid" : "low";
+    return [x, y, label];
+}
+
+function ternaryChain(cases) {
+    const results = [];
+    for (let i = 0; i < cases.length; i++) {
+        const flags = cases[i];
+        results.push(classify(flags[0], flags[1], flags[2], flags[3]));
+    }
+    return results;
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: ternaryChain,
+    params: [[[1, 0, 0, 0], [0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 1], [0, 0, 0, 0], [1, 1, 1, 1], [0, 0, 1, 1]]],
+};
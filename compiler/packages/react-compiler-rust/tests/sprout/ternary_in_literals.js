@@ -0,0 +1,25 @@
+// Sprout Test: Conditional expressions inside object and array literals
+// Tests that only the taken branch runs and the literal gets the merged value
+
+function f(log) {
+    log.push("f");
+    return "from f";
+}
+
+function g(log) {
+    log.push("g");
+    return "from g";
+}
+
+function ternaryInLiterals(cond) {
+    const log = [];
+    const arr = [cond ? f(log) : g(log), !cond ? f(log) : g(log)];
+    const obj = { x: cond ? f(log) : g(log), y: 1, z: cond ? [f(log)] : { g: g(log) } };
+    const nested = [1, cond ? (log.length > 2 ? "many" : "few") : "none", 3];
+    return { arr, obj, nested, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: ternaryInLiterals,
+    params: [true],
+};
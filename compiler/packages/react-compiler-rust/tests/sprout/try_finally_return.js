@@ -0,0 +1,81 @@
+// Sprout Test: return, break and continue inside try/finally
+// Tests that the finally block runs before leaving the try, after the
+// return value has been read
+
+function earlyReturn(log, value) {
+    let x = value;
+    try {
+        log.push("try");
+        if (x > 0) {
+            return x;
+        }
+        x = -x;
+    } finally {
+        log.push("finally " + x);
+        x = 100;
+    }
+    log.push("after");
+    return x;
+}
+
+function loopExits(log, items) {
+    let total = 0;
+    for (let i = 0; i < items.length; i++) {
+        try {
+            if (items[i] === 0) {
+                continue;
+            }
+            if (items[i] < 0) {
+                break;
+            }
+            total += items[i];
+        } finally {
+            log.push("item " + i);
+        }
+    }
+    return total;
+}
+
+function nestedTry(log, flag) {
+    try {
+        try {
+            if (flag) {
+                return "inner";
+            }
+        } finally {
+            log.push("inner finally");
+        }
+        log.push("between");
+    } finally {
+        log.push("outer finally");
+    }
+    return "outer";
+}
+
+function finallyOverrides(log) {
+    try {
+        log.push("try");
+        return "try";
+    } finally {
+        log.push("finally");
+        return "finally";
+    }
+}
+
+function tryFinallyReturn() {
+    const log = [];
+    const results = [
+        earlyReturn(log, 5),
+        earlyReturn(log, -3),
+        loopExits(log, [1, 0, 2, -1, 4]),
+        nestedTry(log, true),
+        nestedTry(log, false),
+        finallyOverrides(log),
+    ];
+    return { results, log };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: tryFinallyReturn,
+    params: [],
+};
@@ -0,0 +1,43 @@
+// Sprout Test: typeof results used as values
+// Tests that typeof produces a string that can be compared and branched on,
+// including for a global that was never declared
+
+function describe(value) {
+    const type = typeof value;
+    if (type === "number") {
+        return "number:" + value;
+    }
+    if (type === "string") {
+        return "string:" + value;
+    }
+    return type;
+}
+
+function defineLateGlobal() {
+    globalThis.lateGlobal = 1;
+}
+
+function typeofValues(values) {
+    const results = [];
+    for (let i = 0; i < values.length; i++) {
+        results.push(describe(values[i]));
+    }
+    const missing = typeof undeclaredGlobal;
+    const missingIsUndefined = typeof undeclaredGlobal === "undefined";
+    let declared;
+    const unset = typeof declared;
+    let changed = 0;
+    if (values.length > 2) {
+        changed = "many";
+    }
+    // A call can define the global between two reads
+    const before = typeof lateGlobal;
+    defineLateGlobal();
+    const after = typeof lateGlobal;
+    return [results, missing, missingIsUndefined, unset, typeof changed, typeof undefined, before, after];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: typeofValues,
+    params: [[1, "a", true, null, undefined, [2]]],
+};
@@ -0,0 +1,38 @@
+// Sprout Test: Uninitialized declarations
+// Tests `let x;` followed by a conditional assignment and a read afterwards
+
+function pick(flag, value) {
+    let x;
+    if (flag) {
+        x = value;
+    }
+    return x === undefined ? "unset" : x;
+}
+
+function lastMatch(items, target) {
+    let found;
+    for (let i = 0; i < items.length; i++) {
+        let candidate;
+        if (items[i] === target) {
+            candidate = i;
+        }
+        if (candidate !== undefined) {
+            found = candidate;
+        }
+    }
+    return found === undefined ? -1 : found;
+}
+
+function uninitializedDeclarations(value) {
+    return {
+        set: pick(true, value),
+        unset: pick(false, value),
+        match: lastMatch([1, value, 3, value], value),
+        noMatch: lastMatch([1, 2, 3], value),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: uninitializedDeclarations,
+    params: [7],
+};
@@ -0,0 +1,25 @@
+// Sprout Test: void expressions
+// Tests that `void expr` still evaluates its operand and yields undefined
+
+function doThing(log, value) {
+    log.push(value);
+    return value * 2;
+}
+
+function voidExpressions(x) {
+    const log = [];
+    const result = void doThing(log, x);
+    void doThing(log, x + 1);
+    const zero = void 0;
+    return {
+        log,
+        resultIsUndefined: result === undefined,
+        zeroIsUndefined: zero === undefined,
+        type: typeof void doThing(log, 0),
+    };
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: voidExpressions,
+    params: [5],
+};
@@ -4,11 +4,14 @@
 //! Uses Node.js to execute both versions and compares results.
 
 use react_compiler_rust::compile;
-use react_compiler_rust::sprout::verify_fixture;
+use react_compiler_rust::sprout::{verify_fixture_runs, MOCK_MEMO_CACHE};
 use oxc_span::SourceType;
 use std::fs;
 use std::path::PathBuf;
 
+/// How many times each fixture's entrypoint is called, sharing one cache
+const RUNS: usize = 3;
+
 fn sprout_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/sprout")
 }
@@ -29,12 +32,27 @@ fn run_sprout_test(filename: &str) -> Result<(), String> {
     let path = sprout_dir().join(filename);
     let original_code = fs::read_to_string(&path)
         .map_err(|e| format!("Read error: {}", e))?;
-    
-    let fixture_entrypoint = extract_fixture_entrypoint(&original_code)
+    run_sprout_source(filename, &original_code, &original_code, SourceType::mjs())
+}
+
+/// Run sprout verification for TypeScript, which Node can't run as written:
+/// the compiled `typescript` is compared against the same code with the
+/// types removed.
+fn run_typescript_sprout_test(name: &str, typescript: &str, javascript: &str) -> Result<(), String> {
+    run_sprout_source(name, javascript, typescript, SourceType::ts())
+}
+
+fn run_sprout_source(
+    filename: &str,
+    original_code: &str,
+    source: &str,
+    source_type: SourceType,
+) -> Result<(), String> {
+    let fixture_entrypoint = extract_fixture_entrypoint(original_code)
         .ok_or_else(|| "No FIXTURE_ENTRYPOINT found".to_string())?;
     
     let compiled_result = std::panic::catch_unwind(|| {
-        compile(&original_code, SourceType::mjs())
+        compile(source, source_type)
     });
     
     let mut compiled_code = match compiled_result {
@@ -53,10 +71,10 @@ fn run_sprout_test(filename: &str) -> Result<(), String> {
     };
     
     // Mock _c function and append entrypoint
-    let mock_cache = "function _c(size) { return new Array(size).fill(undefined); }";
-    compiled_code = format!("{}\n{}\n\n{}", mock_cache, compiled_code, fixture_entrypoint);
+    compiled_code = format!("{}\n{}\n\n{}", MOCK_MEMO_CACHE, compiled_code, fixture_entrypoint);
     
-    let result = verify_fixture(&original_code, &compiled_code);
+    // Later runs read back what the first one memoized
+    let result = verify_fixture_runs(original_code, &compiled_code, RUNS);
     
     if result.passed {
         println!("✓ {} - Output: {}", filename, result.original_output.trim());
@@ -144,3 +162,398 @@ fn sprout_template_literals() {
     let result = run_sprout_test("template_literals.js");
     assert!(result.is_ok(), "{}", result.unwrap_err());
 }
+
+#[test]
+fn sprout_side_effect_statements() {
+    let result = run_sprout_test("side_effect_statements.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_assignment_in_condition() {
+    let result = run_sprout_test("assignment_in_condition.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_computed_assignment_order() {
+    let result = run_sprout_test("computed_assignment_order.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_array_holes() {
+    let result = run_sprout_test("array_holes.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_arguments_object() {
+    let result = run_sprout_test("arguments_object.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_generators() {
+    let result = run_sprout_test("generators.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_labeled_blocks() {
+    let result = run_sprout_test("labeled_blocks.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_optional_chains() {
+    let result = run_sprout_test("optional_chains.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_delete_computed() {
+    let result = run_sprout_test("delete_computed.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_void_expressions() {
+    let result = run_sprout_test("void_expressions.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_spread_middle_args() {
+    let result = run_sprout_test("spread_middle_args.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_loop_accumulator() {
+    let result = run_sprout_test("loop_accumulator.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_constant_folding() {
+    let result = run_sprout_test("constant_folding.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_recursion() {
+    let result = run_sprout_test("recursion.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_multiple_declarators() {
+    let result = run_sprout_test("multiple_declarators.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_uninitialized_declarations() {
+    let result = run_sprout_test("uninitialized_declarations.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_for_in() {
+    let result = run_sprout_test("for_in.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_call_result_access() {
+    let result = run_sprout_test("call_result_access.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_ternary_in_literals() {
+    let result = run_sprout_test("ternary_in_literals.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_matrix_indexing() {
+    let result = run_sprout_test("matrix_indexing.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_optional_call_args() {
+    let result = run_sprout_test("optional_call_args.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_spread_return() {
+    let result = run_sprout_test("spread_return.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_switch_no_match() {
+    let result = run_sprout_test("switch_no_match.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_numeric_separators() {
+    let result = run_sprout_test("numeric_separators.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_destructuring_defaults() {
+    let result = run_sprout_test("destructuring_defaults.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_nested_destructuring() {
+    let result = run_sprout_test("nested_destructuring.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_independent_scopes() {
+    let result = run_sprout_test("independent_scopes.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_delete_result() {
+    let result = run_sprout_test("delete_result.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_for_continue() {
+    let result = run_sprout_test("for_continue.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_switch_in_loop() {
+    let result = run_sprout_test("switch_in_loop.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_exponent_assignment() {
+    let result = run_sprout_test("exponent_assignment.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_imperative_objects() {
+    let result = run_sprout_test("imperative_objects.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_spread_key_order() {
+    let result = run_sprout_test("spread_key_order.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_mixed_logical() {
+    let result = run_sprout_test("mixed_logical.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_ternary_chain() {
+    let result = run_sprout_test("ternary_chain.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_if_else_merge() {
+    let result = run_sprout_test("if_else_merge.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_structured_joins() {
+    let result = run_sprout_test("structured_joins.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_switch_default_first() {
+    let result = run_sprout_test("switch_default_first.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_status_returns() {
+    let result = run_sprout_test("status_returns.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_switch_case_labels() {
+    let result = run_sprout_test("switch_case_labels.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_switch_expression_labels() {
+    let result = run_sprout_test("switch_expression_labels.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_empty_bodies() {
+    let result = run_sprout_test("empty_bodies.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_spread_call_result() {
+    let result = run_sprout_test("spread_call_result.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_typeof_values() {
+    let result = run_sprout_test("typeof_values.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_for_comma_update() {
+    let result = run_sprout_test("for_comma_update.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_conditional_member_target() {
+    let result = run_sprout_test("conditional_member_target.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_try_finally_return() {
+    let result = run_sprout_test("try_finally_return.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_switch_discriminant_once() {
+    let result = run_sprout_test("switch_discriminant_once.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_logical_assignment() {
+    let result = run_sprout_test("logical_assignment.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_loop_early_return() {
+    let result = run_sprout_test("loop_early_return.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_array_method_chain() {
+    let result = run_sprout_test("array_method_chain.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_loop_invariant_zero_iterations() {
+    let result = run_sprout_test("loop_invariant_zero_iterations.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_labeled_loop_break() {
+    let result = run_sprout_test("labeled_loop_break.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_labeled_loop_continue() {
+    let result = run_sprout_test("labeled_loop_continue.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_optional_method_call() {
+    let result = run_sprout_test("optional_method_call.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_optional_chain_non_null() {
+    let typescript = r#"
+function nonNull(o: { b?: number } | null) {
+    return [o?.b!, o?.["b"]!];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: (value: number) => [nonNull(null), nonNull({ b: value })],
+    params: [1],
+};
+"#;
+    let javascript = r#"
+function nonNull(o) {
+    return [o?.b, o?.["b"]];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: (value) => [nonNull(null), nonNull({ b: value })],
+    params: [1],
+};
+"#;
+    let result = run_typescript_sprout_test("optional_chain_non_null", typescript, javascript);
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_optional_chain_type_wrappers() {
+    let typescript = include_str!("../fixtures/optional_chain_assertions.ts").to_string()
+        + r#"
+const FIXTURE_ENTRYPOINT = {
+    fn: (value: number) => [optionalChainAssertions(null), optionalChainAssertions({ b: { c: value } })],
+    params: [1],
+};
+"#;
+    let javascript = r#"
+function optionalChainAssertions(o) {
+    const asserted = o?.b.c;
+    const computed = o?.["b"]["c"];
+    const cast = (o?.b)?.c;
+    const parenthesized = o ? (o?.b).c : 0;
+    return [asserted, computed, cast, parenthesized];
+}
+
+const FIXTURE_ENTRYPOINT = {
+    fn: (value) => [optionalChainAssertions(null), optionalChainAssertions({ b: { c: value } })],
+    params: [1],
+};
+"#;
+    let result = run_typescript_sprout_test("optional_chain_assertions", &typescript, javascript);
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_repeated_coercion() {
+    let result = run_sprout_test("repeated_coercion.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
+
+#[test]
+fn sprout_empty_objects() {
+    let result = run_sprout_test("empty_objects.js");
+    assert!(result.is_ok(), "{}", result.unwrap_err());
+}
@@ -0,0 +1,34 @@
+use oxc_span::SourceType;
+use react_compiler_rust::{compile, compile_with_timings};
+
+const COMPONENT: &str = r#"
+function TodoList(props) {
+    const items = props.items;
+    const visible = [];
+    let done = 0;
+    for (let i = 0; i < items.length; i++) {
+        const item = items[i];
+        if (item.done) {
+            done += 1;
+        } else {
+            visible.push({ id: item.id, label: `${props.prefix}${item.text}` });
+        }
+    }
+    switch (props.filter) {
+        case "done":
+            return { count: done };
+        default:
+            return { visible, count: visible.length, done };
+    }
+}
+"#;
+
+#[test]
+fn test_every_phase_is_timed() {
+    let (code, timings) = compile_with_timings(COMPONENT, SourceType::jsx()).unwrap();
+
+    assert_eq!(code, compile(COMPONENT, SourceType::jsx()).unwrap());
+    for (phase, duration) in timings.phases() {
+        assert!(!duration.is_zero(), "{} took no time", phase);
+    }
+}
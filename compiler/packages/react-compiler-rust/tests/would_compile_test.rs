@@ -0,0 +1,52 @@
+use oxc_span::SourceType;
+use react_compiler_rust::would_compile;
+
+#[test]
+fn test_would_compile_components_and_hooks_only() {
+    let result = would_compile(
+        r#"
+function Greeting(props) {
+    const message = "Hello, " + props.name;
+    return { message };
+}
+
+function useCounter(initial) {
+    const state = useState(initial);
+    return state;
+}
+
+function formatName(user) {
+    return user.first + " " + user.last;
+}
+
+function Clock(props) {
+    const format = (date) => date.toISOString();
+    return format(props.now);
+}
+
+function Throws(props) {
+    if (!props.value) {
+        throw new Error("missing value");
+    }
+    return props.value;
+}
+"#,
+        SourceType::mjs(),
+    );
+
+    assert_eq!(
+        result,
+        [
+            ("Greeting".to_string(), true),
+            ("useCounter".to_string(), true),
+            ("formatName".to_string(), false),
+            ("Clock".to_string(), false),
+            ("Throws".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn test_would_compile_parse_error() {
+    assert!(would_compile("function Broken( {", SourceType::mjs()).is_empty());
+}